@@ -1,5 +1,8 @@
 use arcis_imports::*;
 
+// The shared circuit logic (poker-shared) is pasted into the module below,
+// so the same code runs here in MPC and in plain Rust on the program side
+poker_shared::circuit_logic! {
 #[encrypted]
 mod circuits {
     use arcis_imports::*;
@@ -11,101 +14,191 @@ mod circuits {
     // 1. Fair deck shuffling without any single party controlling the outcome
     // 2. Encrypted card dealing where only the recipient can decrypt
     // 3. Secure card reveal at showdown
+    //
+    // Each instruction only moves data in and out of MPC; the logic it runs
+    // comes from `circuit_logic!` and is tested in plain Rust there.
     // ============================================================================
 
+    /// The shuffled deck, held as MXE-owned state (`Enc<Mxe, Deck>`)
+    /// Stored on-chain in the Deck account; circuits that deal from it take a
+    /// reference to that ciphertext, so no client ever holds the full deck
+    pub struct Deck {
+        cards: [u8; 52],
+    }
+
     /// Input for deck shuffling
-    /// Each player contributes entropy to ensure fairness
+    /// Each seated player contributes entropy to ensure fairness; slots past
+    /// the seated count are padding and are ignored
     pub struct ShuffleInput {
-        entropy_p1: [u8; 32],  // Player 1 entropy
-        entropy_p2: [u8; 32],  // Player 2 entropy
-        entropy_p3: [u8; 32],  // Player 3 entropy (can be zero if < 3 players)
-        entropy_p4: [u8; 32],  // Player 4 entropy (can be zero if < 4 players)
-        entropy_p5: [u8; 32],  // Player 5 entropy (can be zero if < 5 players)
-        entropy_p6: [u8; 32],  // Player 6 entropy (can be zero if < 6 players)
+        entropy: [[u8; 32]; MAX_PLAYERS],
     }
 
     /// Shuffle a 52-card deck using Fisher-Yates algorithm in MPC
     /// This ensures no single party can predict or manipulate the shuffle
     #[instruction]
-    pub fn shuffle_deck(input_ctxt: Enc<Shared, ShuffleInput>) -> Enc<Shared, [u8; 52]> {
+    pub fn shuffle_deck(
+        input_ctxt: Enc<Shared, ShuffleInput>,
+        active_count: u8,
+        mxe: Mxe,
+    ) -> Enc<Mxe, Deck> {
         let input = input_ctxt.to_arcis();
-        
-        // Combine all player entropy via addition (mod 256)
-        let mut combined_entropy = input.entropy_p1;
-        for i in 0..32 {
-            combined_entropy[i] = (combined_entropy[i] as u16
-                + input.entropy_p2[i] as u16
-                + input.entropy_p3[i] as u16
-                + input.entropy_p4[i] as u16
-                + input.entropy_p5[i] as u16
-                + input.entropy_p6[i] as u16) as u8;
-        }
-        
-        // Initialize ordered deck (0-51)
-        let mut deck = [0u8; 52];
-        for i in 0..52 {
-            deck[i] = i as u8;
-        }
-        
-        // Fisher-Yates shuffle using combined entropy
-        for i in (1..52).rev() {
-            // Generate pseudo-random index from entropy
-            let entropy_idx = (i % 32) as usize;
-            let random_byte = combined_entropy[entropy_idx];
-            let j = (random_byte as usize) % (i + 1);
-            
-            // Swap deck[i] with deck[j]
-            let temp = deck[i];
-            deck[i] = deck[j];
-            deck[j] = temp;
-            
-            // Mix entropy for next iteration (simple hash)
-            combined_entropy = hash_entropy(combined_entropy);
-        }
-        
-        // Return shuffled deck (shared among all MPC nodes)
-        input_ctxt.owner.from_arcis(deck)
+
+        let cards = shuffled_deck(&input.entropy, active_count, &node_draws());
+
+        // Shuffled deck is owned by the MXE, never by a client
+        mxe.from_arcis(Deck { cards })
     }
 
-    /// Input for dealing a card to a specific player
-    pub struct DealCardInput {
-        shuffled_deck: [u8; 52],  // The shuffled deck
-        card_index: u8,            // Which card to deal (0-51)
+    /// Shuffle a 36-card short deck (sixes through aces) for 6+ Hold'em
+    /// Output keeps the 52-slot layout so the deal circuits are shared;
+    /// slots past the short deck hold NO_CARD
+    #[instruction]
+    pub fn shuffle_short_deck(
+        input_ctxt: Enc<Shared, ShuffleInput>,
+        active_count: u8,
+        mxe: Mxe,
+    ) -> Enc<Mxe, Deck> {
+        let input = input_ctxt.to_arcis();
+
+        let cards = shuffled_short_deck(&input.entropy, active_count, &node_draws());
+
+        mxe.from_arcis(Deck { cards })
     }
 
     /// Deal a card from the shuffled deck
-    /// The card is encrypted and only the recipient can decrypt it
+    /// The card is encrypted to the recipient's registered x25519 key, so
+    /// only the recipient can decrypt it
     #[instruction]
     pub fn deal_card(
-        input_ctxt: Enc<Shared, DealCardInput>
+        deck_ctxt: Enc<Mxe, &Deck>,
+        card_index: u8,
+        receiver: Shared,
     ) -> Enc<Shared, u8> {
-        let input = input_ctxt.to_arcis();
-        
-        // Get the card at the specified index
-        let card = input.shuffled_deck[input.card_index as usize];
-        
-        // Return encrypted card
-        input_ctxt.owner.from_arcis(card)
+        let deck = deck_ctxt.to_arcis();
+
+        // Return card encrypted for the receiving player
+        receiver.from_arcis(card_at(&deck.cards, card_index))
     }
 
-    /// Input for revealing multiple cards at showdown
-    pub struct RevealCardsInput {
-        card1: u8,  // First hole card
-        card2: u8,  // Second hole card
+    /// Deal a face-up card (seven-card stud upcards)
+    /// The card is revealed to everyone instead of being encrypted to the owner
+    #[instruction]
+    pub fn deal_up_card(
+        deck_ctxt: Enc<Mxe, &Deck>,
+        card_index: u8,
+    ) -> u8 {
+        let deck = deck_ctxt.to_arcis();
+
+        card_at(&deck.cards, card_index).reveal()
+    }
+
+    /// Deal `count` consecutive cards from the shuffled deck, starting at
+    /// deck position `start_index` (2 for Hold'em, 4-6 for Omaha)
+    /// Unused slots are filled with NO_CARD; the hand is encrypted to the
+    /// receiving player's x25519 key
+    #[instruction]
+    pub fn deal_hole_cards(
+        deck_ctxt: Enc<Mxe, &Deck>,
+        start_index: u8,
+        count: u8,
+        receiver: Shared,
+    ) -> Enc<Shared, [u8; MAX_HOLE_CARDS]> {
+        let deck = deck_ctxt.to_arcis();
+
+        receiver.from_arcis(hole_cards(&deck.cards, start_index, count))
+    }
+
+    /// Burn one card and publicly reveal the next `count` (3 for the flop,
+    /// 1 for the turn and river) from the MXE deck
+    /// `burn_index` is the deck position of the burn card; it is never revealed.
+    /// Unused slots are NO_CARD
+    #[instruction]
+    pub fn reveal_community(
+        deck_ctxt: Enc<Mxe, &Deck>,
+        burn_index: u8,
+        count: u8,
+    ) -> [u8; MAX_BOARD_REVEAL] {
+        let deck = deck_ctxt.to_arcis();
+
+        board_cards(&deck.cards, burn_index, count).reveal()
+    }
+
+    /// Swap each discarded card with the next undealt card in the deck
+    /// Discards land in the dealt part of the deck and are never revealed;
+    /// the player's hand positions keep pointing at their (new) cards.
+    /// Returns the updated MXE deck and the new hand for the player
+    /// Positions and discard choices are public; only card values are secret
+    #[instruction]
+    pub fn draw_cards(
+        deck_ctxt: Enc<Mxe, Deck>,
+        hand_positions: [u8; DRAW_HAND_CARDS], // Deck positions holding the player's hand
+        discard: [bool; DRAW_HAND_CARDS],      // Which hand slots are thrown away
+        cursor: u8,                            // Next undealt deck position
+        receiver: Shared,
+    ) -> (Enc<Mxe, Deck>, Enc<Shared, [u8; DRAW_HAND_CARDS]>) {
+        let deck = deck_ctxt.to_arcis();
+
+        let (cards, hand) = drawn_hand(&deck.cards, &hand_positions, &discard, cursor);
+
+        (deck_ctxt.owner.from_arcis(Deck { cards }), receiver.from_arcis(hand))
+    }
+
+    /// Reshuffle discards together with the remaining stock when a draw
+    /// needs more cards than are left
+    /// `start` is the first discard position (reshuffle start..52) and
+    /// `entropy_ctxt` carries fresh randomness for the reshuffle
+    #[instruction]
+    pub fn recycle_discards(
+        deck_ctxt: Enc<Mxe, Deck>,
+        start: u8,
+        entropy_ctxt: Enc<Shared, [u8; 32]>,
+    ) -> Enc<Mxe, Deck> {
+        let deck = deck_ctxt.to_arcis();
+        let entropy = entropy_ctxt.to_arcis();
+
+        let cards = recycled_deck(&deck.cards, start, &entropy, &node_draws());
+
+        deck_ctxt.owner.from_arcis(Deck { cards })
     }
 
     /// Reveal hole cards at showdown
-    /// This decrypts the cards so everyone can see them
+    /// Reads the player's deck positions (first `count` entries of
+    /// `positions`) from the MXE deck and decrypts them so everyone can see
     #[instruction]
     pub fn reveal_hole_cards(
-        input_ctxt: Enc<Shared, RevealCardsInput>
-    ) -> Enc<Shared, [u8; 2]> {
-        let input = input_ctxt.to_arcis();
-        
-        let cards = [input.card1, input.card2];
-        
+        deck_ctxt: Enc<Mxe, &Deck>,
+        positions: [u8; MAX_HOLE_CARDS],
+        count: u8,
+    ) -> [u8; MAX_HOLE_CARDS] {
+        let deck = deck_ctxt.to_arcis();
+
         // Return revealed cards
-        input_ctxt.owner.from_arcis(cards)
+        shown_hole_cards(&deck.cards, &positions, count).reveal()
+    }
+
+    /// Evaluate a Hold'em showdown without showing anyone's cards
+    /// Scores every contender's best five of seven inside MPC and reveals
+    /// only which seats hold the best hand, plus that hand's score when the
+    /// table shows winning hands (0 otherwise). Losing hands never leave MPC.
+    #[instruction]
+    pub fn evaluate_showdown(
+        deck_ctxt: Enc<Mxe, &Deck>,
+        hole_positions: [[u8; SHOWDOWN_HOLE_CARDS]; MAX_PLAYERS],
+        contenders: [bool; MAX_PLAYERS],
+        board: [u8; 5],
+        show_winning_hand: bool,
+    ) -> ([bool; MAX_PLAYERS], u32) {
+        let deck = deck_ctxt.to_arcis();
+
+        let (winners, winning_score) = showdown_winners(
+            &deck.cards,
+            &hole_positions,
+            &contenders,
+            &board,
+            show_winning_hand,
+        );
+
+        (winners.reveal(), winning_score.reveal())
     }
 
     /// Generate random number for tie-breaking
@@ -119,13 +212,9 @@ mod circuits {
         input_ctxt: Enc<Shared, RandomInput>
     ) -> Enc<Shared, u8> {
         let input = input_ctxt.to_arcis();
-        
-        // Simple random generation from seed
-        let mut hash = input.seed;
-        hash = hash_entropy(hash);
-        
-        let random_value = hash[0] % input.max_value;
-        
+
+        let random_value = random_below(&input.seed, input.max_value, &random_draws());
+
         input_ctxt.owner.from_arcis(random_value)
     }
 
@@ -133,25 +222,27 @@ mod circuits {
     // HELPER FUNCTIONS
     // ============================================================================
 
-    /// Simple entropy mixing function
-    /// In production, this would use a proper cryptographic hash
-    fn hash_entropy(input: [u8; 32]) -> [u8; 32] {
-        let mut output = input;
-        
-        // Simple mixing (XOR with rotated values)
-        for i in 0..32 {
-            let prev_idx = if i == 0 { 31 } else { i - 1 };
-            let next_idx = if i == 31 { 0 } else { i + 1 };
-            
-            let prev = input[prev_idx];
-            let curr = input[i];
-            let next = input[next_idx];
-            
-            // Simple arithmetic mixing (avoiding wrapping_* methods)
-            output[i] = (prev * 7 + curr * 13 + next * 17) as u8;
+    /// The MPC nodes' joint randomness for one shuffle: a byte per draw,
+    /// `SHUFFLE_DRAW_ATTEMPTS` draws for every deck position
+    fn node_draws() -> [[u8; SHUFFLE_DRAW_ATTEMPTS]; DECK_SIZE] {
+        let mut draws = [[0u8; SHUFFLE_DRAW_ATTEMPTS]; DECK_SIZE];
+        for i in 1..DECK_SIZE {
+            for attempt in 0..SHUFFLE_DRAW_ATTEMPTS {
+                draws[i][attempt] = ArcisRNG::gen_integer_from_width(8) as u8;
+            }
+        }
+
+        draws
+    }
+
+    /// The MPC nodes' joint randomness for one `random_below`
+    fn random_draws() -> [u8; SHUFFLE_DRAW_ATTEMPTS] {
+        let mut draws = [0u8; SHUFFLE_DRAW_ATTEMPTS];
+        for attempt in 0..SHUFFLE_DRAW_ATTEMPTS {
+            draws[attempt] = ArcisRNG::gen_integer_from_width(8) as u8;
         }
-        
-        output
+
+        draws
     }
 }
-
+}
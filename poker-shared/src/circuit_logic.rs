@@ -0,0 +1,685 @@
+//! Logic of the Arcis circuits, written once for MPC and plain Rust
+//!
+//! `#[encrypted]` only compiles code that sits inside its module, so the
+//! logic lives in a macro that pastes it into a module: `encrypted-ixs`
+//! wraps its `#[encrypted]` module in `circuit_logic!`, and this crate
+//! expands it into the plain `circuits` module used by the program's mock
+//! backend, the MXE stand-in and the tests below.
+//!
+//! Everything in the macro must stay inside the Arcis subset: `for` loops
+//! with fixed bounds, no `while`, iterators, closures or early returns, and
+//! no shifts or bitwise operators on integers. Arcis also resolves names
+//! only inside the module, so the macro pastes no paths into other crates,
+//! `$crate::` included; `cargo build -p encrypted-ixs` is the real check,
+//! the tests below catch the usual slips. Randomness is an
+//! input (`draws`): the circuits fill it from `ArcisRNG`, plain Rust from
+//! whatever stands in for the cluster, so the same draws give the same deck.
+
+/// Paste the circuit logic into a module
+///
+/// ```ignore
+/// poker_shared::circuit_logic! {
+///     #[encrypted]
+///     mod circuits {
+///         use arcis_imports::*;
+///         // instructions calling shuffled_deck, hole_cards, ...
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! circuit_logic {
+    ($(#[$attr:meta])* $vis:vis mod $name:ident { $($body:tt)* }) => {
+        $(#[$attr])*
+        $vis mod $name {
+            $($body)*
+
+            /// Seats at the table; sizes every per-seat input
+            /// A literal: Arcis cannot resolve constants from other crates.
+            /// Must equal the crate's `MAX_PLAYERS` (checked in the tests)
+            pub const MAX_PLAYERS: usize = 6;
+
+            /// Cards in a standard deck; every deck keeps this many slots
+            pub const DECK_SIZE: usize = 52;
+
+            /// Cards in a short deck (sixes through aces)
+            pub const SHORT_DECK_SIZE: usize = 36;
+
+            /// Most hole cards any variant deals to one player (6-card Omaha)
+            pub const MAX_HOLE_CARDS: usize = 6;
+
+            /// Most board cards revealed at once (the flop)
+            pub const MAX_BOARD_REVEAL: usize = 3;
+
+            /// Cards in a draw-game hand
+            pub const DRAW_HAND_CARDS: usize = 5;
+
+            /// Hole cards per player in a private showdown (Texas Hold'em)
+            pub const SHOWDOWN_HOLE_CARDS: usize = 2;
+
+            /// Filler for unused card slots (not a valid card index)
+            pub const NO_CARD: u8 = 255;
+
+            /// Draws per swap before giving up on rejection sampling; each draw
+            /// is accepted with probability above 1/2, so all failing is below
+            /// 2^-40
+            pub const SHUFFLE_DRAW_ATTEMPTS: usize = 40;
+
+            // ====================================================================
+            // CIRCUIT BODIES
+            // ====================================================================
+
+            /// `shuffle_deck`: Fisher-Yates over the full deck
+            /// `draws[i]` holds the node randomness for the swap at position i
+            pub fn shuffled_deck(
+                entropy: &[[u8; 32]; MAX_PLAYERS],
+                active_count: u8,
+                draws: &[[u8; SHUFFLE_DRAW_ATTEMPTS]; DECK_SIZE],
+            ) -> [u8; DECK_SIZE] {
+                let combined_entropy = combine_entropy(entropy, active_count);
+                fisher_yates(ordered_deck(), DECK_SIZE, &combined_entropy, draws)
+            }
+
+            /// `shuffle_short_deck`: Fisher-Yates over the first 36 slots only
+            /// Output keeps the 52-slot layout so the deal circuits are shared;
+            /// slots past the short deck hold NO_CARD
+            pub fn shuffled_short_deck(
+                entropy: &[[u8; 32]; MAX_PLAYERS],
+                active_count: u8,
+                draws: &[[u8; SHUFFLE_DRAW_ATTEMPTS]; DECK_SIZE],
+            ) -> [u8; DECK_SIZE] {
+                let combined_entropy = combine_entropy(entropy, active_count);
+                fisher_yates(ordered_short_deck(), SHORT_DECK_SIZE, &combined_entropy, draws)
+            }
+
+            /// `deal_card` and `deal_up_card`: the card at a deck position
+            pub fn card_at(deck: &[u8; DECK_SIZE], card_index: u8) -> u8 {
+                deck[card_index as usize]
+            }
+
+            /// `deal_hole_cards`: `count` consecutive cards from `start_index`,
+            /// the rest NO_CARD
+            pub fn hole_cards(
+                deck: &[u8; DECK_SIZE],
+                start_index: u8,
+                count: u8,
+            ) -> [u8; MAX_HOLE_CARDS] {
+                let mut cards = [NO_CARD; MAX_HOLE_CARDS];
+                for i in 0..MAX_HOLE_CARDS {
+                    let position = (start_index as usize + i) % DECK_SIZE;
+                    if (i as u8) < count {
+                        cards[i] = deck[position];
+                    }
+                }
+
+                cards
+            }
+
+            /// `reveal_community`: the `count` cards after the burn card, the
+            /// rest NO_CARD
+            pub fn board_cards(
+                deck: &[u8; DECK_SIZE],
+                burn_index: u8,
+                count: u8,
+            ) -> [u8; MAX_BOARD_REVEAL] {
+                let mut cards = [NO_CARD; MAX_BOARD_REVEAL];
+                for i in 0..MAX_BOARD_REVEAL {
+                    let position = (burn_index as usize + 1 + i) % DECK_SIZE;
+                    if (i as u8) < count {
+                        cards[i] = deck[position];
+                    }
+                }
+
+                cards
+            }
+
+            /// `draw_cards`: swap each discarded card with the next undealt
+            /// card; returns the updated deck and the player's new hand
+            pub fn drawn_hand(
+                deck: &[u8; DECK_SIZE],
+                hand_positions: &[u8; DRAW_HAND_CARDS],
+                discard: &[bool; DRAW_HAND_CARDS],
+                cursor: u8,
+            ) -> ([u8; DECK_SIZE], [u8; DRAW_HAND_CARDS]) {
+                let mut deck = *deck;
+                let mut hand = [NO_CARD; DRAW_HAND_CARDS];
+                let mut cursor = cursor as usize;
+
+                for i in 0..DRAW_HAND_CARDS {
+                    let position = hand_positions[i] as usize;
+                    let current = deck[position];
+
+                    if discard[i] {
+                        let replacement = deck[cursor];
+                        deck[position] = replacement;
+                        deck[cursor] = current;
+                        hand[i] = replacement;
+                        cursor += 1;
+                    } else {
+                        hand[i] = current;
+                    }
+                }
+
+                (deck, hand)
+            }
+
+            /// `recycle_discards`: Fisher-Yates over positions start..52
+            pub fn recycled_deck(
+                deck: &[u8; DECK_SIZE],
+                start: u8,
+                entropy: &[u8; 32],
+                draws: &[[u8; SHUFFLE_DRAW_ATTEMPTS]; DECK_SIZE],
+            ) -> [u8; DECK_SIZE] {
+                let mut deck = *deck;
+                let start = start as usize;
+
+                for i in (1..DECK_SIZE).rev() {
+                    if i > start {
+                        let j = start + random_index(i - start, &draws[i], entropy);
+                        swap_cards(&mut deck, i, j);
+                    }
+                }
+
+                deck
+            }
+
+            /// `reveal_hole_cards`: the cards at the first `count` positions,
+            /// the rest NO_CARD
+            pub fn shown_hole_cards(
+                deck: &[u8; DECK_SIZE],
+                positions: &[u8; MAX_HOLE_CARDS],
+                count: u8,
+            ) -> [u8; MAX_HOLE_CARDS] {
+                let mut cards = [NO_CARD; MAX_HOLE_CARDS];
+                for i in 0..MAX_HOLE_CARDS {
+                    if (i as u8) < count {
+                        cards[i] = deck[positions[i] as usize % DECK_SIZE];
+                    }
+                }
+
+                cards
+            }
+
+            /// `evaluate_showdown`: which seats hold the best hand, and that
+            /// hand's score when the table shows winning hands (0 otherwise)
+            pub fn showdown_winners(
+                deck: &[u8; DECK_SIZE],
+                hole_positions: &[[u8; SHOWDOWN_HOLE_CARDS]; MAX_PLAYERS],
+                contenders: &[bool; MAX_PLAYERS],
+                board: &[u8; 5],
+                show_winning_hand: bool,
+            ) -> ([bool; MAX_PLAYERS], u32) {
+                let mut scores = [0u32; MAX_PLAYERS];
+                let mut best = 0u32;
+                for seat in 0..MAX_PLAYERS {
+                    let mut cards = [0u8; 7];
+                    for i in 0..SHOWDOWN_HOLE_CARDS {
+                        cards[i] = deck[hole_positions[seat][i] as usize % DECK_SIZE];
+                    }
+                    for i in 0..5 {
+                        cards[SHOWDOWN_HOLE_CARDS + i] = board[i];
+                    }
+
+                    // Folded seats score 0; every real hand scores above that
+                    if contenders[seat] {
+                        scores[seat] = best_hand_score(cards);
+                    }
+                    if scores[seat] > best {
+                        best = scores[seat];
+                    }
+                }
+
+                let mut winners = [false; MAX_PLAYERS];
+                for seat in 0..MAX_PLAYERS {
+                    winners[seat] = contenders[seat] && scores[seat] == best;
+                }
+
+                let winning_score = if show_winning_hand { best } else { 0 };
+
+                (winners, winning_score)
+            }
+
+            /// `generate_random`: a uniform value below `max_value`, by the
+            /// shuffle's rejection sampling over node draws XORed with the seed
+            /// A `max_value` of 0 has no value below it and gives 0
+            pub fn random_below(
+                seed: &[u8; 32],
+                max_value: u8,
+                draws: &[u8; SHUFFLE_DRAW_ATTEMPTS],
+            ) -> u8 {
+                let max = if max_value == 0 { 0 } else { max_value - 1 };
+                random_index(max as usize, draws, seed) as u8
+            }
+
+            // ====================================================================
+            // HELPER FUNCTIONS
+            // ====================================================================
+
+            /// Card indices 0-51 in order
+            pub fn ordered_deck() -> [u8; DECK_SIZE] {
+                let mut deck = [0u8; DECK_SIZE];
+                for i in 0..DECK_SIZE {
+                    deck[i] = i as u8;
+                }
+
+                deck
+            }
+
+            /// Sixes through aces in the first 36 slots, the rest NO_CARD
+            /// Standard card indices, skipping twos-fives
+            /// (index = suit * 13 + rank - 2, ranks 6..=14)
+            pub fn ordered_short_deck() -> [u8; DECK_SIZE] {
+                let mut deck = [NO_CARD; DECK_SIZE];
+                for i in 0..SHORT_DECK_SIZE {
+                    deck[i] = ((i / 9) * 13 + 4 + i % 9) as u8;
+                }
+
+                deck
+            }
+
+            /// Fisher-Yates over the first `len` slots, one unbiased draw per
+            /// swap
+            pub fn fisher_yates(
+                deck: [u8; DECK_SIZE],
+                len: usize,
+                entropy: &[u8; 32],
+                draws: &[[u8; SHUFFLE_DRAW_ATTEMPTS]; DECK_SIZE],
+            ) -> [u8; DECK_SIZE] {
+                let mut deck = deck;
+                for i in (1..DECK_SIZE).rev() {
+                    if i < len {
+                        let j = random_index(i, &draws[i], entropy);
+                        swap_cards(&mut deck, i, j);
+                    }
+                }
+
+                deck
+            }
+
+            /// Uniform index in 0..=max by rejection sampling
+            /// Candidates are node draws plus player entropy (mod 256, then mod
+            /// a power of two that divides it), so the index is uniform if
+            /// either source is. Candidates past `max` are rejected rather than
+            /// reduced, which would bias low indices.
+            pub fn random_index(
+                max: usize,
+                draws: &[u8; SHUFFLE_DRAW_ATTEMPTS],
+                entropy: &[u8; 32],
+            ) -> usize {
+                let range = covering_range(max);
+
+                let mut index = 0u8;
+                let mut found = false;
+                for attempt in 0..SHUFFLE_DRAW_ATTEMPTS {
+                    let candidate = (draws[attempt] as u16 + entropy[(max + attempt) % 32] as u16) % range;
+                    if !found && (candidate as usize) <= max {
+                        index = candidate as u8;
+                        found = true;
+                    }
+                }
+
+                index as usize
+            }
+
+            /// Smallest power of two above `max` (at most 255)
+            pub fn covering_range(max: usize) -> u16 {
+                if max < 2 {
+                    2
+                } else if max < 4 {
+                    4
+                } else if max < 8 {
+                    8
+                } else if max < 16 {
+                    16
+                } else if max < 32 {
+                    32
+                } else if max < 64 {
+                    64
+                } else if max < 128 {
+                    128
+                } else {
+                    256
+                }
+            }
+
+            /// Swap two cards at a secret position `j` without revealing it
+            pub fn swap_cards(deck: &mut [u8; DECK_SIZE], i: usize, j: usize) {
+                let card_i = deck[i];
+                for k in 0..DECK_SIZE {
+                    if k == j {
+                        deck[i] = deck[k];
+                        deck[k] = card_i;
+                    }
+                }
+            }
+
+            /// Combine the seated players' entropy via addition (mod 256)
+            /// `active_count` is public, so padding slots are skipped outright
+            pub fn combine_entropy(entropy: &[[u8; 32]; MAX_PLAYERS], active_count: u8) -> [u8; 32] {
+                let mut combined_entropy = [0u8; 32];
+                for seat in 0..MAX_PLAYERS {
+                    if seat < active_count as usize {
+                        for i in 0..32 {
+                            combined_entropy[i] =
+                                (combined_entropy[i] as u16 + entropy[seat][i] as u16) as u8;
+                        }
+                    }
+                }
+
+                combined_entropy
+            }
+
+            /// Best five-card score among the 21 ways to drop two of seven cards
+            pub fn best_hand_score(cards: [u8; 7]) -> u32 {
+                let mut best = 0u32;
+                for skip_a in 0..7 {
+                    for skip_b in (skip_a + 1)..7 {
+                        let mut hand = [0u8; 5];
+                        let mut next = 0;
+                        for i in 0..7 {
+                            if i != skip_a && i != skip_b {
+                                hand[next] = cards[i];
+                                next += 1;
+                            }
+                        }
+
+                        let score = hand_score(hand);
+                        if score > best {
+                            best = score;
+                        }
+                    }
+                }
+
+                best
+            }
+
+            /// Comparable score for five cards: category (0 = high card ..
+            /// 8 = straight flush) in bits 20-23, then the five ranks ordered by
+            /// group size and rank, four bits each
+            pub fn hand_score(hand: [u8; 5]) -> u32 {
+                let mut ranks = [0u8; 5];
+                let mut suits = [0u8; 5];
+                for i in 0..5 {
+                    ranks[i] = hand[i] % 13 + 2;
+                    suits[i] = hand[i] / 13;
+                }
+
+                // Key each card by how many share its rank, then by rank
+                let mut keys = [0u8; 5];
+                for i in 0..5 {
+                    let mut count = 0u8;
+                    for j in 0..5 {
+                        if ranks[j] == ranks[i] {
+                            count += 1;
+                        }
+                    }
+                    keys[i] = count * 16 + ranks[i];
+                }
+
+                // Sort keys high to low (fixed compare-swap network)
+                for pass in 0..4 {
+                    for j in 0..(4 - pass) {
+                        if keys[j] < keys[j + 1] {
+                            let tmp = keys[j];
+                            keys[j] = keys[j + 1];
+                            keys[j + 1] = tmp;
+                        }
+                    }
+                }
+
+                let mut counts = [0u8; 5];
+                let mut sorted = [0u8; 5];
+                for i in 0..5 {
+                    counts[i] = keys[i] / 16;
+                    sorted[i] = keys[i] % 16;
+                }
+
+                let mut flush = true;
+                for i in 1..5 {
+                    if suits[i] != suits[0] {
+                        flush = false;
+                    }
+                }
+
+                let distinct = counts[0] == 1;
+                let wheel = distinct && sorted[0] == 14 && sorted[1] == 5;
+                let straight = distinct && (sorted[0] - sorted[4] == 4 || wheel);
+
+                // A-2-3-4-5 plays as a five-high straight
+                if wheel {
+                    sorted = [5, 4, 3, 2, 1];
+                }
+
+                let category: u32 = if straight && flush {
+                    8
+                } else if counts[0] == 4 {
+                    7
+                } else if counts[0] == 3 && counts[3] == 2 {
+                    6
+                } else if flush {
+                    5
+                } else if straight {
+                    4
+                } else if counts[0] == 3 {
+                    3
+                } else if counts[0] == 2 && counts[2] == 2 {
+                    2
+                } else if counts[0] == 2 {
+                    1
+                } else {
+                    0
+                };
+
+                let mut score = category;
+                for i in 0..5 {
+                    score = score * 16 + sorted[i] as u32;
+                }
+
+                score
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::circuits::*;
+
+    fn entropy() -> [[u8; 32]; MAX_PLAYERS] {
+        let mut entropy = [[0u8; 32]; MAX_PLAYERS];
+        for (seat, seat_entropy) in entropy.iter_mut().enumerate() {
+            for (i, byte) in seat_entropy.iter_mut().enumerate() {
+                *byte = (seat * 37 + i * 11) as u8;
+            }
+        }
+        entropy
+    }
+
+    /// Deterministic stand-in for node randomness
+    fn draws(seed: u8) -> [[u8; SHUFFLE_DRAW_ATTEMPTS]; DECK_SIZE] {
+        let mut draws = [[0u8; SHUFFLE_DRAW_ATTEMPTS]; DECK_SIZE];
+        let mut state = seed as u32 + 1;
+        for draw in draws.iter_mut().flatten() {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            *draw = (state >> 16) as u8;
+        }
+        draws
+    }
+
+    fn sorted(mut deck: [u8; DECK_SIZE]) -> [u8; DECK_SIZE] {
+        deck.sort_unstable();
+        deck
+    }
+
+    #[test]
+    fn test_macro_pastes_only_arcis_tokens() {
+        let source = include_str!("circuit_logic.rs");
+        let start = source.find("macro_rules! circuit_logic").unwrap();
+        let end = start + source[start..].find("\n}\n").unwrap();
+        let body = &source[start..end];
+
+        for token in ["$crate", "poker_shared::", "while ", " << ", " >> ", " & ", " | ", " ^ "] {
+            assert!(!body.contains(token), "`{}` in circuit_logic!", token.trim());
+        }
+    }
+
+    #[test]
+    fn test_circuits_size_seats_like_the_crate() {
+        assert_eq!(MAX_PLAYERS, crate::MAX_PLAYERS);
+    }
+
+    #[test]
+    fn test_shuffle_is_a_permutation_of_the_inputs() {
+        let deck = shuffled_deck(&entropy(), 3, &draws(1));
+        assert_eq!(sorted(deck), ordered_deck());
+        assert_ne!(deck, ordered_deck());
+
+        assert_eq!(shuffled_deck(&entropy(), 3, &draws(1)), deck);
+        assert_ne!(shuffled_deck(&entropy(), 3, &draws(2)), deck);
+        assert_ne!(shuffled_deck(&entropy(), 2, &draws(1)), deck);
+
+        // Padding seats past the active count are ignored
+        let mut padded = entropy();
+        padded[5] = [99; 32];
+        assert_eq!(shuffled_deck(&padded, 3, &draws(1)), deck);
+    }
+
+    #[test]
+    fn test_short_deck_keeps_sixes_and_up() {
+        let deck = shuffled_short_deck(&entropy(), 2, &draws(3));
+        assert_eq!(sorted(deck), sorted(ordered_short_deck()));
+        assert!(deck[..SHORT_DECK_SIZE].iter().all(|&card| card % 13 >= 4));
+        assert!(deck[SHORT_DECK_SIZE..].iter().all(|&card| card == NO_CARD));
+    }
+
+    #[test]
+    fn test_fisher_yates_swaps_with_each_drawn_index() {
+        let entropy = entropy()[1];
+        let draws = draws(4);
+
+        // Textbook Fisher-Yates fed the same indices
+        let mut expected = ordered_deck();
+        for i in (1..DECK_SIZE).rev() {
+            expected.swap(i, random_index(i, &draws[i], &entropy));
+        }
+        assert_eq!(fisher_yates(ordered_deck(), DECK_SIZE, &entropy, &draws), expected);
+    }
+
+    #[test]
+    fn test_random_index_rejects_out_of_range_draws() {
+        // max 4 draws 3 bits: 7 and 6 are rejected, 2 is accepted
+        let mut attempts = [0u8; SHUFFLE_DRAW_ATTEMPTS];
+        attempts[..3].copy_from_slice(&[7, 6, 2]);
+        assert_eq!(random_index(4, &attempts, &[0; 32]), 2);
+
+        // Bits above the width are ignored, entropy shifts the candidate
+        attempts[0] = 0xf9;
+        assert_eq!(random_index(4, &attempts, &[0; 32]), 1);
+        let mut entropy = [0u8; 32];
+        entropy[4] = 1;
+        assert_eq!(random_index(4, &attempts, &entropy), 2);
+
+        // Every draw rejected falls back to 0
+        assert_eq!(random_index(4, &[7; SHUFFLE_DRAW_ATTEMPTS], &[0; 32]), 0);
+    }
+
+    #[test]
+    fn test_recycle_only_touches_discards() {
+        let deck = shuffled_deck(&entropy(), 2, &draws(5));
+        let recycled = recycled_deck(&deck, 30, &entropy()[0], &draws(6));
+        assert_eq!(recycled[..30], deck[..30]);
+        assert_ne!(recycled[30..], deck[30..]);
+        assert_eq!(sorted(recycled), ordered_deck());
+    }
+
+    #[test]
+    fn test_deals_pad_with_no_card() {
+        let mut deck = ordered_deck();
+        deck.reverse();
+
+        assert_eq!(card_at(&deck, 7), deck[7]);
+        assert_eq!(hole_cards(&deck, 50, 3), [deck[50], deck[51], deck[0], NO_CARD, NO_CARD, NO_CARD]);
+        assert_eq!(board_cards(&deck, 10, 3), [deck[11], deck[12], deck[13]]);
+        assert_eq!(board_cards(&deck, 20, 1), [deck[21], NO_CARD, NO_CARD]);
+        assert_eq!(
+            shown_hole_cards(&deck, &[4, 9, 0, 0, 0, 0], 2),
+            [deck[4], deck[9], NO_CARD, NO_CARD, NO_CARD, NO_CARD]
+        );
+    }
+
+    #[test]
+    fn test_draw_swaps_discards_with_the_stock() {
+        let deck = ordered_deck();
+        let (after, hand) = drawn_hand(&deck, &[0, 1, 2, 3, 4], &[true, false, true, false, false], 20);
+        assert_eq!(hand, [20, 1, 21, 3, 4]);
+        assert_eq!(after[..5], [20, 1, 21, 3, 4]);
+        assert_eq!(after[20..22], [0, 2]);
+        assert_eq!(sorted(after), deck);
+    }
+
+    #[test]
+    fn test_showdown_reveals_only_the_best_seats() {
+        // Seat 0 holds aces (12, 25), seat 1 sevens (5, 18), seat 2 folded
+        let mut deck = ordered_deck();
+        deck[..6].copy_from_slice(&[12, 25, 5, 18, 38, 51]);
+        let mut hole_positions = [[0u8; SHOWDOWN_HOLE_CARDS]; MAX_PLAYERS];
+        hole_positions[0] = [0, 1];
+        hole_positions[1] = [2, 3];
+        hole_positions[2] = [4, 5];
+        let mut contenders = [false; MAX_PLAYERS];
+        contenders[0] = true;
+        contenders[1] = true;
+        let board = [0, 14, 29, 43, 8];
+
+        let (winners, score) = showdown_winners(&deck, &hole_positions, &contenders, &board, false);
+        let mut expected = [false; MAX_PLAYERS];
+        expected[0] = true;
+        assert_eq!(winners, expected);
+        assert_eq!(score, 0);
+
+        // The board plays for everyone: both seats split
+        let (winners, shown) = showdown_winners(&deck, &hole_positions, &contenders, &[8, 9, 10, 11, 12], true);
+        expected[1] = true;
+        assert_eq!(winners, expected);
+        assert_eq!(shown >> 20, 8);
+    }
+
+    #[test]
+    fn test_hand_scores_order_categories() {
+        let pair = hand_score([0, 13, 28, 42, 4]);
+        let two_pair = hand_score([0, 13, 1, 14, 4]);
+        let wheel = hand_score([12, 13, 27, 41, 3]);
+        let six_high = hand_score([0, 14, 28, 42, 4]);
+        let quads = hand_score([5, 18, 31, 44, 0]);
+        assert!(pair < two_pair && two_pair < wheel && wheel < six_high && six_high < quads);
+        assert_eq!(wheel >> 20, 4);
+        assert_eq!(best_hand_score([5, 18, 31, 44, 0, 1, 2]), hand_score([5, 18, 31, 44, 2]));
+    }
+
+    #[test]
+    fn test_random_below_is_uniform() {
+        // 6 does not divide 256: a modulo would favour the low values
+        let mut counts = [0u32; 6];
+        let mut state = 1u32;
+        for _ in 0..60_000 {
+            let mut attempts = [0u8; SHUFFLE_DRAW_ATTEMPTS];
+            for draw in attempts.iter_mut() {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                *draw = (state >> 16) as u8;
+            }
+            counts[random_below(&[0x5a; 32], 6, &attempts) as usize] += 1;
+        }
+        assert!(counts.iter().all(|&count| count.abs_diff(10_000) < 300), "{:?}", counts);
+
+        assert_eq!(random_below(&[9; 32], 0, &draws(1)[1]), 0);
+        assert_eq!(random_below(&[9; 32], 1, &draws(1)[1]), 0);
+    }
+
+    #[test]
+    fn test_covering_range() {
+        for max in 0..=255usize {
+            let range = covering_range(max) as usize;
+            assert!(range > max && range.is_power_of_two());
+            assert!(max < 2 || range / 2 <= max);
+        }
+    }
+}
@@ -0,0 +1,23 @@
+//! Definitions shared by the `arcium_poker` program and the Arcis circuits
+//! in `encrypted-ixs`, so the two can't drift apart.
+
+#![no_std]
+
+#[macro_use]
+mod circuit_logic;
+
+/// Maximum number of players per game
+///
+/// Also sizes the circuits' per-seat inputs (shuffle entropy, showdown
+/// contenders). Raising it for 9- or 10-max tables resizes those inputs too
+/// (change the literal in `circuit_logic!` with it); the program pads them
+/// to this length and passes the seated count.
+pub const MAX_PLAYERS: usize = 6;
+
+circuit_logic! {
+    /// The circuits' logic as plain Rust, for local execution and tests
+    ///
+    /// Same code the `#[encrypted]` module compiles; see `circuit_logic!`.
+    #[allow(clippy::needless_range_loop, clippy::manual_swap)]
+    pub mod circuits {}
+}
@@ -0,0 +1,56 @@
+//! Compares the bitmask 7-card evaluator with the 21-subset reference.
+//!
+//! Run with `cargo bench -p arcium_poker --bench evaluator`.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use arcium_poker::cards::evaluator::evaluate_best_hand_exhaustive;
+use arcium_poker::cards::{evaluate_seven, Card};
+
+const SAMPLES: usize = 20_000;
+const ROUNDS: usize = 10;
+
+fn sample_hands() -> Vec<[Card; 7]> {
+    let mut state = 0x2545_F491_4F6C_DD1Du64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    (0..SAMPLES)
+        .map(|_| {
+            let mut deck: [u8; 52] = core::array::from_fn(|i| i as u8);
+            for i in 0..7 {
+                let j = i + (next() % (52 - i) as u64) as usize;
+                deck.swap(i, j);
+            }
+            core::array::from_fn(|i| Card::from_index(deck[i]).unwrap())
+        })
+        .collect()
+}
+
+fn bench(name: &str, hands: &[[Card; 7]], eval: impl Fn(&[Card; 7])) {
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        for hand in hands {
+            eval(black_box(hand));
+        }
+    }
+    let elapsed = start.elapsed();
+    let per_hand = elapsed.as_nanos() / (hands.len() * ROUNDS) as u128;
+    println!("{:<12} {:>8} ns/hand ({:?} total)", name, per_hand, elapsed);
+}
+
+fn main() {
+    let hands = sample_hands();
+
+    bench("bitmask", &hands, |hand| {
+        black_box(evaluate_seven(hand));
+    });
+    bench("exhaustive", &hands, |hand| {
+        black_box(evaluate_best_hand_exhaustive(hand).unwrap());
+    });
+}
@@ -0,0 +1,95 @@
+//! Statistical check that the plaintext shuffle is uniform.
+//!
+//! Runs the shuffle circuits' code with node randomness from `ShuffleRng`,
+//! exactly as the mock backend does.
+//!
+//! Counts how often each card lands in each position over many shuffles and
+//! runs a chi-square test on the table, for the full and the short deck.
+//! Exits non-zero if either looks biased.
+//!
+//! Run with `cargo bench -p arcium_poker --bench shuffle_uniformity`;
+//! `SHUFFLE_SAMPLES` overrides the number of shuffles (default 2,000,000).
+
+use std::process::ExitCode;
+use std::time::Instant;
+
+use arcium_poker::arcium::shuffle_rng::ShuffleRng;
+use poker_shared::circuits;
+
+const DEFAULT_SAMPLES: usize = 2_000_000;
+
+/// Chi-square of the position x card table, with its degrees of freedom
+fn position_chi_square(deck_size: usize, samples: usize) -> (f64, f64) {
+    let mut counts = vec![0u64; deck_size * deck_size];
+
+    // Each card's slot in the unshuffled deck
+    let ordered = if deck_size == circuits::SHORT_DECK_SIZE {
+        circuits::ordered_short_deck()
+    } else {
+        circuits::ordered_deck()
+    };
+    let mut slot_of = [0usize; 256];
+    for (slot, &card) in ordered.iter().enumerate().take(deck_size) {
+        slot_of[card as usize] = slot;
+    }
+
+    for sample in 0..samples {
+        // Fresh, independent entropy for every shuffle
+        let mut entropy = [[0u8; 32]; circuits::MAX_PLAYERS];
+        entropy[0][..8].copy_from_slice(&(sample as u64).to_le_bytes());
+        entropy[1][..8].copy_from_slice(&(deck_size as u64).to_le_bytes());
+        let draws = ShuffleRng::new(&entropy[..2], &[]).shuffle_draws();
+
+        let cards = if deck_size == circuits::SHORT_DECK_SIZE {
+            circuits::shuffled_short_deck(&entropy, 2, &draws)
+        } else {
+            circuits::shuffled_deck(&entropy, 2, &draws)
+        };
+        for (position, &card) in cards.iter().enumerate().take(deck_size) {
+            counts[position * deck_size + slot_of[card as usize]] += 1;
+        }
+    }
+
+    let expected = samples as f64 / deck_size as f64;
+    let chi_square = counts
+        .iter()
+        .map(|&count| (count as f64 - expected).powi(2) / expected)
+        .sum();
+    let degrees = ((deck_size - 1) * (deck_size - 1)) as f64;
+    (chi_square, degrees)
+}
+
+fn main() -> ExitCode {
+    let samples = std::env::var("SHUFFLE_SAMPLES")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_SAMPLES);
+
+    let mut uniform = true;
+    for deck_size in [52, 36] {
+        let start = Instant::now();
+        let (chi_square, degrees) = position_chi_square(deck_size, samples);
+
+        // Normal approximation: flag anything beyond five standard deviations
+        let z = (chi_square - degrees) / (2.0 * degrees).sqrt();
+        let pass = z.abs() < 5.0;
+        uniform &= pass;
+
+        println!(
+            "{}-card deck: {} shuffles, chi-square {:.1} (df {}, z {:+.2}) {} in {:?}",
+            deck_size,
+            samples,
+            chi_square,
+            degrees,
+            z,
+            if pass { "ok" } else { "BIASED" },
+            start.elapsed()
+        );
+    }
+
+    if uniform {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,275 @@
+use anchor_lang::prelude::*;
+use crate::shared::PokerError;
+use super::comp_defs::{Circuit, CircuitConfig};
+use super::config::ArciumConfig;
+use super::integration;
+use super::mpc_community::{self, MxeCommunityParams};
+use super::mpc_deal::{self, Dealt, MxeDealParams};
+use super::mpc_draw::{self, Drawn, MxeDrawParams, MxeRecycleParams};
+use super::mpc_random::{self, MxeRandomParams};
+use super::mpc_reveal::{self, MxeRevealParams};
+use super::mpc_showdown::{self, MxeShowdownParams, ShowdownOutcome};
+use super::mpc_shuffle::{self, MxeShuffleParams, ShuffleResult};
+
+/// MPC backend used by this build
+///
+/// Selected at compile time: the `mock-mpc` feature (and unit tests) compute
+/// every result locally and deterministically; every other build queues
+/// computations on Arcium. There is no runtime switch, so a production build
+/// cannot end up on the mock because an account was left out.
+#[cfg(any(test, feature = "mock-mpc"))]
+pub type ActiveBackend = MockBackend;
+
+/// MPC backend used by this build (see the `mock-mpc` feature)
+#[cfg(not(any(test, feature = "mock-mpc")))]
+pub type ActiveBackend = ArciumBackend;
+
+/// Accounts the Arcium program needs to queue a computation
+#[derive(Clone)]
+pub struct MxeAccounts<'info> {
+    /// MXE program account
+    pub mxe_program: AccountInfo<'info>,
+    
+    /// MXE account (persistent MXE account PDA)
+    pub mxe_account: AccountInfo<'info>,
+    
+    /// Computation definition account
+    pub comp_def: AccountInfo<'info>,
+    
+    /// Mempool account for queueing computation
+    pub mempool: AccountInfo<'info>,
+    
+    /// Cluster account
+    pub cluster: AccountInfo<'info>,
+    
+    /// Computation account (to be created/used)
+    pub computation_account: AccountInfo<'info>,
+    
+    /// Authority (signer)
+    pub authority: AccountInfo<'info>,
+    
+    /// Sign seed PDA
+    pub sign_seed: AccountInfo<'info>,
+    
+    /// Executing pool PDA
+    pub executing_pool: AccountInfo<'info>,
+    
+    /// Staking pool PDA
+    pub staking_pool: AccountInfo<'info>,
+    
+    /// System program
+    pub system_program: AccountInfo<'info>,
+    
+    /// Clock sysvar
+    pub clock: AccountInfo<'info>,
+}
+
+/// `MxeAccounts` from an instruction's accounts, named as in `StartGame`;
+/// `$payer` is the signer paying for the computation
+macro_rules! mxe_accounts {
+    ($accounts:expr, $payer:ident) => {
+        $crate::arcium::backend::MxeAccounts {
+            mxe_program: $accounts.mxe_program.clone(),
+            mxe_account: $accounts.mxe_account.clone(),
+            comp_def: $accounts.comp_def_account.clone(),
+            mempool: $accounts.mempool_account.clone(),
+            cluster: $accounts.cluster_account.clone(),
+            computation_account: $accounts.computation_account.clone(),
+            authority: $accounts.$payer.to_account_info(),
+            sign_seed: $accounts.sign_seed.clone(),
+            executing_pool: $accounts.executing_pool_account.clone(),
+            staking_pool: $accounts.staking_pool.clone(),
+            system_program: $accounts.system_program.to_account_info(),
+            clock: $accounts.clock.clone(),
+        }
+    };
+}
+pub(crate) use mxe_accounts;
+
+/// MXE accounts an instruction was given, before it knows which circuit it
+/// will queue
+///
+/// Game logic decides the circuit (a hole card deal, an upcard, a board
+/// reveal); `accounts_for` then checks the accounts against it.
+pub struct MxeQueue<'a, 'info> {
+    /// Accounts for queueing on Arcium
+    pub accounts: MxeAccounts<'info>,
+    
+    /// MXE program and PDAs the accounts are checked against
+    pub arcium_config: &'a ArciumConfig,
+    
+    /// Registered comp defs
+    pub circuit_config: &'a CircuitConfig,
+    
+    /// Accounts the callback writes: game, its Deck account, then every
+    /// seat's PlayerState in seat order
+    pub callback_accounts: Vec<Pubkey>,
+}
+
+impl<'a, 'info> MxeQueue<'a, 'info> {
+    /// The accounts, checked for queueing `circuit` at `computation_offset`,
+    /// and the circuit's registered comp def offset
+    pub fn accounts_for(&self, circuit: Circuit, computation_offset: u64) -> Result<(MxeAccounts<'info>, u32)> {
+        let comp_def_offset = self.circuit_config.comp_def_offset(circuit)?;
+        self.arcium_config.validate_mxe_accounts(&self.accounts, comp_def_offset, computation_offset)?;
+        Ok((self.accounts.clone(), comp_def_offset))
+    }
+}
+
+/// Confidential computations the poker program needs
+///
+/// Methods that return `Option` yield `None` when the computation was queued
+/// and its result arrives via an MXE callback, and `Some` when the backend
+/// computed it immediately.
+pub trait MpcBackend {
+    /// Logged with every computation
+    const NAME: &'static str;
+    
+    /// Create a circuit's computation definition
+    fn init_comp_def(accounts: &crate::InitCompDef, circuit: Circuit) -> Result<()>;
+    
+    /// Shuffle the variant's deck with the players' entropy
+    fn shuffle(params: MxeShuffleParams) -> Result<ShuffleResult>;
+    
+    /// Deal cards from deck positions: encrypted to their owner, or face up
+    fn deal(params: MxeDealParams) -> Result<Option<Dealt>>;
+    
+    /// Replace a player's discards with the next undealt cards
+    fn draw(params: MxeDrawParams) -> Result<Option<Drawn>>;
+    
+    /// Reshuffle the discards back into the stock
+    fn recycle(params: MxeRecycleParams) -> Result<Option<Vec<u8>>>;
+    
+    /// Reveal dealt cards in plaintext (card indices, padded with NO_CARD)
+    fn reveal(params: MxeRevealParams) -> Result<Option<Vec<u8>>>;
+    
+    /// Burn a card and reveal the next community cards
+    fn reveal_community(params: MxeCommunityParams) -> Result<Option<Vec<u8>>>;
+    
+    /// Score every contender's hand and reveal only the winners
+    fn evaluate_showdown(params: MxeShowdownParams) -> Result<Option<ShowdownOutcome>>;
+    
+    /// Uniform random value below `max_value`
+    fn random(params: MxeRandomParams) -> Result<Option<u8>>;
+}
+
+/// Arcium MPC: every computation is queued on the MXE
+///
+/// Missing MXE accounts are an error, never a reason to compute locally.
+pub struct ArciumBackend;
+
+impl MpcBackend for ArciumBackend {
+    const NAME: &'static str = "arcium";
+    
+    fn init_comp_def(accounts: &crate::InitCompDef, circuit: Circuit) -> Result<()> {
+        integration::init_computation_definition(
+            &accounts.mxe_program,
+            &accounts.comp_def_account,
+            &accounts.mxe_account,
+            &accounts.authority.to_account_info(),
+            &accounts.system_program.to_account_info(),
+            circuit.comp_def_offset(),
+        )
+    }
+    
+    fn shuffle(params: MxeShuffleParams) -> Result<ShuffleResult> {
+        let mxe = require_mxe_accounts(&params.mxe)?;
+        mpc_shuffle::queue_shuffle(mxe, &params)
+    }
+    
+    fn deal(params: MxeDealParams) -> Result<Option<Dealt>> {
+        let mxe = require_mxe_accounts(&params.mxe)?;
+        mpc_deal::queue_deal(mxe, &params)?;
+        Ok(None)
+    }
+    
+    fn draw(params: MxeDrawParams) -> Result<Option<Drawn>> {
+        let mxe = require_mxe_accounts(&params.mxe)?;
+        mpc_draw::queue_draw(mxe, &params)?;
+        Ok(None)
+    }
+    
+    fn recycle(params: MxeRecycleParams) -> Result<Option<Vec<u8>>> {
+        let mxe = require_mxe_accounts(&params.mxe)?;
+        mpc_draw::queue_recycle(mxe, &params)?;
+        Ok(None)
+    }
+    
+    fn reveal(params: MxeRevealParams) -> Result<Option<Vec<u8>>> {
+        let mxe = require_mxe_accounts(&params.mxe)?;
+        mpc_reveal::queue_reveal(mxe, &params)?;
+        Ok(None)
+    }
+    
+    fn reveal_community(params: MxeCommunityParams) -> Result<Option<Vec<u8>>> {
+        let mxe = require_mxe_accounts(&params.mxe)?;
+        mpc_community::queue_reveal_community(mxe, &params)?;
+        Ok(None)
+    }
+    
+    fn evaluate_showdown(params: MxeShowdownParams) -> Result<Option<ShowdownOutcome>> {
+        let mxe = require_mxe_accounts(&params.mxe)?;
+        mpc_showdown::queue_evaluate_showdown(mxe, &params)?;
+        Ok(None)
+    }
+    
+    fn random(params: MxeRandomParams) -> Result<Option<u8>> {
+        let mxe = require_mxe_accounts(&params.mxe)?;
+        mpc_random::queue_random(mxe, &params)?;
+        Ok(None)
+    }
+}
+
+/// Deterministic local stand-in for the MPC circuits (development and tests)
+///
+/// MXE accounts are ignored; every result is computed immediately.
+pub struct MockBackend;
+
+impl MpcBackend for MockBackend {
+    const NAME: &'static str = "mock";
+    
+    fn init_comp_def(_accounts: &crate::InitCompDef, circuit: Circuit) -> Result<()> {
+        msg!("[ARCIUM MPC] Mock backend: no comp def to create for {}", circuit.name());
+        Ok(())
+    }
+    
+    fn shuffle(params: MxeShuffleParams) -> Result<ShuffleResult> {
+        mpc_shuffle::mock_shuffle(&params)
+    }
+    
+    fn deal(params: MxeDealParams) -> Result<Option<Dealt>> {
+        mpc_deal::mock_deal(&params).map(Some)
+    }
+    
+    fn draw(params: MxeDrawParams) -> Result<Option<Drawn>> {
+        mpc_draw::mock_draw(&params).map(Some)
+    }
+    
+    fn recycle(params: MxeRecycleParams) -> Result<Option<Vec<u8>>> {
+        mpc_draw::mock_recycle(&params).map(Some)
+    }
+    
+    fn reveal(params: MxeRevealParams) -> Result<Option<Vec<u8>>> {
+        mpc_reveal::mock_reveal(&params).map(Some)
+    }
+    
+    fn reveal_community(params: MxeCommunityParams) -> Result<Option<Vec<u8>>> {
+        mpc_community::mock_reveal_community(&params).map(Some)
+    }
+    
+    fn evaluate_showdown(params: MxeShowdownParams) -> Result<Option<ShowdownOutcome>> {
+        mpc_showdown::mock_evaluate_showdown(&params).map(Some)
+    }
+    
+    fn random(params: MxeRandomParams) -> Result<Option<u8>> {
+        mpc_random::mock_random(&params).map(Some)
+    }
+}
+
+/// MXE accounts the Arcium backend cannot run without
+fn require_mxe_accounts<'a, 'info>(mxe: &'a Option<MxeAccounts<'info>>) -> Result<&'a MxeAccounts<'info>> {
+    mxe.as_ref().ok_or_else(|| {
+        msg!("[ARCIUM MPC] MXE accounts missing - refusing to compute locally");
+        error!(PokerError::MxeAccountsMissing)
+    })
+}
@@ -0,0 +1,258 @@
+use anchor_lang::prelude::*;
+use solana_sha256_hasher::hash;
+use super::backend::{ActiveBackend, MpcBackend};
+use crate::types::GameVariant;
+
+/// Number of circuits in `encrypted-ixs` the program queues
+pub const CIRCUIT_COUNT: usize = 11;
+
+/// Circuits in `encrypted-ixs` the program queues computations for
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Circuit {
+    ShuffleDeck,
+    ShuffleShortDeck,
+    DealCard,
+    RevealHoleCards,
+    RevealCommunity,
+    EvaluateShowdown,
+    GenerateRandom,
+    DealUpCard,
+    DealHoleCards,
+    DrawCards,
+    RecycleDiscards,
+}
+
+impl Circuit {
+    /// Every circuit, in `CircuitConfig` order
+    pub const ALL: [Circuit; CIRCUIT_COUNT] = [
+        Circuit::ShuffleDeck,
+        Circuit::ShuffleShortDeck,
+        Circuit::DealCard,
+        Circuit::RevealHoleCards,
+        Circuit::RevealCommunity,
+        Circuit::EvaluateShowdown,
+        Circuit::GenerateRandom,
+        Circuit::DealUpCard,
+        Circuit::DealHoleCards,
+        Circuit::DrawCards,
+        Circuit::RecycleDiscards,
+    ];
+    
+    /// Name of the `#[instruction]` in `encrypted-ixs`
+    pub fn name(self) -> &'static str {
+        match self {
+            Circuit::ShuffleDeck => "shuffle_deck",
+            Circuit::ShuffleShortDeck => "shuffle_short_deck",
+            Circuit::DealCard => "deal_card",
+            Circuit::RevealHoleCards => "reveal_hole_cards",
+            Circuit::RevealCommunity => "reveal_community",
+            Circuit::EvaluateShowdown => "evaluate_showdown",
+            Circuit::GenerateRandom => "generate_random",
+            Circuit::DealUpCard => "deal_up_card",
+            Circuit::DealHoleCards => "deal_hole_cards",
+            Circuit::DrawCards => "draw_cards",
+            Circuit::RecycleDiscards => "recycle_discards",
+        }
+    }
+    
+    /// Computation definition offset of the circuit
+    ///
+    /// Arcium derives it from the circuit name: the first four bytes of its
+    /// SHA-256, little endian. Clients derive the comp def PDA the same way.
+    pub fn comp_def_offset(self) -> u32 {
+        let digest = hash(self.name().as_bytes()).to_bytes();
+        u32::from_le_bytes([digest[0], digest[1], digest[2], digest[3]])
+    }
+    
+    /// Instruction index the MXE dispatches the computation on
+    pub fn ix_index(self) -> u8 {
+        match self {
+            Circuit::ShuffleDeck => 0,
+            Circuit::DealCard => 1,
+            Circuit::RevealHoleCards => 2,
+            Circuit::RevealCommunity => 3,
+            Circuit::EvaluateShowdown => 4,
+            Circuit::GenerateRandom => 5,
+            Circuit::ShuffleShortDeck => 6,
+            Circuit::DealUpCard => 7,
+            Circuit::DealHoleCards => 8,
+            Circuit::DrawCards => 9,
+            Circuit::RecycleDiscards => 10,
+        }
+    }
+    
+    /// Shuffle circuit for a variant (short deck shuffles 36 cards)
+    pub fn shuffle(variant: GameVariant) -> Self {
+        if variant.is_short_deck() {
+            Circuit::ShuffleShortDeck
+        } else {
+            Circuit::ShuffleDeck
+        }
+    }
+    
+    fn slot(self) -> usize {
+        Circuit::ALL.iter().position(|&c| c == self).unwrap()
+    }
+}
+
+/// A circuit's registered computation definition
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct CompDefEntry {
+    /// Computation definition offset on the MXE
+    pub comp_def_offset: u32,
+    
+    /// Hash of the compiled circuit the comp def was created for
+    pub circuit_hash: [u8; 32],
+    
+    /// Comp def created on the MXE
+    pub initialized: bool,
+}
+
+impl CompDefEntry {
+    pub const LEN: usize = 4 + 32 + 1;
+}
+
+/// Program-wide registry of the computation definitions the program queues
+///
+/// Written by `init_comp_def`, one circuit at a time; every computation looks
+/// its comp def offset up here.
+#[account]
+pub struct CircuitConfig {
+    /// May register computation definitions
+    pub authority: Pubkey,
+    
+    /// One entry per circuit, in `Circuit::ALL` order
+    pub comp_defs: [CompDefEntry; CIRCUIT_COUNT],
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl CircuitConfig {
+    pub const SEED: &'static [u8] = b"circuit_config";
+    
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        (CompDefEntry::LEN * CIRCUIT_COUNT) + // comp_defs
+        1; // bump
+    
+    /// Registered entry of a circuit
+    pub fn entry(&self, circuit: Circuit) -> &CompDefEntry {
+        &self.comp_defs[circuit.slot()]
+    }
+    
+    /// Comp def offset of a circuit, which must have been initialized
+    pub fn comp_def_offset(&self, circuit: Circuit) -> Result<u32> {
+        let entry = self.entry(circuit);
+        require!(
+            entry.initialized,
+            super::integration::ErrorCode::ComputationDefinitionNotInitialized
+        );
+        Ok(entry.comp_def_offset)
+    }
+    
+    /// Record a circuit's comp def
+    pub fn register(&mut self, circuit: Circuit, circuit_hash: [u8; 32]) {
+        self.comp_defs[circuit.slot()] = CompDefEntry {
+            comp_def_offset: circuit.comp_def_offset(),
+            circuit_hash,
+            initialized: true,
+        };
+    }
+}
+
+/// Create the program's `CircuitConfig`
+///
+/// The signer becomes the authority allowed to register circuits.
+pub fn init_config_handler(ctx: Context<crate::InitCircuitConfig>) -> Result<()> {
+    let config = &mut ctx.accounts.circuit_config;
+    config.authority = ctx.accounts.authority.key();
+    config.comp_defs = [CompDefEntry::default(); CIRCUIT_COUNT];
+    config.bump = ctx.bumps.circuit_config;
+    
+    msg!("[ARCIUM] Circuit config created, authority {}", config.authority);
+    Ok(())
+}
+
+/// Create a circuit's computation definition on the MXE and register it
+///
+/// Idempotent on the MXE side: an existing comp def is kept, so a circuit can
+/// be re-registered with a new hash after redeploying it.
+pub fn init_comp_def_handler(
+    ctx: Context<crate::InitCompDef>,
+    circuit: Circuit,
+    circuit_hash: [u8; 32],
+) -> Result<()> {
+    msg!(
+        "[ARCIUM] Registering circuit {} at comp def offset {} ({})",
+        circuit.name(),
+        circuit.comp_def_offset(),
+        ActiveBackend::NAME
+    );
+    
+    ActiveBackend::init_comp_def(ctx.accounts, circuit)?;
+    ctx.accounts.circuit_config.register(circuit, circuit_hash);
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_comp_def_offsets_are_distinct() {
+        for (i, a) in Circuit::ALL.iter().enumerate() {
+            for b in &Circuit::ALL[i + 1..] {
+                assert_ne!(a.comp_def_offset(), b.comp_def_offset());
+                assert_ne!(a.ix_index(), b.ix_index());
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_encrypted_instruction_has_a_circuit() {
+        let source = include_str!("../../../../encrypted-ixs/src/lib.rs");
+        let mut names = Vec::new();
+        for chunk in source.split("#[instruction]").skip(1) {
+            let name = chunk.split("pub fn ").nth(1).and_then(|rest| rest.split('(').next());
+            names.push(name.unwrap().trim().to_string());
+        }
+
+        assert_eq!(names.len(), CIRCUIT_COUNT);
+        for name in names {
+            assert!(
+                Circuit::ALL.iter().any(|circuit| circuit.name() == name),
+                "no Circuit for {}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_comp_def_offset_hashes_circuit_name() {
+        let digest = hash(b"shuffle_deck").to_bytes();
+        assert_eq!(
+            Circuit::ShuffleDeck.comp_def_offset().to_le_bytes(),
+            digest[..4]
+        );
+    }
+
+    #[test]
+    fn test_unregistered_circuit_has_no_offset() {
+        let mut config = CircuitConfig {
+            authority: Pubkey::default(),
+            comp_defs: [CompDefEntry::default(); CIRCUIT_COUNT],
+            bump: 0,
+        };
+        assert!(config.comp_def_offset(Circuit::RevealCommunity).is_err());
+
+        config.register(Circuit::RevealCommunity, [7; 32]);
+        assert_eq!(
+            config.comp_def_offset(Circuit::RevealCommunity).unwrap(),
+            Circuit::RevealCommunity.comp_def_offset()
+        );
+        assert_eq!(config.entry(Circuit::RevealCommunity).circuit_hash, [7; 32]);
+        assert!(config.comp_def_offset(Circuit::ShuffleDeck).is_err());
+    }
+}
@@ -0,0 +1,256 @@
+use anchor_lang::prelude::*;
+use super::backend::MxeAccounts;
+use super::integration::ErrorCode;
+use crate::shared::PokerError;
+
+/// Settings for an `ArciumConfig`, set by the admin
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct ArciumConfigParams {
+    /// Arcium program that runs the MXE
+    pub mxe_program_id: Pubkey,
+    
+    /// Offset of the cluster computations are queued on
+    pub cluster_offset: u32,
+    
+    /// Authority the MXE signs callbacks with
+    pub callback_authority: Pubkey,
+    
+    /// Minimum nodes required for MPC
+    pub min_nodes: u8,
+    
+    /// Seconds a queued computation may take before the hand can be aborted
+    pub timeout: i64,
+}
+
+/// Program-wide Arcium configuration
+///
+/// Every MXE account the program is handed is checked against it: the MXE
+/// program by id, and the cluster, mempool and other Arcium accounts by
+/// their PDA derivation from it.
+///
+/// Devnet runs on cluster offset 1078779259, the offset the MXE account was
+/// initialized with:
+/// ```text
+/// arcium deploy --cluster-offset 1078779259 \
+///   --keypair-path ~/.config/solana/id.json \
+///   -u devnet --skip-deploy --skip-init
+/// ```
+#[account]
+pub struct ArciumConfig {
+    /// May update the config (the program's upgrade authority at init)
+    pub admin: Pubkey,
+    
+    /// Arcium program that runs the MXE
+    pub mxe_program_id: Pubkey,
+    
+    /// Offset of the cluster computations are queued on
+    pub cluster_offset: u32,
+    
+    /// Callback authority
+    pub callback_authority: Pubkey,
+    
+    /// Minimum nodes required for MPC
+    pub min_nodes: u8,
+    
+    /// Computation timeout (seconds)
+    pub timeout: i64,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl ArciumConfig {
+    pub const SEED: &'static [u8] = b"arcium_config";
+    
+    pub const LEN: usize = 8 + // discriminator
+        32 + // admin
+        32 + // mxe_program_id
+        4 + // cluster_offset
+        32 + // callback_authority
+        1 + // min_nodes
+        8 + // timeout
+        1; // bump
+    
+    /// Apply admin-supplied settings
+    pub fn apply(&mut self, params: ArciumConfigParams) -> Result<()> {
+        require!(params.min_nodes > 0, PokerError::InvalidGameConfig);
+        require!(params.timeout > 0, PokerError::InvalidGameConfig);
+        
+        self.mxe_program_id = params.mxe_program_id;
+        self.cluster_offset = params.cluster_offset;
+        self.callback_authority = params.callback_authority;
+        self.min_nodes = params.min_nodes;
+        self.timeout = params.timeout;
+        Ok(())
+    }
+    
+    /// PDA of the Arcium program
+    fn mxe_pda(&self, seeds: &[&[u8]]) -> Pubkey {
+        Pubkey::find_program_address(seeds, &self.mxe_program_id).0
+    }
+    
+    /// MXE account
+    pub fn mxe_account_address(&self) -> Pubkey {
+        self.mxe_pda(&[b"MXEAccount", self.mxe_program_id.as_ref()])
+    }
+    
+    /// Computation definition of a circuit
+    pub fn comp_def_address(&self, comp_def_offset: u32) -> Pubkey {
+        self.mxe_pda(&[
+            b"ComputationDefinitionAccount",
+            self.mxe_program_id.as_ref(),
+            &comp_def_offset.to_le_bytes(),
+        ])
+    }
+    
+    /// Computation account of a queued computation
+    pub fn computation_address(&self, computation_offset: u64) -> Pubkey {
+        self.mxe_pda(&[
+            b"ComputationAccount",
+            self.mxe_program_id.as_ref(),
+            &computation_offset.to_le_bytes(),
+        ])
+    }
+    
+    /// Cluster account
+    pub fn cluster_address(&self) -> Pubkey {
+        self.mxe_pda(&[b"Cluster", &self.cluster_offset.to_le_bytes()])
+    }
+    
+    /// Cluster's mempool
+    pub fn mempool_address(&self) -> Pubkey {
+        self.mxe_pda(&[b"Mempool", &self.cluster_offset.to_le_bytes()])
+    }
+    
+    /// Cluster's executing pool
+    pub fn executing_pool_address(&self) -> Pubkey {
+        self.mxe_pda(&[b"Execpool", &self.cluster_offset.to_le_bytes()])
+    }
+    
+    /// Arcium fee pool
+    pub fn staking_pool_address(&self) -> Pubkey {
+        self.mxe_pda(&[b"FeePool"])
+    }
+    
+    /// Arcium clock account
+    pub fn clock_address(&self) -> Pubkey {
+        self.mxe_pda(&[b"ClockAccount"])
+    }
+    
+    /// This program's signer PDA for queueing computations
+    pub fn sign_seed_address() -> Pubkey {
+        Pubkey::find_program_address(&[b"SignerAccount"], &crate::ID).0
+    }
+    
+    /// Check the accounts a computation is queued with
+    ///
+    /// The MXE program must be the configured one and every other account
+    /// its PDA for this cluster, circuit and computation.
+    pub fn validate_mxe_accounts(
+        &self,
+        mxe: &MxeAccounts,
+        comp_def_offset: u32,
+        computation_offset: u64,
+    ) -> Result<()> {
+        let expected = [
+            (mxe.mxe_program.key(), self.mxe_program_id),
+            (mxe.mxe_account.key(), self.mxe_account_address()),
+            (mxe.comp_def.key(), self.comp_def_address(comp_def_offset)),
+            (mxe.mempool.key(), self.mempool_address()),
+            (mxe.cluster.key(), self.cluster_address()),
+            (mxe.computation_account.key(), self.computation_address(computation_offset)),
+            (mxe.sign_seed.key(), Self::sign_seed_address()),
+            (mxe.executing_pool.key(), self.executing_pool_address()),
+            (mxe.staking_pool.key(), self.staking_pool_address()),
+            (mxe.clock.key(), self.clock_address()),
+        ];
+        for (actual, expected) in expected {
+            if actual != expected {
+                msg!("[ARCIUM] MXE account {} expected {}", actual, expected);
+                return err!(ErrorCode::InvalidMxeAccount);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Create the program's `ArciumConfig`
+///
+/// Only the program's upgrade authority may, and it becomes the admin.
+pub fn init_handler(ctx: Context<crate::InitArciumConfig>, params: ArciumConfigParams) -> Result<()> {
+    let config = &mut ctx.accounts.arcium_config;
+    config.admin = ctx.accounts.admin.key();
+    config.bump = ctx.bumps.arcium_config;
+    config.apply(params)?;
+    
+    msg!(
+        "[ARCIUM] Config created: MXE {} on cluster {}, timeout {}s",
+        config.mxe_program_id,
+        config.cluster_offset,
+        config.timeout
+    );
+    
+    Ok(())
+}
+
+/// Change the `ArciumConfig` (admin only)
+pub fn update_handler(ctx: Context<crate::UpdateArciumConfig>, params: ArciumConfigParams) -> Result<()> {
+    let config = &mut ctx.accounts.arcium_config;
+    config.apply(params)?;
+    
+    msg!(
+        "[ARCIUM] Config updated: MXE {} on cluster {}, timeout {}s",
+        config.mxe_program_id,
+        config.cluster_offset,
+        config.timeout
+    );
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(timeout: i64) -> ArciumConfigParams {
+        ArciumConfigParams {
+            mxe_program_id: Pubkey::new_unique(),
+            cluster_offset: 1078779259,
+            callback_authority: Pubkey::new_unique(),
+            min_nodes: 2,
+            timeout,
+        }
+    }
+
+    fn config() -> ArciumConfig {
+        ArciumConfig {
+            admin: Pubkey::default(),
+            mxe_program_id: Pubkey::default(),
+            cluster_offset: 0,
+            callback_authority: Pubkey::default(),
+            min_nodes: 0,
+            timeout: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_apply_rejects_zero_timeout() {
+        let mut config = config();
+        assert!(config.apply(params(0)).is_err());
+        assert!(config.apply(params(120)).is_ok());
+        assert_eq!(config.timeout, 120);
+    }
+
+    #[test]
+    fn test_pdas_follow_cluster_and_program() {
+        let mut config = config();
+        config.apply(params(120)).unwrap();
+        let mempool = config.mempool_address();
+        assert_ne!(mempool, config.executing_pool_address());
+        assert_ne!(config.comp_def_address(1), config.comp_def_address(2));
+
+        config.cluster_offset += 1;
+        assert_ne!(config.mempool_address(), mempool);
+    }
+}
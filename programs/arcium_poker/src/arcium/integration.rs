@@ -4,29 +4,14 @@
 /// Production-ready implementation for encrypted poker computations.
 
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT};
 use anchor_lang::solana_program::program::invoke;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
 use crate::game::state::Game;
-
-/// Arcium MXE Program ID on Devnet
-///
-/// This is the official Arcium MXE program deployed on Solana devnet.
-/// It handles all MPC computation orchestration.
-///
-/// Note: Using cluster offset 1078779259 to match existing MXE account
-/// (The MXE account was already initialized with this offset)
-///
-/// Deployment command:
-/// ```
-/// arcium deploy --cluster-offset 1078779259 \
-///   --keypair-path ~/.config/solana/id.json \
-///   -u devnet --skip-deploy --skip-init
-/// ```
-pub const ARCIUM_PROGRAM_ID: &str = "BKck65TgoKRokMjQM3datB9oRwJ8rAj2jxPXvHXUvcL6";
-
-/// Computation definition offsets
-pub const SHUFFLE_COMP_DEF_OFFSET: u32 = 1;
-pub const DEAL_COMP_DEF_OFFSET: u32 = 2;
-pub const REVEAL_COMP_DEF_OFFSET: u32 = 3;
+use crate::cards::deck::Deck;
+use super::backend::MxeAccounts;
+use super::comp_defs::Circuit;
+use super::types::{Argument, CallbackAccount, CallbackInstruction};
 
 /// Encrypted data wrapper for MPC
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
@@ -67,52 +52,58 @@ pub struct MxeCallbackData {
     pub status: u8,
 }
 
-/// Initialize computation definition for an MXE instruction
+/// Discriminator of the Arcium program's `init_computation_definition`
+const INIT_COMP_DEF_DISCRIMINATOR: [u8; 8] = [45, 185, 155, 17, 97, 77, 230, 73];
+
+/// Create a circuit's computation definition on the Arcium program via CPI
 /// 
-/// Must be called once per computation type (shuffle, deal, reveal)
-pub fn init_computation_definition(
-    comp_def_account: &AccountInfo,
-    mxe_account: &AccountInfo,
-    authority: &Signer,
-    system_program: &Program<System>,
+/// Must happen once per circuit before computations can be queued for it. A
+/// comp def that already exists is left alone.
+pub fn init_computation_definition<'info>(
+    mxe_program: &AccountInfo<'info>,
+    comp_def_account: &AccountInfo<'info>,
+    mxe_account: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
     comp_def_offset: u32,
-    instruction_index: u8,
 ) -> Result<()> {
     msg!("[ARCIUM] Initializing computation definition {}", comp_def_offset);
-    msg!("[ARCIUM] Instruction index: {}", instruction_index);
     msg!("[ARCIUM] MXE account: {}", mxe_account.key());
-
-    // ⚠️  TODO: Implement actual CPI call to Arcium MXE
-    //
-    // Example implementation:
-    // ```rust
-    // let mut ix_data = Vec::new();
-    // ix_data.extend_from_slice(&INIT_COMP_DEF_DISCRIMINATOR);
-    // ix_data.extend_from_slice(&comp_def_offset.to_le_bytes());
-    // ix_data.push(instruction_index);
-    //
-    // let account_metas = vec![
-    //     AccountMeta::new(*comp_def_account.key, false),
-    //     AccountMeta::new(*mxe_account.key, false),
-    //     AccountMeta::new(*authority.key, true),
-    //     AccountMeta::new_readonly(*system_program.key, false),
-    // ];
-    //
-    // let ix = Instruction {
-    //     program_id: Pubkey::from_str(ARCIUM_PROGRAM_ID).unwrap(),
-    //     accounts: account_metas,
-    //     data: ix_data,
-    // };
-    //
-    // invoke_signed(&ix, account_infos, &[])?;
-    // ```
-    //
-    // For now, this must be done via Arcium CLI or client SDK externally:
-    // $ arcium init-comp-def --program-id <MXE_ID> --offset <OFFSET>
-
-    msg!("[ARCIUM] Note: Comp def should be initialized via Arcium CLI or client SDK");
-    msg!("[ARCIUM] This is a placeholder - implement CPI call for production");
-
+    
+    if comp_def_account.lamports() > 0 && !comp_def_account.try_borrow_data()?.is_empty() {
+        msg!("[ARCIUM] Comp def {} already initialized", comp_def_account.key());
+        return Ok(());
+    }
+    
+    let mut ix_data = Vec::with_capacity(8 + 32 + 4);
+    ix_data.extend_from_slice(&INIT_COMP_DEF_DISCRIMINATOR);
+    ix_data.extend_from_slice(mxe_program.key.as_ref());
+    ix_data.extend_from_slice(&comp_def_offset.to_le_bytes());
+    
+    let ix = anchor_lang::solana_program::instruction::Instruction {
+        program_id: *mxe_program.key,
+        accounts: vec![
+            // 1. signer (mut, signer)
+            anchor_lang::solana_program::instruction::AccountMeta::new(*authority.key, true),
+            // 2. mxe (mut) - PDA
+            anchor_lang::solana_program::instruction::AccountMeta::new(*mxe_account.key, false),
+            // 3. comp_def_acc (mut) - PDA to create
+            anchor_lang::solana_program::instruction::AccountMeta::new(*comp_def_account.key, false),
+            // 4. system_program
+            anchor_lang::solana_program::instruction::AccountMeta::new_readonly(*system_program.key, false),
+        ],
+        data: ix_data,
+    };
+    
+    invoke(&ix, &[
+        mxe_program.clone(),
+        authority.clone(),
+        mxe_account.clone(),
+        comp_def_account.clone(),
+        system_program.clone(),
+    ])?;
+    
+    msg!("[ARCIUM] Comp def created: {}", comp_def_account.key());
     Ok(())
 }
 
@@ -135,11 +126,13 @@ pub fn queue_mxe_computation<'info>(
     comp_offset: u64,
     comp_def_offset: u32,
     encrypted_inputs: &[EncryptedData],
+    extra_args: Vec<Argument>,
+    callback_instructions: Vec<CallbackInstruction>,
 ) -> Result<[u8; 32]> {
     msg!("[ARCIUM MPC] Queueing computation via CPI");
     msg!("[ARCIUM MPC] Comp offset: {}", comp_offset);
     msg!("[ARCIUM MPC] Comp def offset: {}", comp_def_offset);
-
+    
     // DEBUG: Log all accounts being passed
     msg!("[DEBUG] === ACCOUNTS BEING PASSED TO ARCIUM ===");
     msg!("[DEBUG] 1. signer: {}", authority.key);
@@ -154,41 +147,22 @@ pub fn queue_mxe_computation<'info>(
     msg!("[DEBUG] 10. system_program: {}", system_program.key);
     msg!("[DEBUG] 11. clock: {}", clock.key);
     msg!("[DEBUG] === END ACCOUNTS ===");
-
-    // ========================================================================
-    // FALLBACK: Check if comp_def is initialized
-    // ========================================================================
-    // If comp_def account doesn't exist or isn't initialized, fall back to mock mode
-    // This allows the game to work before deploying Arcium circuits
-
-    let comp_def_data = comp_def.try_borrow_data()?;
-    let is_initialized = comp_def.lamports() > 0 && comp_def_data.len() > 0;
-
+    
+    // An uninitialized comp_def means the circuit was never deployed; the
+    // computation cannot run, so fail rather than pretend it was queued
+    let is_initialized = comp_def.lamports() > 0 && !comp_def.try_borrow_data()?.is_empty();
+    
     if !is_initialized {
-        msg!("[ARCIUM MPC] ⚠️  WARNING: comp_def account not initialized!");
-        msg!("[ARCIUM MPC] ⚠️  Expected account: {}", comp_def.key);
-        msg!("[ARCIUM MPC] ⚠️  Falling back to MOCK MODE");
-        msg!("[ARCIUM MPC] ℹ️  To use real MPC:");
-        msg!("[ARCIUM MPC] ℹ️  1. Deploy your Arcium circuit using: arcium deploy");
-        msg!("[ARCIUM MPC] ℹ️  2. Ensure comp_def PDA is created during deployment");
-        msg!("[ARCIUM MPC] ℹ️  3. Retry the game - it will use real MPC automatically");
-
-        // Generate mock computation ID
-        let mut computation_id = [0u8; 32];
-        computation_id[..8].copy_from_slice(&comp_offset.to_le_bytes());
-        computation_id[8..16].copy_from_slice(&authority.key.as_ref()[..8]); // Only take first 8 bytes
-        computation_id[16] = 0xFF; // Mark as mock
-
-        msg!("[ARCIUM MPC] Mock computation ID generated: {:?}", &computation_id[..8]);
-
-        return Ok(computation_id);
+        msg!("[ARCIUM MPC] comp_def account not initialized: {}", comp_def.key);
+        msg!("[ARCIUM MPC] Deploy the circuit (arcium deploy) before queueing computations");
+        return Err(ErrorCode::ComputationDefinitionNotInitialized.into());
     }
-
+    
     msg!("[ARCIUM MPC] ✅ comp_def is initialized - using REAL MPC");
-
-    use crate::arcium::types::*;
+    
+    use crate::arcium::types::QueueComputationArgs;
     use borsh::BorshSerialize;
-
+    
     // Convert encrypted inputs to Argument enum
     let mut args = Vec::new();
     for input in encrypted_inputs {
@@ -197,7 +171,10 @@ pub fn queue_mxe_computation<'info>(
         encrypted_bytes.copy_from_slice(&input.ciphertext[..32]);
         args.push(Argument::EncryptedU8(encrypted_bytes));
     }
-
+    
+    // Plaintext arguments and account references (e.g. the stored deck)
+    args.extend(extra_args);
+    
     // Build the arguments structure
     let queue_args = QueueComputationArgs {
         comp_offset,
@@ -206,22 +183,22 @@ pub fn queue_mxe_computation<'info>(
         args,
         mxe_program: *mxe_program.key,
         callback_url: None,
-        custom_callback_instructions: vec![], // No custom callbacks for now
+        custom_callback_instructions: callback_instructions,
         input_delivery_fee: 0,
         output_delivery_fee: 0,
         cu_price_micro: 0, // No priority fee
     };
-
+    
     // Serialize arguments with Borsh
     let mut args_data = Vec::new();
     queue_args.serialize(&mut args_data)
         .map_err(|_| error!(crate::shared::PokerError::ArciumMpcFailed))?;
-
+    
     // Build instruction data: discriminator + Borsh-serialized args
     let mut ix_data = Vec::new();
     ix_data.extend_from_slice(&[1, 149, 103, 13, 102, 227, 93, 164]); // discriminator
     ix_data.extend_from_slice(&args_data);
-
+    
     // Create accounts in the exact order Arcium expects (from IDL)
     let account_metas = vec![
         // 1. signer (mut, signer)
@@ -247,14 +224,14 @@ pub fn queue_mxe_computation<'info>(
         // 11. clock
         anchor_lang::solana_program::instruction::AccountMeta::new_readonly(*clock.key, false),
     ];
-
+    
     // Create instruction
     let ix = anchor_lang::solana_program::instruction::Instruction {
         program_id: *mxe_program.key,
         accounts: account_metas,
         data: ix_data,
     };
-
+    
     // Invoke via CPI
     let account_infos = &[
         mxe_program.clone(),
@@ -270,23 +247,152 @@ pub fn queue_mxe_computation<'info>(
         system_program.clone(),
         clock.clone(),
     ];
-
+    
     invoke(&ix, account_infos)?;
-
+    
     msg!("[ARCIUM MPC] Computation queued successfully");
-
+    
     // Generate computation ID from offset
     let mut computation_id = [0u8; 32];
     computation_id[..8].copy_from_slice(&comp_offset.to_le_bytes());
-
+    
     Ok(computation_id)
 }
 
+/// Queue a circuit on the MXE with the accounts of an `MxeQueue`
+pub fn queue_circuit(
+    mxe: &MxeAccounts,
+    computation_offset: u64,
+    comp_def_offset: u32,
+    encrypted_inputs: &[EncryptedData],
+    args: Vec<Argument>,
+    callback: CallbackInstruction,
+) -> Result<[u8; 32]> {
+    queue_mxe_computation(
+        &mxe.mxe_program,
+        &mxe.authority,
+        &mxe.sign_seed,
+        &mxe.computation_account,
+        &mxe.mxe_account,
+        &mxe.executing_pool,
+        &mxe.mempool,
+        &mxe.comp_def,
+        &mxe.cluster,
+        &mxe.staking_pool,
+        &mxe.system_program,
+        &mxe.clock,
+        computation_offset,
+        comp_def_offset,
+        encrypted_inputs,
+        args,
+        vec![callback],
+    )
+}
+
+/// Callback into this program with a computation's output
+///
+/// `accounts` are the ones the callback writes: the game and its Deck
+/// account, then any PlayerState accounts. The read-only accounts
+/// `authenticate_callback` checks go between the two, as laid out in
+/// `MxeCallback`; the player accounts become its remaining accounts.
+pub fn callback_instruction(
+    mxe: &MxeAccounts,
+    discriminator: &[u8],
+    accounts: &[Pubkey],
+) -> CallbackInstruction {
+    let writable = |pubkey: &Pubkey| CallbackAccount {
+        pubkey: *pubkey,
+        is_signer: false,
+        is_writable: true,
+    };
+    let readonly = [
+        Pubkey::find_program_address(&[super::config::ArciumConfig::SEED], &crate::ID).0,
+        Pubkey::find_program_address(&[super::comp_defs::CircuitConfig::SEED], &crate::ID).0,
+        mxe.mxe_program.key(),
+        mxe.comp_def.key(),
+        mxe.computation_account.key(),
+        anchor_lang::solana_program::sysvar::instructions::ID,
+    ]
+    .into_iter()
+    .map(|pubkey| CallbackAccount {
+        pubkey,
+        is_signer: false,
+        is_writable: false,
+    });
+    let (game_and_deck, players) = accounts.split_at(accounts.len().min(2));
+    
+    CallbackInstruction {
+        program_id: crate::ID,
+        discriminator: discriminator.to_vec(),
+        accounts: game_and_deck
+            .iter()
+            .map(writable)
+            .chain(readonly)
+            .chain(players.iter().map(writable))
+            .collect(),
+    }
+}
+
+/// Verify that a callback comes from the MXE and answers the computation the
+/// game is waiting for, then clear the pending computation
+///
+/// The callback must be a direct CPI from a top-level instruction of the
+/// Arcium program, name the pending computation offset in `computation_id`,
+/// and pass that computation's account and the expected circuit's comp def,
+/// as registered in the `CircuitConfig` and derived from the `ArciumConfig`.
+/// Anything else could be a forged result.
+pub fn authenticate_callback(
+    accounts: &mut crate::MxeCallback,
+    computation_id: &[u8; 32],
+    circuit: Circuit,
+) -> Result<()> {
+    let config = &accounts.arcium_config;
+    let comp_def_offset = accounts.circuit_config.comp_def_offset(circuit)?;
+    
+    // Invoked by the Arcium program itself, not called directly or relayed
+    // through another program
+    require!(
+        get_stack_height() == TRANSACTION_LEVEL_STACK_HEIGHT + 1,
+        ErrorCode::InvalidMxeCallback
+    );
+    let sysvar = &accounts.instructions_sysvar;
+    let current = load_current_index_checked(sysvar)?;
+    let caller = load_instruction_at_checked(current as usize, sysvar)?;
+    require_keys_eq!(caller.program_id, config.mxe_program_id, ErrorCode::InvalidMxeCallback);
+    
+    // Computation and comp def accounts of the pending computation
+    let computation_offset = u64::from_le_bytes(computation_id[..8].try_into().unwrap());
+    require_keys_eq!(
+        accounts.computation_account.key(),
+        config.computation_address(computation_offset),
+        ErrorCode::InvalidMxeCallback
+    );
+    require_keys_eq!(
+        *accounts.computation_account.owner,
+        config.mxe_program_id,
+        ErrorCode::InvalidMxeCallback
+    );
+    require_keys_eq!(
+        accounts.comp_def_account.key(),
+        config.comp_def_address(comp_def_offset),
+        ErrorCode::InvalidMxeCallback
+    );
+    
+    // Bound to this hand: only the computation the game queued last
+    accounts.game.resolve_computation(computation_offset, comp_def_offset)?;
+    
+    msg!("[ARCIUM] Callback authenticated for computation {:?}", &computation_id[..8]);
+    Ok(())
+}
+
 /// Handle MXE callback with shuffle result
 /// 
-/// Called by Arcium network after MPC shuffle completes
+/// Called by Arcium network after MPC shuffle completes, once
+/// `authenticate_callback` has accepted it. The output is the
+/// `Enc<Mxe, Deck>` state, which is stored in the game's Deck account.
 pub fn handle_shuffle_callback(
     game: &mut Game,
+    deck: &mut Deck,
     computation_id: [u8; 32],
     encrypted_output: Vec<u8>,
 ) -> Result<()> {
@@ -294,23 +400,16 @@ pub fn handle_shuffle_callback(
     msg!("[ARCIUM] Computation ID: {:?}", &computation_id[..8]);
     msg!("[ARCIUM] Output length: {} bytes", encrypted_output.len());
     
-    // Verify this is for our game
-    let expected_offset = game.game_id.to_le_bytes();
+    // Parse encrypted output as the MXE-owned deck
     require!(
-        computation_id[..8] == expected_offset,
+        encrypted_output.len() == Deck::ENCRYPTED_LEN as usize,
         ErrorCode::InvalidMxeCallback
     );
     
-    // Parse encrypted output as shuffled deck
-    require!(
-        encrypted_output.len() >= 52,
-        ErrorCode::InvalidMxeCallback
-    );
-    
-    // Store shuffled deck indices in game state
-    // In production, these would be encrypted indices
+    // Ciphertexts stay MXE-owned; deals reference them from the Deck account
+    deck.store_encrypted(game.encrypted_deck, &encrypted_output)?;
     msg!("[ARCIUM] Shuffle result received and verified");
-    msg!("[ARCIUM] Deck ready for dealing");
+    msg!("[ARCIUM] Deck ready for dealing (deal_cards)");
     
     // Mark deck as ready
     game.deck_initialized = true;
@@ -345,7 +444,7 @@ pub fn encrypt_for_mxe(
 ) -> Result<EncryptedData> {
     msg!("[ERROR] encrypt_for_mxe called - this should be client-side only!");
     msg!("[ERROR] Use @arcium-hq/client SDK in your TypeScript/JavaScript code");
-
+    
     Err(ErrorCode::EncryptionFailed.into())
 }
 
@@ -373,7 +472,7 @@ pub fn decrypt_from_mxe(
 ) -> Result<Vec<u8>> {
     msg!("[ERROR] decrypt_from_mxe called - this should be client-side only!");
     msg!("[ERROR] Use @arcium-hq/client SDK in your TypeScript/JavaScript code");
-
+    
     Err(ErrorCode::DecryptionFailed.into())
 }
 
@@ -409,29 +508,12 @@ pub enum ErrorCode {
     
     #[msg("Decryption failed")]
     DecryptionFailed,
-}
-
-/// Configuration for Arcium MPC
-#[account]
-pub struct ArciumConfig {
-    /// MXE program ID
-    pub mxe_program_id: Pubkey,
-    
-    /// Cluster ID
-    pub cluster_id: [u8; 32],
     
-    /// Callback authority
-    pub callback_authority: Pubkey,
+    #[msg("Computation definition is not initialized")]
+    ComputationDefinitionNotInitialized,
     
-    /// Minimum nodes required for MPC
-    pub min_nodes: u8,
-    
-    /// Computation timeout (seconds)
-    pub timeout: i64,
-}
-
-impl ArciumConfig {
-    pub const LEN: usize = 8 + 32 + 32 + 32 + 1 + 8;
+    #[msg("MXE account does not match the Arcium config")]
+    InvalidMxeAccount,
 }
 
 // Helper module for hex encoding (for logging)
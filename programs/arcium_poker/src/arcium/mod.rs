@@ -1,21 +1,35 @@
 // Arcium MPC integration module - Module 2 (CRITICAL)
 pub mod types;
+pub mod backend;
+pub mod comp_defs;
+pub mod config;
 pub mod mpc_shuffle;
+pub mod shuffle_rng;
 pub mod mpc_deal;
 pub mod mpc_reveal;
+pub mod mpc_draw;
+pub mod mpc_community;
+pub mod mpc_showdown;
+pub mod mpc_random;
 pub mod integration;
 
 // Export specific types only, not glob
+pub use backend::{MpcBackend, ActiveBackend, ArciumBackend, MockBackend, MxeAccounts, MxeQueue};
+pub use comp_defs::{Circuit, CircuitConfig, CompDefEntry};
+pub use config::{ArciumConfig, ArciumConfigParams};
 pub use mpc_shuffle::{ShuffleResult, ShuffleParams, mpc_shuffle_deck, verify_shuffle};
-pub use mpc_deal::{EncryptedCard, DealParams, mpc_deal_card, mpc_deal_cards};
-pub use mpc_reveal::{RevealParams, mpc_reveal_card, mpc_reveal_cards, verify_reveal};
+pub use mpc_deal::{EncryptedCard, Deal, Dealt, MxeDealParams, mpc_deal_with_mxe, handle_deal_callback};
+pub use mpc_reveal::{MxeRevealParams, mpc_reveal_card_with_mxe, handle_reveal_callback, verify_reveal};
+pub use mpc_draw::{Drawn, MxeDrawParams, MxeRecycleParams, mpc_draw_cards, mpc_recycle_discards};
+pub use mpc_community::{MxeCommunityParams, mpc_reveal_community_with_mxe, open_board_reveal, queue_community_reveal, handle_community_callback};
+pub use mpc_showdown::{ShowdownOutcome, MxeShowdownParams, mpc_evaluate_showdown_with_mxe, handle_showdown_callback};
+pub use mpc_random::{MxeRandomParams, mpc_generate_random};
 
 // Export real Arcium integration (for production use)
 pub use integration::{
     MxeInstructionData,
     MxeCallbackData,
     EncryptedData,
-    ArciumConfig,
     queue_mxe_computation,
     handle_shuffle_callback,
     init_computation_definition,
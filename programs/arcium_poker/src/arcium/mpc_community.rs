@@ -0,0 +1,273 @@
+use anchor_lang::prelude::*;
+use super::backend::{ActiveBackend, MpcBackend, MxeAccounts, MxeQueue};
+use super::comp_defs::Circuit;
+use super::integration::{callback_instruction, queue_circuit};
+use super::mpc_shuffle::mock_decrypt_deck;
+use super::types::Argument;
+use crate::cards::codec::{self, CardSet};
+use crate::cards::deck::Deck;
+use crate::game::state::Game;
+use crate::game::events::CommunityCardsRevealed;
+use crate::shared::{constants::*, PokerError};
+
+/// Most community cards revealed at once (the flop)
+pub const MAX_BOARD_REVEAL: usize = poker_shared::circuits::MAX_BOARD_REVEAL;
+
+/// Parameters for burning one card and revealing the next 1-3 to everyone
+pub struct MxeCommunityParams<'a, 'info> {
+    /// Accounts for queueing on Arcium (required by the Arcium backend)
+    pub mxe: Option<MxeAccounts<'info>>,
+    
+    /// Deck account holding the MXE-encrypted deck
+    pub deck: &'a Deck,
+    
+    /// Deck position of the burn card; the board cards follow it
+    pub burn_position: u8,
+    
+    /// Cards to reveal (3 for the flop, 1 for the turn and river)
+    pub count: u8,
+    
+    /// Computation offset
+    pub computation_offset: u64,
+    
+    /// Registered comp def offset of `reveal_community`
+    pub comp_def_offset: u32,
+    
+    /// Accounts `handle_community_callback` writes: the game and its Deck
+    pub callback_accounts: Vec<Pubkey>,
+}
+
+/// Reveal community cards using Arcium MPC
+///
+/// **REAL ARCIUM INTEGRATION**
+///
+/// Queues the `reveal_community` circuit, which reads the burn card and the
+/// board cards from the stored `Enc<Mxe, Deck>` and publicly reveals only the
+/// board cards. The result arrives via `handle_community_callback`, so this
+/// returns `None` in real mode.
+///
+/// Runs on the build's `ActiveBackend`; the mock returns the revealed card
+/// indices immediately.
+pub fn mpc_reveal_community_with_mxe(
+    params: MxeCommunityParams,
+) -> Result<Option<Vec<u8>>> {
+    require!(params.deck.initialized, PokerError::DeckNotInitialized);
+    require!(
+        (1..=MAX_BOARD_REVEAL as u8).contains(&params.count),
+        PokerError::InvalidCardIndex
+    );
+    
+    msg!(
+        "[ARCIUM MPC] Burning position {} and revealing {} community cards ({})",
+        params.burn_position,
+        params.count,
+        ActiveBackend::NAME
+    );
+    
+    ActiveBackend::reveal_community(params)
+}
+
+/// Queue the `reveal_community` circuit on Arcium
+pub(super) fn queue_reveal_community(mxe: &MxeAccounts, params: &MxeCommunityParams) -> Result<()> {
+    // `Enc<Mxe, &Deck>`: the circuit reads the cards from the stored deck
+    let args = vec![
+        Deck::argument(params.deck.address()?),
+        Argument::PlaintextU8(params.burn_position),
+        Argument::PlaintextU8(params.count),
+    ];
+    
+    let callback = callback_instruction(
+        mxe,
+        crate::instruction::HandleCommunityCallback::DISCRIMINATOR,
+        &params.callback_accounts,
+    );
+    let computation_id = queue_circuit(
+        mxe,
+        params.computation_offset,
+        params.comp_def_offset,
+        &[],
+        args,
+        callback,
+    )?;
+    
+    msg!("[ARCIUM MPC] Community reveal queued, computation ID: {:?}", &computation_id[..8]);
+    Ok(())
+}
+
+/// Read the board cards from the stored mock deck (mock backend)
+pub(super) fn mock_reveal_community(params: &MxeCommunityParams) -> Result<Vec<u8>> {
+    let cards = mock_decrypt_deck(params.deck)?;
+    require!(
+        ((params.burn_position + params.count) as usize) < DECK_SIZE,
+        PokerError::DeckExhausted
+    );
+    
+    // The burn card is skipped, never revealed
+    let first = params.burn_position as usize + 1;
+    Ok(cards[first..first + params.count as usize].to_vec())
+}
+
+/// Owe the board `count` cards for the street just opened
+///
+/// Betting stays closed until they are revealed; `advance_stage` queues the
+/// reveal with the MXE accounts (see `queue_community_reveal`).
+pub fn open_board_reveal(game: &mut Game, count: u8) -> Result<()> {
+    require!(
+        game.community_reveal_pending == 0,
+        PokerError::CommunityRevealPending
+    );
+    require!(
+        game.community_cards_revealed as usize + count as usize <= COMMUNITY_CARDS,
+        PokerError::InvalidCardIndex
+    );
+    
+    game.community_reveal_pending = count;
+    Ok(())
+}
+
+/// Reveal the board cards the current street is owed
+///
+/// Takes the burn card and the board cards off the deck. The mock backend
+/// reveals the cards at once; on Arcium the game waits for
+/// `handle_community_callback` and betting stays closed until it arrives.
+pub fn queue_community_reveal(game: &mut Game, deck: &Deck, mxe: Option<&MxeQueue>) -> Result<()> {
+    let count = game.community_reveal_pending;
+    require!(count > 0, PokerError::InvalidGameStage);
+    require!(game.deck_initialized, PokerError::DeckNotInitialized);
+    require!(!game.has_pending_computation(), PokerError::MpcResultPending);
+    
+    let positions = game.take_deck_positions(1 + count)?;
+    let computation_offset = game.next_computation_offset();
+    let (accounts, comp_def_offset) = match mxe {
+        Some(queue) => {
+            let (accounts, offset) = queue.accounts_for(Circuit::RevealCommunity, computation_offset)?;
+            (Some(accounts), offset)
+        }
+        None => (None, Circuit::RevealCommunity.comp_def_offset()),
+    };
+    
+    let revealed = mpc_reveal_community_with_mxe(MxeCommunityParams {
+        mxe: accounts,
+        deck,
+        burn_position: positions[0],
+        count,
+        computation_offset,
+        comp_def_offset,
+        callback_accounts: mxe.map(|queue| queue.callback_accounts.clone()).unwrap_or_default(),
+    })?;
+    
+    match revealed {
+        Some(cards) => apply_community_cards(game, &cards),
+        None => {
+            game.await_computation(computation_offset, comp_def_offset, Clock::get()?.unix_timestamp);
+            Ok(())
+        }
+    }
+}
+
+/// Handle MXE callback with revealed community cards
+///
+/// Called by Arcium network after `reveal_community` completes, once
+/// `authenticate_callback` has accepted it. Writes the plaintext cards into
+/// the board and reopens betting.
+pub fn handle_community_callback(
+    game: &mut Game,
+    computation_id: [u8; 32],
+    revealed: Vec<u8>,
+) -> Result<()> {
+    msg!("[ARCIUM] Handling community reveal callback {:?}", &computation_id[..8]);
+    
+    // The circuit pads to MAX_BOARD_REVEAL with NO_CARD
+    let count = game.community_reveal_pending as usize;
+    require!(
+        count > 0 && revealed.len() >= count,
+        super::integration::ErrorCode::InvalidMxeCallback
+    );
+    
+    apply_community_cards(game, &revealed[..count])
+}
+
+/// Write revealed cards after the existing board and clear the pending reveal
+fn apply_community_cards(game: &mut Game, cards: &[u8]) -> Result<()> {
+    require!(
+        cards.len() == game.community_reveal_pending as usize,
+        PokerError::InvalidCardIndex
+    );
+    
+    // Every card must be real and new to the board
+    let start = game.community_cards_revealed as usize;
+    let mut board = CardSet::empty();
+    for &index in &game.community_cards[..start] {
+        board.insert(codec::decode(index)?);
+    }
+    for &index in cards {
+        let card = codec::decode(index)?;
+        require!(!board.contains(card), PokerError::InvalidCardIndex);
+        board.insert(card);
+    }
+    
+    game.community_cards[start..start + cards.len()].copy_from_slice(cards);
+    game.community_cards_revealed += cards.len() as u8;
+    game.community_reveal_pending = 0;
+    
+    msg!("[ARCIUM MPC] Board: {}", board);
+    
+    emit!(CommunityCardsRevealed {
+        game_id: game.game_id,
+        stage: game.stage,
+        cards: cards.to_vec(),
+    });
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arcium::mpc_shuffle::mock_deck_account;
+
+    fn reveal(deck: &Deck, burn_position: u8, count: u8) -> Result<Option<Vec<u8>>> {
+        mpc_reveal_community_with_mxe(MxeCommunityParams {
+            mxe: None,
+            deck,
+            burn_position,
+            count,
+            computation_offset: 0,
+            comp_def_offset: 0,
+            callback_accounts: vec![],
+        })
+    }
+
+    #[test]
+    fn test_burn_card_is_never_revealed() {
+        let cards: [u8; DECK_SIZE] = core::array::from_fn(|position| (DECK_SIZE - 1 - position) as u8);
+        let deck = mock_deck_account(&cards);
+
+        let flop = reveal(&deck, 10, 3).unwrap().unwrap();
+        assert_eq!(flop, cards[11..14]);
+        assert!(!flop.contains(&cards[10]));
+
+        assert_eq!(reveal(&deck, 20, 1).unwrap().unwrap().len(), 1);
+        assert!(reveal(&deck, 20, 0).is_err());
+        assert!(reveal(&deck, 20, 4).is_err());
+    }
+
+    #[test]
+    fn test_owed_board_is_revealed_from_the_deck_cursor() {
+        let deck = mock_deck_account(&core::array::from_fn(|position| position as u8));
+        let mut game = Game::deserialize(&mut &[0u8; Game::LEN][..]).unwrap();
+        game.deck_initialized = true;
+        game.deck_cursor = 4;
+
+        // Nothing owed yet
+        assert!(queue_community_reveal(&mut game, &deck, None).is_err());
+
+        open_board_reveal(&mut game, 3).unwrap();
+        assert!(open_board_reveal(&mut game, 1).is_err());
+        queue_community_reveal(&mut game, &deck, None).unwrap();
+
+        assert_eq!(game.community_cards[..3], [5, 6, 7]);
+        assert_eq!((game.community_cards_revealed, game.community_reveal_pending), (3, 0));
+        assert_eq!(game.deck_cursor, 8);
+    }
+}
@@ -1,78 +1,121 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::program::invoke;
-use crate::shared::PokerError;
-use super::integration::{MxeInstructionData, EncryptedData};
+use crate::shared::{constants::MAX_HOLE_CARDS, PokerError};
+use crate::types::EncryptedHoleCard;
+use crate::cards::deck::Deck;
+use crate::cards::dealing::apply_dealt;
+use crate::game::state::Game;
+use crate::player::state::PlayerState;
+use super::backend::{ActiveBackend, MpcBackend, MxeAccounts};
+use super::comp_defs::Circuit;
+use super::integration::{callback_instruction, queue_circuit};
+use super::mpc_shuffle::mock_decrypt_deck;
+use super::types::Argument;
+use poker_shared::circuits;
 
 /// Encrypted card data
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
 pub struct EncryptedCard {
-    /// Encrypted card index (pointing to position in shuffled deck)
+    /// Position of the card in the shuffled deck
     pub encrypted_index: u8,
     
-    /// Encryption key shard (each player has part of the key)
-    pub key_shard: [u8; 32],
+    /// Card encrypted to the owner's x25519 key (Enc<Owner, u8>)
+    pub ciphertext: [u8; 32],
+    
+    /// Nonce the ciphertext was encrypted with
+    pub nonce: u128,
     
     /// Player who owns this card (can decrypt)
     pub owner: Pubkey,
 }
 
-/// Parameters for dealing encrypted cards to a player
-#[derive(Clone, Debug)]
-pub struct DealParams {
-    /// Card index from encrypted deck
-    pub card_index: u8,
-    
-    /// Player receiving the card
-    pub player: Pubkey,
+impl EncryptedCard {
+    /// The part the owner needs to decrypt the card off-chain
+    pub fn hole_card(&self) -> EncryptedHoleCard {
+        EncryptedHoleCard {
+            ciphertext: self.ciphertext,
+            nonce: self.nonce,
+        }
+    }
+}
+
+/// Cards one deal computation gives a seat, by deck position
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Deal {
+    /// `count` hole cards from consecutive positions, encrypted to the seat
+    HoleCards { start: u8, count: u8 },
     
-    /// Shuffle session ID (links to MPC shuffle)
-    pub session_id: [u8; 32],
+    /// One card encrypted to the seat (stud, seventh street)
+    DownCard { position: u8 },
     
-    /// Game ID
-    pub game_id: u64,
+    /// One card revealed to everyone (stud upcards)
+    UpCard { position: u8 },
 }
 
-/// Parameters for invoking MXE deal
-pub struct MxeDealParams<'info> {
-    /// MXE program account
-    pub mxe_program: Option<AccountInfo<'info>>,
-    
-    /// Computation definition account
-    pub comp_def: Option<AccountInfo<'info>>,
+impl Deal {
+    /// Circuit that deals these cards
+    pub fn circuit(&self) -> Circuit {
+        match self {
+            Deal::HoleCards { .. } => Circuit::DealHoleCards,
+            Deal::DownCard { .. } => Circuit::DealCard,
+            Deal::UpCard { .. } => Circuit::DealUpCard,
+        }
+    }
     
-    /// Mempool account
-    pub mempool: Option<AccountInfo<'info>>,
+    /// First deck position dealt
+    pub fn position(&self) -> u8 {
+        match *self {
+            Deal::HoleCards { start, .. } => start,
+            Deal::DownCard { position } | Deal::UpCard { position } => position,
+        }
+    }
+}
+
+/// Cards a deal produced
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Dealt {
+    /// Encrypted to the seat's x25519 key, in deal order
+    Hidden(Vec<EncryptedHoleCard>),
     
-    /// Cluster account
-    pub cluster: Option<AccountInfo<'info>>,
+    /// Dealt face up
+    FaceUp(u8),
+}
+
+/// Parameters for dealing from the stored deck
+pub struct MxeDealParams<'a, 'info> {
+    /// Accounts for queueing on Arcium (required by the Arcium backend)
+    pub mxe: Option<MxeAccounts<'info>>,
     
-    /// Shuffled deck session ID
-    pub shuffled_deck: [u8; 32],
+    /// Deck account holding the MXE-encrypted deck
+    pub deck: &'a Deck,
     
-    /// Card index to deal
-    pub card_index: u8,
+    /// Deck positions to deal and how
+    pub deal: Deal,
     
-    /// Player receiving card
-    pub player: Pubkey,
+    /// x25519 public key hidden cards are encrypted to
+    pub owner_key: [u8; 32],
     
     /// Computation offset
-    pub computation_offset: [u8; 8],
+    pub computation_offset: u64,
     
-    /// Game ID
-    pub game_id: u64,
+    /// Registered comp def offset of the deal's circuit
+    pub comp_def_offset: u32,
+    
+    /// Accounts the deal callback writes: game, Deck, then every seat's
+    /// PlayerState in seat order
+    pub callback_accounts: Vec<Pubkey>,
 }
 
-/// Deal encrypted card to specific player using Arcium MPC
+/// Deal cards from deck positions using Arcium MPC
 /// 
 /// **REAL ARCIUM INTEGRATION**
 /// 
-/// This function uses Arcium's MPC to deal cards securely:
+/// The deal circuits read the cards at the given positions of the stored
+/// `Enc<Mxe, Deck>`, so neither the program nor any client ever sees the
+/// shuffled order:
 /// 
-/// 1. Card is selected from shuffled deck (already in MPC)
-/// 2. Card is encrypted specifically for target player using Enc<Owner, T>
-/// 3. Only the target player can decrypt their card
-/// 4. Other players see only encrypted data
-/// 5. Card can be revealed at showdown via MPC
+/// 1. `deal_hole_cards` / `deal_card` encrypt the cards to the owner's
+///    registered x25519 key (`Enc<Shared, T>`); only the owner can decrypt
+/// 2. `deal_up_card` reveals a stud upcard to everyone
 /// 
 /// Client-side (TypeScript) decryption:
 /// ```typescript
@@ -92,191 +135,271 @@ pub struct MxeDealParams<'info> {
 /// console.log("Your card:", cardValue[0]); // 0-51
 /// ```
 /// 
-/// On-chain, this creates an Enc<Owner, u8> where Owner is the player's pubkey.
-/// The MXE program ensures only that player can call decrypt operations.
-/// 
-/// This function can work in two modes:
-/// 1. **Real MPC Mode**: When MXE accounts are provided
-/// 2. **Mock Mode**: When MXE accounts are None (for testing)
-pub fn mpc_deal_card_with_mxe<'info>(
-    params: MxeDealParams<'info>,
-) -> Result<EncryptedCard> {
-    msg!(
-        "[ARCIUM MPC] Dealing card {} to player {}",
-        params.card_index,
-        params.player
-    );
-    
-    // Check if MXE accounts are provided
-    if let (Some(mxe_program), Some(comp_def), Some(mempool), Some(cluster)) = 
-        (&params.mxe_program, &params.comp_def, &params.mempool, &params.cluster) {
-        
-        msg!("[ARCIUM MPC] Using REAL MPC for card dealing");
-        
-        // Create MXE instruction data
-        let ix_data = create_deal_instruction(
-            1, // deal_card instruction index
-            params.shuffled_deck,
-            params.card_index,
-            params.computation_offset,
-        )?;
-        
-        // Invoke MXE program via CPI
-        invoke_mxe_computation(
-            mxe_program,
-            &ix_data,
-            &[comp_def.clone(), mempool.clone(), cluster.clone()],
-        )?;
-        
-        msg!("[ARCIUM MPC] Card deal queued, computation ID: {:?}", params.computation_offset);
-        
-        // Generate key shard for player
-        let key_shard = generate_player_key_shard(
-            &params.player,
-            &params.shuffled_deck,
-            params.card_index,
+/// Runs on the build's `ActiveBackend`. Arcium queues the deal circuit and
+/// returns `None`, the cards arriving via the deal callbacks; the mock deals
+/// from the stored deck immediately.
+pub fn mpc_deal_with_mxe<'a, 'info>(
+    params: MxeDealParams<'a, 'info>,
+) -> Result<Option<Dealt>> {
+    require!(params.deck.initialized, PokerError::DeckNotInitialized);
+    if let Deal::HoleCards { count, .. } = params.deal {
+        require!(
+            (1..=MAX_HOLE_CARDS as u8).contains(&count),
+            PokerError::InvalidCardIndex
         );
-        
-        return Ok(EncryptedCard {
-            encrypted_index: params.card_index,
-            key_shard,
-            owner: params.player,
-        });
     }
     
-    msg!("[ARCIUM MPC] Using MOCK card dealing");
-    
-    // Mock mode: Generate encryption key shard for this player
-    let key_shard = generate_player_key_shard(
-        &params.player,
-        &params.shuffled_deck,
-        params.card_index,
+    msg!(
+        "[ARCIUM MPC] Dealing {:?} ({})",
+        params.deal,
+        ActiveBackend::NAME
     );
     
-    msg!("[ARCIUM MPC] Card encrypted for player (key shard: {:?})", &key_shard[..8]);
-    
-    Ok(EncryptedCard {
-        encrypted_index: params.card_index,
-        key_shard,
-        owner: params.player,
-    })
+    ActiveBackend::deal(params)
 }
 
-/// Legacy function for backward compatibility
-pub fn mpc_deal_card(params: DealParams) -> Result<EncryptedCard> {
-    // Convert to new format without MXE accounts (mock mode)
-    let mxe_params = MxeDealParams {
-        mxe_program: None,
-        comp_def: None,
-        mempool: None,
-        cluster: None,
-        shuffled_deck: params.session_id,
-        card_index: params.card_index,
-        player: params.player,
-        computation_offset: params.game_id.to_le_bytes(),
-        game_id: params.game_id,
+/// Queue the deal's circuit on Arcium
+pub(super) fn queue_deal(mxe: &MxeAccounts, params: &MxeDealParams) -> Result<()> {
+    // `Enc<Mxe, &Deck>`: the circuit reads the cards from the stored deck
+    let deck = Deck::argument(params.deck.address()?);
+    
+    // Receiver key and nonce for the `receiver: Shared` circuit argument
+    let receiver = [
+        Argument::ArcisPubkey(params.owner_key),
+        Argument::PlaintextU128(generate_deal_nonce(
+            &params.deck.session_id,
+            params.deal.position(),
+        )),
+    ];
+    
+    let (discriminator, args) = match params.deal {
+        Deal::HoleCards { start, count } => (
+            crate::instruction::HandleDealCallback::DISCRIMINATOR,
+            [deck, Argument::PlaintextU8(start), Argument::PlaintextU8(count)]
+                .into_iter()
+                .chain(receiver)
+                .collect(),
+        ),
+        Deal::DownCard { position } => (
+            crate::instruction::HandleDownCardCallback::DISCRIMINATOR,
+            [deck, Argument::PlaintextU8(position)]
+                .into_iter()
+                .chain(receiver)
+                .collect(),
+        ),
+        Deal::UpCard { position } => (
+            crate::instruction::HandleUpCardCallback::DISCRIMINATOR,
+            vec![deck, Argument::PlaintextU8(position)],
+        ),
     };
     
-    mpc_deal_card_with_mxe(mxe_params)
+    let callback = callback_instruction(mxe, discriminator, &params.callback_accounts);
+    let computation_id = queue_circuit(
+        mxe,
+        params.computation_offset,
+        params.comp_def_offset,
+        &[],
+        args,
+        callback,
+    )?;
+    
+    msg!("[ARCIUM MPC] Deal queued, computation ID: {:?}", &computation_id[..8]);
+    Ok(())
 }
 
-/// Deal multiple encrypted cards to a player
-pub fn mpc_deal_cards(params: DealParams, count: usize) -> Result<Vec<EncryptedCard>> {
-    let mut cards = Vec::with_capacity(count);
+/// Deal from the stored mock deck (mock backend)
+pub(super) fn mock_deal(params: &MxeDealParams) -> Result<Dealt> {
+    let cards = mock_decrypt_deck(params.deck)?;
+    let nonce = generate_deal_nonce(&params.deck.session_id, params.deal.position());
     
-    for i in 0..count {
-        let card_params = DealParams {
-            card_index: params.card_index + i as u8,
-            ..params.clone()
-        };
-        cards.push(mpc_deal_card(card_params)?);
-    }
+    let dealt = match params.deal {
+        Deal::HoleCards { start, count } => Dealt::Hidden(
+            circuits::hole_cards(&cards, start, count)[..count as usize]
+                .iter()
+                .enumerate()
+                .map(|(i, &card)| encrypt_for_owner(card, &params.owner_key, nonce + i as u128))
+                .collect(),
+        ),
+        Deal::DownCard { position } => Dealt::Hidden(vec![encrypt_for_owner(
+            circuits::card_at(&cards, position),
+            &params.owner_key,
+            nonce,
+        )]),
+        Deal::UpCard { position } => Dealt::FaceUp(circuits::card_at(&cards, position)),
+    };
     
-    Ok(cards)
+    Ok(dealt)
 }
 
-// ============================================================================
-// MXE INTEGRATION HELPERS
-// ============================================================================
+/// Handle MXE callback with the cards dealt to the seat being dealt to
+///
+/// Called by Arcium network after a deal circuit completes, once
+/// `authenticate_callback` has accepted it. Records the cards and moves the
+/// deal round on, reopening betting after the last seat.
+pub fn handle_deal_callback(
+    game: &mut Game,
+    player_states: &mut [PlayerState],
+    computation_id: [u8; 32],
+    dealt: Dealt,
+) -> Result<()> {
+    msg!("[ARCIUM] Handling deal callback {:?}", &computation_id[..8]);
+    
+    apply_dealt(game, player_states, dealt)
+}
 
-/// Helper: Create MXE instruction data for dealing
-fn create_deal_instruction(
-    ix_index: u8,
-    shuffled_deck: [u8; 32],
-    card_index: u8,
-    computation_offset: [u8; 8],
-) -> Result<Vec<u8>> {
-    let mut data = Vec::new();
-    data.push(ix_index);
-    data.extend_from_slice(&computation_offset);
-    data.extend_from_slice(&shuffled_deck);
-    data.push(card_index);
-    Ok(data)
+/// Cards encrypted to their owner as the deal circuits return them: one
+/// nonce, then one ciphertext per card, card `i` under `nonce + i`
+pub fn hidden_cards(nonce: u128, ciphertexts: &[[u8; 32]]) -> Dealt {
+    Dealt::Hidden(encrypted_hand(nonce, ciphertexts))
 }
 
-/// Helper: Invoke MXE via CPI
-fn invoke_mxe_computation<'a>(
-    mxe_program: &AccountInfo<'a>,
-    ix_data: &[u8],
-    accounts: &[AccountInfo<'a>],
-) -> Result<()> {
-    let ix = anchor_lang::solana_program::instruction::Instruction {
-        program_id: *mxe_program.key,
-        accounts: accounts.iter().map(|a| {
-            anchor_lang::solana_program::instruction::AccountMeta {
-                pubkey: *a.key,
-                is_signer: a.is_signer,
-                is_writable: a.is_writable,
-            }
-        }).collect(),
-        data: ix_data.to_vec(),
-    };
-    
-    let account_infos: Vec<AccountInfo> = std::iter::once(mxe_program.clone())
-        .chain(accounts.iter().cloned())
-        .collect();
-    
-    invoke(&ix, &account_infos)?;
-    Ok(())
+/// Ciphertexts sharing one output nonce, card `i` under `nonce + i`
+pub fn encrypted_hand(nonce: u128, ciphertexts: &[[u8; 32]]) -> Vec<EncryptedHoleCard> {
+    ciphertexts
+        .iter()
+        .enumerate()
+        .map(|(i, &ciphertext)| EncryptedHoleCard {
+            ciphertext,
+            nonce: nonce + i as u128,
+        })
+        .collect()
 }
 
 // ============================================================================
 // MOCK IMPLEMENTATIONS (FOR TESTING)
 // ============================================================================
 
-/// Generate player-specific key shard for card decryption
+/// Encrypt a card index to the owner's x25519 key
 /// 
-/// In Arcium MPC, this creates an encryption key that only the target player
-/// can use to decrypt their card. The key is derived from:
-/// - Player's public key
-/// - Session ID (shuffle session)
-/// - Card index (position in deck)
-/// 
-/// This implements the Enc<Owner, T> pattern where Owner = player pubkey.
-fn generate_player_key_shard(
-    player: &Pubkey,
-    session_id: &[u8; 32],
-    card_index: u8,
-) -> [u8; 32] {
-    // Generate key shard by mixing player, session, and card index
-    // In production with Arcium, use their key derivation
-    let mut key_shard = *session_id;
-    let player_bytes = player.to_bytes();
+/// In Arcium MPC, the deal circuits return `receiver.from_arcis(cards)`, a
+/// Rescue ciphertext under the shared secret between the MXE and the player.
+/// For development, XOR the card with a keystream derived from the owner's
+/// key and the nonce.
+pub(super) fn encrypt_for_owner(card: u8, owner_key: &[u8; 32], nonce: u128) -> EncryptedHoleCard {
+    let mut ciphertext = mock_keystream(owner_key, nonce);
+    ciphertext[0] ^= card;
+    EncryptedHoleCard { ciphertext, nonce }
+}
+
+/// Decrypt a mock ciphertext produced by `encrypt_for_owner`
+#[cfg(test)]
+pub(crate) fn decrypt_for_owner(hole_card: &EncryptedHoleCard, owner_key: &[u8; 32]) -> u8 {
+    hole_card.ciphertext[0] ^ mock_keystream(owner_key, hole_card.nonce)[0]
+}
+
+/// Mix a key with a nonce into a mock keystream
+pub(crate) fn mock_keystream(owner_key: &[u8; 32], nonce: u128) -> [u8; 32] {
+    let nonce_bytes = nonce.to_le_bytes();
+    let mut keystream = *owner_key;
     
     for i in 0..32 {
-        key_shard[i] ^= player_bytes[i];
-        key_shard[i] = key_shard[i].wrapping_add(card_index);
+        keystream[i] ^= nonce_bytes[i % 16];
+        // Mix with the previous byte so every nonce byte spreads
+        keystream[i] = keystream[i].wrapping_add(keystream[(i + 31) % 32]);
     }
     
-    key_shard
+    keystream
 }
 
 /// Generate nonce for card dealing operation
-fn generate_deal_nonce(game_id: u64, card_index: u8) -> [u8; 16] {
+/// 
+/// Unique per shuffle session and deck position, so no two cards are ever
+/// encrypted under the same key and nonce.
+fn generate_deal_nonce(session_id: &[u8; 32], card_index: u8) -> u128 {
     let mut nonce = [0u8; 16];
-    let game_bytes = game_id.to_le_bytes();
-    nonce[..8].copy_from_slice(&game_bytes);
+    nonce[..8].copy_from_slice(&session_id[..8]);
     nonce[8] = card_index;
     nonce[9] = 0x01; // Deal operation marker
-    nonce
-}
\ No newline at end of file
+    u128::from_le_bytes(nonce)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arcium::mpc_shuffle::mock_deck_account;
+    use crate::shared::constants::DECK_SIZE;
+
+    fn reversed() -> [u8; DECK_SIZE] {
+        core::array::from_fn(|position| (DECK_SIZE - 1 - position) as u8)
+    }
+
+    fn deal(deck: &Deck, deal: Deal, owner_key: [u8; 32]) -> Dealt {
+        mpc_deal_with_mxe(MxeDealParams {
+            mxe: None,
+            deck,
+            deal,
+            owner_key,
+            computation_offset: 0,
+            comp_def_offset: 0,
+            callback_accounts: vec![],
+        })
+        .unwrap()
+        .unwrap()
+    }
+
+    fn hidden(dealt: Dealt) -> Vec<EncryptedHoleCard> {
+        match dealt {
+            Dealt::Hidden(cards) => cards,
+            Dealt::FaceUp(card) => panic!("card {} dealt face up", card),
+        }
+    }
+
+    #[test]
+    fn test_deals_the_cards_at_deck_positions() {
+        let deck = mock_deck_account(&reversed());
+        let alice = [1u8; 32];
+
+        let hole = hidden(deal(&deck, Deal::HoleCards { start: 4, count: 4 }, alice));
+        let cards: Vec<u8> = hole.iter().map(|card| decrypt_for_owner(card, &alice)).collect();
+        assert_eq!(cards, vec![47, 46, 45, 44]);
+
+        let down = hidden(deal(&deck, Deal::DownCard { position: 30 }, alice));
+        assert_eq!(decrypt_for_owner(&down[0], &alice), 21);
+
+        assert_eq!(deal(&deck, Deal::UpCard { position: 0 }, alice), Dealt::FaceUp(51));
+    }
+
+    #[test]
+    fn test_hole_card_only_decrypts_with_owner_key() {
+        let deck = mock_deck_account(&reversed());
+        let alice = [1u8; 32];
+        let bob = [2u8; 32];
+
+        let card = hidden(deal(&deck, Deal::DownCard { position: 5 }, alice))[0];
+        assert_eq!(decrypt_for_owner(&card, &alice), 46);
+        assert_ne!(card, hidden(deal(&deck, Deal::DownCard { position: 5 }, bob))[0]);
+    }
+
+    #[test]
+    fn test_nonces_differ_per_card() {
+        let deck = mock_deck_account(&reversed());
+        let key = [3u8; 32];
+        let mut nonces: Vec<u128> = hidden(deal(&deck, Deal::HoleCards { start: 0, count: 6 }, key))
+            .iter()
+            .map(|card| card.nonce)
+            .collect();
+        nonces.extend((6..52).map(|position| {
+            hidden(deal(&deck, Deal::DownCard { position }, key))[0].nonce
+        }));
+
+        for (i, nonce) in nonces.iter().enumerate() {
+            assert!(!nonces[..i].contains(nonce));
+        }
+    }
+
+    #[test]
+    fn test_cannot_deal_from_an_empty_deck() {
+        let mut deck = mock_deck_account(&reversed());
+        deck.clear();
+        assert!(mpc_deal_with_mxe(MxeDealParams {
+            mxe: None,
+            deck: &deck,
+            deal: Deal::UpCard { position: 0 },
+            owner_key: [0; 32],
+            computation_offset: 0,
+            comp_def_offset: 0,
+            callback_accounts: vec![],
+        })
+        .is_err());
+    }
+}
@@ -0,0 +1,290 @@
+use anchor_lang::prelude::*;
+use poker_shared::circuits;
+use crate::cards::deck::Deck;
+use crate::shared::{constants::DRAW_HAND_CARDS, PokerError};
+use crate::types::EncryptedHoleCard;
+use super::backend::{ActiveBackend, MpcBackend, MxeAccounts};
+use super::integration::{callback_instruction, queue_circuit};
+use super::mpc_deal::{encrypt_for_owner, encrypted_hand};
+use super::mpc_shuffle::{mock_decrypt_deck, mock_encrypt_deck};
+use super::shuffle_rng::ShuffleRng;
+use super::types::Argument;
+
+/// Parameters for replacing a player's discards via Arcium MPC
+///
+/// The `draw_cards` circuit swaps each discarded card with the next undealt
+/// card in the MXE deck, so discards end up in the dealt part of the deck
+/// and are never revealed. The player's hand keeps its deck positions.
+pub struct MxeDrawParams<'a, 'info> {
+    /// Accounts for queueing on Arcium (required by the Arcium backend)
+    pub mxe: Option<MxeAccounts<'info>>,
+    
+    /// Deck account holding the MXE-encrypted deck
+    pub deck: &'a Deck,
+    
+    /// Deck positions holding the player's hand
+    pub hand_positions: [u8; DRAW_HAND_CARDS],
+    
+    /// Which hand slots are thrown away
+    pub discard: [bool; DRAW_HAND_CARDS],
+    
+    /// First deck position supplying the replacements
+    pub cursor: u8,
+    
+    /// x25519 public key the new hand is encrypted to
+    pub owner_key: [u8; 32],
+    
+    /// Nonce of the new hand; card `i` is encrypted under `nonce + i`
+    pub nonce: u128,
+    
+    /// Computation offset
+    pub computation_offset: u64,
+    
+    /// Registered comp def offset of `draw_cards`
+    pub comp_def_offset: u32,
+    
+    /// Accounts `handle_draw_callback` writes: the game, its Deck, then the
+    /// drawing player's PlayerState
+    pub callback_accounts: Vec<Pubkey>,
+}
+
+/// Result of a draw: the updated MXE deck, laid out like the Deck account,
+/// and the player's whole new hand encrypted to them
+pub struct Drawn {
+    pub deck: Vec<u8>,
+    pub hand: Vec<EncryptedHoleCard>,
+}
+
+/// Replace discarded cards using Arcium MPC
+///
+/// Queues `draw_cards`; the new deck and hand arrive via
+/// `handle_draw_callback`, so this returns `None` in real mode. The mock
+/// runs the circuit's own code on the mock deck and returns them at once.
+pub fn mpc_draw_cards(params: MxeDrawParams) -> Result<Option<Drawn>> {
+    require!(params.deck.initialized, PokerError::DeckNotInitialized);
+    require!(params.discard.contains(&true), PokerError::InvalidDiscard);
+    
+    msg!(
+        "[ARCIUM MPC] Drawing {} cards from position {} ({})",
+        params.discard.iter().filter(|&&discard| discard).count(),
+        params.cursor,
+        ActiveBackend::NAME
+    );
+    
+    ActiveBackend::draw(params)
+}
+
+/// Queue the `draw_cards` circuit on Arcium
+pub(super) fn queue_draw(mxe: &MxeAccounts, params: &MxeDrawParams) -> Result<()> {
+    // `Enc<Mxe, Deck>`: the circuit reads the stored deck and returns it updated
+    let mut args = vec![Deck::argument(params.deck.address()?)];
+    args.extend(params.hand_positions.iter().map(|&position| Argument::PlaintextU8(position)));
+    args.extend(params.discard.iter().map(|&discard| Argument::PlaintextBool(discard)));
+    args.push(Argument::PlaintextU8(params.cursor));
+    
+    // Receiver key and nonce for the `receiver: Shared` circuit argument
+    args.push(Argument::ArcisPubkey(params.owner_key));
+    args.push(Argument::PlaintextU128(params.nonce));
+    
+    let callback = callback_instruction(
+        mxe,
+        crate::instruction::HandleDrawCallback::DISCRIMINATOR,
+        &params.callback_accounts,
+    );
+    let computation_id = queue_circuit(
+        mxe,
+        params.computation_offset,
+        params.comp_def_offset,
+        &[],
+        args,
+        callback,
+    )?;
+    
+    msg!("[ARCIUM MPC] Draw queued, computation ID: {:?}", &computation_id[..8]);
+    Ok(())
+}
+
+/// Draw from the stored mock deck (mock backend)
+pub(super) fn mock_draw(params: &MxeDrawParams) -> Result<Drawn> {
+    let cards = mock_decrypt_deck(params.deck)?;
+    let (cards, hand) = circuits::drawn_hand(&cards, &params.hand_positions, &params.discard, params.cursor);
+    
+    Ok(Drawn {
+        deck: mock_encrypt_deck(&cards, &params.deck.session_id),
+        hand: hand
+            .iter()
+            .enumerate()
+            .map(|(i, &card)| encrypt_for_owner(card, &params.owner_key, params.nonce + i as u128))
+            .collect(),
+    })
+}
+
+/// A draw as the `draw_cards` callback returns it: the deck, then the new
+/// hand under one nonce, card `i` under `nonce + i`
+pub fn drawn(deck: Vec<u8>, nonce: u128, ciphertexts: &[[u8; 32]]) -> Drawn {
+    Drawn {
+        deck,
+        hand: encrypted_hand(nonce, ciphertexts),
+    }
+}
+
+/// Parameters for reshuffling the discards back into the stock
+pub struct MxeRecycleParams<'a, 'info> {
+    /// Accounts for queueing on Arcium (required by the Arcium backend)
+    pub mxe: Option<MxeAccounts<'info>>,
+    
+    /// Deck account holding the MXE-encrypted deck
+    pub deck: &'a Deck,
+    
+    /// First discard position; positions from here to the end are reshuffled
+    pub start: u8,
+    
+    /// Fresh entropy for the reshuffle, encrypted to the MXE by the player
+    pub entropy: [u8; 32],
+    
+    /// Computation offset
+    pub computation_offset: u64,
+    
+    /// Registered comp def offset of `recycle_discards`
+    pub comp_def_offset: u32,
+    
+    /// Accounts `handle_recycle_callback` writes: the game and its Deck
+    pub callback_accounts: Vec<Pubkey>,
+}
+
+/// Reshuffle the discards together with the remaining stock using Arcium MPC
+///
+/// Queues `recycle_discards`; the reshuffled deck arrives via
+/// `handle_recycle_callback`, so this returns `None` in real mode. The mock
+/// returns the reshuffled deck, laid out like the Deck account, at once.
+pub fn mpc_recycle_discards(params: MxeRecycleParams) -> Result<Option<Vec<u8>>> {
+    require!(params.deck.initialized, PokerError::DeckNotInitialized);
+    
+    msg!(
+        "[ARCIUM MPC] Recycling deck positions {}.. ({})",
+        params.start,
+        ActiveBackend::NAME
+    );
+    
+    ActiveBackend::recycle(params)
+}
+
+/// Queue the `recycle_discards` circuit on Arcium
+pub(super) fn queue_recycle(mxe: &MxeAccounts, params: &MxeRecycleParams) -> Result<()> {
+    let args = vec![
+        Deck::argument(params.deck.address()?),
+        Argument::PlaintextU8(params.start),
+        Argument::EncryptedU8(params.entropy),
+    ];
+    
+    let callback = callback_instruction(
+        mxe,
+        crate::instruction::HandleRecycleCallback::DISCRIMINATOR,
+        &params.callback_accounts,
+    );
+    let computation_id = queue_circuit(
+        mxe,
+        params.computation_offset,
+        params.comp_def_offset,
+        &[],
+        args,
+        callback,
+    )?;
+    
+    msg!("[ARCIUM MPC] Recycle queued, computation ID: {:?}", &computation_id[..8]);
+    Ok(())
+}
+
+/// Reshuffle the stored mock deck (mock backend)
+pub(super) fn mock_recycle(params: &MxeRecycleParams) -> Result<Vec<u8>> {
+    let cards = mock_decrypt_deck(params.deck)?;
+    
+    // The nodes' randomness comes from the player's entropy and the session
+    let draws = ShuffleRng::new(&[params.entropy], &[&params.deck.session_id, &[params.start]]).shuffle_draws();
+    let cards = circuits::recycled_deck(&cards, params.start, &params.entropy, &draws);
+    
+    Ok(mock_encrypt_deck(&cards, &params.deck.session_id))
+}
+
+/// Generate nonce for a drawn hand
+///
+/// Unique per shuffle session, draw phase and seat; the hand's cards take
+/// the following nonces.
+pub fn generate_draw_nonce(session_id: &[u8; 32], draw: u8, seat: u8) -> u128 {
+    let mut nonce = [0u8; 16];
+    nonce[..8].copy_from_slice(&session_id[..8]);
+    nonce[8] = seat;
+    nonce[9] = 0x02; // Draw operation marker
+    nonce[10] = draw;
+    u128::from_le_bytes(nonce)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arcium::mpc_deal::decrypt_for_owner;
+    use crate::arcium::mpc_shuffle::mock_deck_account;
+    use crate::shared::constants::DECK_SIZE;
+
+    fn draw(deck: &Deck, discard: [bool; DRAW_HAND_CARDS], cursor: u8) -> Result<Option<Drawn>> {
+        mpc_draw_cards(MxeDrawParams {
+            mxe: None,
+            deck,
+            hand_positions: [0, 1, 2, 3, 4],
+            discard,
+            cursor,
+            owner_key: [5; 32],
+            nonce: 100,
+            computation_offset: 0,
+            comp_def_offset: 0,
+            callback_accounts: vec![],
+        })
+    }
+
+    #[test]
+    fn test_discards_are_swapped_with_the_stock() {
+        let cards: [u8; DECK_SIZE] = core::array::from_fn(|position| position as u8);
+        let mut deck = mock_deck_account(&cards);
+
+        let drawn = draw(&deck, [false, true, false, true, false], 20).unwrap().unwrap();
+        let hand: Vec<u8> = drawn.hand.iter().map(|card| decrypt_for_owner(card, &[5; 32])).collect();
+        assert_eq!(hand, vec![0, 20, 2, 21, 4]);
+
+        // Discards sit at the replacement positions, never dealt again
+        let session_id = deck.session_id;
+        deck.store_encrypted(session_id, &drawn.deck).unwrap();
+        let cards = mock_decrypt_deck(&deck).unwrap();
+        assert_eq!(cards[..5], [0, 20, 2, 21, 4]);
+        assert_eq!(cards[20..22], [1, 3]);
+
+        assert!(draw(&deck, [false; DRAW_HAND_CARDS], 22).is_err());
+    }
+
+    #[test]
+    fn test_recycle_reshuffles_only_past_the_start() {
+        let cards: [u8; DECK_SIZE] = core::array::from_fn(|position| position as u8);
+        let mut deck = mock_deck_account(&cards);
+
+        let recycled = mpc_recycle_discards(MxeRecycleParams {
+            mxe: None,
+            deck: &deck,
+            start: 25,
+            entropy: [3; 32],
+            computation_offset: 0,
+            comp_def_offset: 0,
+            callback_accounts: vec![],
+        })
+        .unwrap()
+        .unwrap();
+
+        let session_id = deck.session_id;
+        deck.store_encrypted(session_id, &recycled).unwrap();
+        let recycled = mock_decrypt_deck(&deck).unwrap();
+        assert_eq!(recycled[..25], cards[..25]);
+        assert_ne!(recycled[25..], cards[25..]);
+
+        let mut rest = recycled[25..].to_vec();
+        rest.sort_unstable();
+        assert_eq!(rest, cards[25..]);
+    }
+}
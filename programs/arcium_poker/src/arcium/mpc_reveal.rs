@@ -1,39 +1,23 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::program::invoke;
+use poker_shared::circuits;
 use super::mpc_deal::EncryptedCard;
-use super::integration::{MxeInstructionData, EncryptedData};
+use super::backend::{ActiveBackend, MpcBackend, MxeAccounts};
+use super::integration::{callback_instruction, queue_circuit};
+use super::types::Argument;
+use crate::cards::codec;
 use crate::cards::deck::Card;
-use crate::shared::PokerError;
-
-/// Parameters for revealing/decrypting a card
-#[derive(Clone, Debug)]
-pub struct RevealParams {
-    /// The encrypted card to reveal
-    pub encrypted_card: EncryptedCard,
-    
-    /// Requester of the reveal (must be owner or showdown)
-    pub requester: Pubkey,
-    
-    /// Session ID from original shuffle
-    pub session_id: [u8; 32],
-    
-    /// Is this for showdown? (may reveal to all)
-    pub is_showdown: bool,
-}
+use crate::cards::deck::Deck;
+use crate::game::state::Game;
+use crate::player::state::PlayerState;
+use crate::showdown::instruction::settle_public_showdown;
+use crate::types::GameStage;
+use crate::shared::{constants::*, PokerError};
+use super::mpc_shuffle::mock_decrypt_deck;
 
 /// Parameters for invoking MXE reveal
-pub struct MxeRevealParams<'info> {
-    /// MXE program account
-    pub mxe_program: Option<AccountInfo<'info>>,
-    
-    /// Computation definition account
-    pub comp_def: Option<AccountInfo<'info>>,
-    
-    /// Mempool account
-    pub mempool: Option<AccountInfo<'info>>,
-    
-    /// Cluster account
-    pub cluster: Option<AccountInfo<'info>>,
+pub struct MxeRevealParams<'a, 'info> {
+    /// Accounts for queueing on Arcium (required by the Arcium backend)
+    pub mxe: Option<MxeAccounts<'info>>,
     
     /// Encrypted cards to reveal
     pub encrypted_cards: Vec<EncryptedCard>,
@@ -41,11 +25,18 @@ pub struct MxeRevealParams<'info> {
     /// Requester
     pub requester: Pubkey,
     
-    /// Session ID
-    pub session_id: [u8; 32],
+    /// Deck account holding the MXE-encrypted deck
+    pub deck: &'a Deck,
     
     /// Computation offset
-    pub computation_offset: [u8; 8],
+    pub computation_offset: u64,
+    
+    /// Registered comp def offset of `reveal_hole_cards`
+    pub comp_def_offset: u32,
+    
+    /// Accounts `handle_reveal_callback` writes: the game, its Deck, then
+    /// every seat's PlayerState in seat order
+    pub callback_accounts: Vec<Pubkey>,
     
     /// Is showdown
     pub is_showdown: bool,
@@ -59,7 +50,7 @@ pub struct MxeRevealParams<'info> {
 /// 
 /// 1. **Private Reveal** (owner only): Uses Enc<Owner, T>.to_arcis() pattern
 ///    - Only the card owner can decrypt
-///    - Uses player's key shard
+///    - Uses the player's registered x25519 key
 ///    - Other players cannot see the card
 /// 
 /// 2. **Showdown Reveal** (public): Uses threshold decryption
@@ -86,17 +77,22 @@ pub struct MxeRevealParams<'info> {
 /// For showdown, the MXE program performs threshold decryption where
 /// multiple nodes must agree on the decrypted value.
 /// 
-/// This function can work in two modes:
-/// 1. **Real MPC Mode**: When MXE accounts are provided
-/// 2. **Mock Mode**: When MXE accounts are None (for testing)
-pub fn mpc_reveal_card_with_mxe<'info>(
-    params: MxeRevealParams<'info>,
-) -> Result<Vec<Card>> {
+/// Runs on the build's `ActiveBackend`. Arcium queues `reveal_hole_cards`
+/// and returns `None`; the mock decrypts immediately. Either way the cards
+/// come back as the circuit reveals them: card indices padded to
+/// MAX_HOLE_CARDS with NO_CARD.
+pub fn mpc_reveal_card_with_mxe(params: MxeRevealParams) -> Result<Option<Vec<u8>>> {
     msg!(
-        "[ARCIUM MPC] Revealing {} cards for {} (showdown: {})",
+        "[ARCIUM MPC] Revealing {} cards for {} (showdown: {}, {})",
         params.encrypted_cards.len(),
         params.requester,
-        params.is_showdown
+        params.is_showdown,
+        ActiveBackend::NAME
+    );
+    
+    require!(
+        !params.encrypted_cards.is_empty() && params.encrypted_cards.len() <= MAX_HOLE_CARDS,
+        PokerError::InvalidCardIndex
     );
     
     // Verify permission
@@ -109,110 +105,102 @@ pub fn mpc_reveal_card_with_mxe<'info>(
         }
     }
     
-    // Check if MXE accounts are provided
-    if let (Some(mxe_program), Some(comp_def), Some(mempool), Some(cluster)) = 
-        (&params.mxe_program, &params.comp_def, &params.mempool, &params.cluster) {
-        
-        msg!("[ARCIUM MPC] Using REAL MPC for card reveal");
-        
-        // Create MXE instruction data
-        let ix_data = create_reveal_instruction(
-            2, // reveal_hole_cards instruction index
-            &params.encrypted_cards,
-            params.computation_offset,
-        )?;
-        
-        // Invoke MXE program via CPI
-        invoke_mxe_computation(
-            mxe_program,
-            &ix_data,
-            &[comp_def.clone(), mempool.clone(), cluster.clone()],
-        )?;
-        
-        msg!("[ARCIUM MPC] Card reveal queued, computation ID: {:?}", params.computation_offset);
-        
-        // In production, result comes from callback
-        // For now, return placeholder
-        let mut revealed = Vec::new();
-        for card in &params.encrypted_cards {
-            let decrypted = decrypt_card_deterministic(
-                card.encrypted_index,
-                &card.key_shard,
-                &params.session_id,
-            )?;
-            revealed.push(decrypted);
-        }
-        
-        return Ok(revealed);
+    ActiveBackend::reveal(params)
+}
+
+/// Queue the `reveal_hole_cards` circuit on Arcium
+pub(super) fn queue_reveal(mxe: &MxeAccounts, params: &MxeRevealParams) -> Result<()> {
+    let args = reveal_arguments(params.deck.address()?, &params.encrypted_cards);
+    
+    let callback = callback_instruction(
+        mxe,
+        crate::instruction::HandleRevealCallback::DISCRIMINATOR,
+        &params.callback_accounts,
+    );
+    let computation_id = queue_circuit(
+        mxe,
+        params.computation_offset,
+        params.comp_def_offset,
+        &[],
+        args,
+        callback,
+    )?;
+    
+    msg!("[ARCIUM MPC] Card reveal queued, computation ID: {:?}", &computation_id[..8]);
+    Ok(())
+}
+
+/// Arguments of `reveal_hole_cards`: the deck, MAX_HOLE_CARDS deck positions
+/// (padded; the circuit reads only the first `count`), then `count`
+fn reveal_arguments(deck: Pubkey, encrypted_cards: &[EncryptedCard]) -> Vec<Argument> {
+    // `Enc<Mxe, &Deck>`: the circuit reads the cards from the stored deck
+    let mut args = vec![Deck::argument(deck)];
+    args.extend(
+        padded_positions(encrypted_cards)
+            .iter()
+            .map(|&position| Argument::PlaintextU8(position)),
+    );
+    args.push(Argument::PlaintextU8(encrypted_cards.len() as u8));
+    args
+}
+
+/// The cards' deck positions in the circuit's fixed-size input
+fn padded_positions(encrypted_cards: &[EncryptedCard]) -> [u8; MAX_HOLE_CARDS] {
+    let mut positions = [0u8; MAX_HOLE_CARDS];
+    for (position, card) in positions.iter_mut().zip(encrypted_cards) {
+        *position = card.encrypted_index;
     }
-    
-    msg!("[ARCIUM MPC] Using MOCK card reveal");
-    
-    // Mock mode: Decrypt cards
-    let mut revealed = Vec::new();
-    
-    for encrypted_card in &params.encrypted_cards {
-        let card = if params.is_showdown {
-            // Showdown: Threshold decryption (reveal to all)
-            decrypt_card_deterministic(
-                encrypted_card.encrypted_index,
-                &encrypted_card.key_shard,
-                &params.session_id,
-            )?
-        } else {
-            // Private reveal: Only owner can decrypt
-            decrypt_card_for_owner(
-                encrypted_card.encrypted_index,
-                &encrypted_card.key_shard,
-                &encrypted_card.owner,
-            )?
-        };
-        
-        msg!(
-            "[ARCIUM MPC] Card revealed: {:?} of {:?}",
-            card.rank,
-            card.suit
-        );
-        
-        revealed.push(card);
+    positions
+}
+
+/// Read the cards at their positions in the stored mock deck (mock backend)
+pub(super) fn mock_reveal(params: &MxeRevealParams) -> Result<Vec<u8>> {
+    let deck = mock_decrypt_deck(params.deck)?;
+    for card in &params.encrypted_cards {
+        require!((card.encrypted_index as usize) < DECK_SIZE, PokerError::InvalidCardIndex);
     }
     
-    Ok(revealed)
+    // Showdown reveals to all; a private reveal was already restricted to
+    // the owner. Either way the MXE reads the same cards.
+    let positions = padded_positions(&params.encrypted_cards);
+    Ok(circuits::shown_hole_cards(&deck, &positions, params.encrypted_cards.len() as u8).to_vec())
 }
 
-/// Legacy function for backward compatibility
-pub fn mpc_reveal_card(params: RevealParams) -> Result<Card> {
-    let mxe_params = MxeRevealParams {
-        mxe_program: None,
-        comp_def: None,
-        mempool: None,
-        cluster: None,
-        encrypted_cards: vec![params.encrypted_card],
-        requester: params.requester,
-        session_id: params.session_id,
-        computation_offset: [0; 8],
-        is_showdown: params.is_showdown,
-    };
+/// Handle MXE callback with a seat's revealed hole cards
+///
+/// Called by Arcium network after `reveal_hole_cards` completes, once
+/// `authenticate_callback` has accepted it. Records the cards shown by
+/// `game.reveal_seat` and, once every seat still in the hand has shown,
+/// settles the showdown.
+pub fn handle_reveal_callback(
+    game: &mut Game,
+    player_states: &mut [PlayerState],
+    computation_id: [u8; 32],
+    revealed: Vec<u8>,
+) -> Result<()> {
+    msg!("[ARCIUM] Handling hole card reveal callback {:?}", &computation_id[..8]);
     
-    let mut cards = mpc_reveal_card_with_mxe(mxe_params)?;
-    Ok(cards.remove(0))
+    record_shown_cards(game, &revealed)?;
+    settle_public_showdown(game, player_states)
 }
 
-/// Reveal multiple cards (e.g., for showdown)
-pub fn mpc_reveal_cards(cards: &[EncryptedCard], requester: Pubkey, session_id: [u8; 32]) -> Result<Vec<Card>> {
-    let mut revealed = Vec::with_capacity(cards.len());
-    
-    for encrypted_card in cards {
-        let params = RevealParams {
-            encrypted_card: *encrypted_card,
-            requester,
-            session_id,
-            is_showdown: true,
-        };
-        revealed.push(mpc_reveal_card(params)?);
+/// Record the cards `game.reveal_seat` showed; the circuit pads them to
+/// MAX_HOLE_CARDS with NO_CARD
+pub fn record_shown_cards(game: &mut Game, revealed: &[u8]) -> Result<()> {
+    let seat = game.reveal_seat as usize;
+    require!(
+        game.stage == GameStage::Showdown
+            && seat < game.player_count as usize
+            && revealed.len() == MAX_HOLE_CARDS
+            && revealed[0] != NO_CARD,
+        super::integration::ErrorCode::InvalidMxeCallback
+    );
+    for &card in revealed.iter().filter(|&&card| card != NO_CARD) {
+        codec::decode(card)?;
     }
     
-    Ok(revealed)
+    game.shown_cards[seat].copy_from_slice(revealed);
+    Ok(())
 }
 
 /// Verify that a card reveal was done correctly
@@ -261,165 +249,49 @@ pub fn verify_reveal(
     Ok(matches)
 }
 
-// ============================================================================
-// MXE INTEGRATION HELPERS
-// ============================================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-/// Helper: Create MXE instruction data for reveal
-fn create_reveal_instruction(
-    ix_index: u8,
-    encrypted_cards: &[EncryptedCard],
-    computation_offset: [u8; 8],
-) -> Result<Vec<u8>> {
-    let mut data = Vec::new();
-    data.push(ix_index);
-    data.extend_from_slice(&computation_offset);
-    
-    // Add each card's data
-    for card in encrypted_cards {
-        data.push(card.encrypted_index);
+    fn hole_card(encrypted_index: u8) -> EncryptedCard {
+        EncryptedCard {
+            encrypted_index,
+            ciphertext: [0; 32],
+            nonce: 0,
+            owner: Pubkey::default(),
+        }
     }
-    
-    Ok(data)
-}
-
-/// Helper: Invoke MXE via CPI
-fn invoke_mxe_computation<'a>(
-    mxe_program: &AccountInfo<'a>,
-    ix_data: &[u8],
-    accounts: &[AccountInfo<'a>],
-) -> Result<()> {
-    let ix = anchor_lang::solana_program::instruction::Instruction {
-        program_id: *mxe_program.key,
-        accounts: accounts.iter().map(|a| {
-            anchor_lang::solana_program::instruction::AccountMeta {
-                pubkey: *a.key,
-                is_signer: a.is_signer,
-                is_writable: a.is_writable,
-            }
-        }).collect(),
-        data: ix_data.to_vec(),
-    };
-    
-    let account_infos: Vec<AccountInfo> = std::iter::once(mxe_program.clone())
-        .chain(accounts.iter().cloned())
-        .collect();
-    
-    invoke(&ix, &account_infos)?;
-    Ok(())
-}
 
-// ============================================================================
-// MOCK IMPLEMENTATIONS (FOR TESTING)
-// ============================================================================
+    #[test]
+    fn test_reveal_arguments_match_the_circuit() {
+        let args = reveal_arguments(Pubkey::new_unique(), &[hole_card(9), hole_card(4)]);
 
-/// Decrypt card using deterministic method (for development)
-/// 
-/// In production with MXE deployed, this would invoke the Arcium network
-/// to perform threshold decryption across multiple nodes.
-fn decrypt_card_deterministic(
-    encrypted_index: u8,
-    key_shard: &[u8; 32],
-    session_id: &[u8; 32],
-) -> Result<Card> {
-    require!(
-        encrypted_index < 52,
-        PokerError::InvalidCardIndex
-    );
-    
-    // Derive actual card index using key shard and session
-    let card_index = derive_card_index(encrypted_index, key_shard, session_id);
-    
-    index_to_card(card_index)
-}
+        // Deck, MAX_HOLE_CARDS positions, count
+        assert_eq!(args.len(), 1 + MAX_HOLE_CARDS + 1);
+        assert!(matches!(args[1], Argument::PlaintextU8(9)));
+        assert!(matches!(args[2], Argument::PlaintextU8(4)));
+        assert!(args[3..=MAX_HOLE_CARDS]
+            .iter()
+            .all(|arg| matches!(arg, Argument::PlaintextU8(0))));
+        assert!(matches!(args[MAX_HOLE_CARDS + 1], Argument::PlaintextU8(2)));
+    }
 
-/// Decrypt card for owner (private reveal)
-fn decrypt_card_for_owner(
-    encrypted_index: u8,
-    key_shard: &[u8; 32],
-    owner: &Pubkey,
-) -> Result<Card> {
-    require!(
-        encrypted_index < 52,
-        PokerError::InvalidCardIndex
-    );
-    
-    // In Arcium MPC, this uses Enc<Owner, T>.to_arcis()
-    // Only the owner can perform this operation
-    
-    // For development, use deterministic decryption
-    let card_index = derive_card_index_for_owner(encrypted_index, key_shard, owner);
-    
-    index_to_card(card_index)
-}
+    #[test]
+    fn test_shown_cards_are_recorded_for_the_revealing_seat() {
+        let mut game = Game::deserialize(&mut &[0u8; Game::LEN][..]).unwrap();
+        game.stage = GameStage::Showdown;
+        game.player_count = 3;
+        game.reveal_seat = 2;
 
-/// Convert card index (0-51) to Card struct
-fn index_to_card(index: u8) -> Result<Card> {
-    require!(index < 52, PokerError::InvalidCardIndex);
-    
-    let suit_index = index / 13;
-    let rank_index = index % 13;
-    
-    let suit = match suit_index {
-        0 => crate::types::Suit::Hearts,
-        1 => crate::types::Suit::Diamonds,
-        2 => crate::types::Suit::Clubs,
-        3 => crate::types::Suit::Spades,
-        _ => return Err(PokerError::InvalidCardIndex.into()),
-    };
-    
-    let rank = match rank_index {
-        0 => crate::types::Rank::Two,
-        1 => crate::types::Rank::Three,
-        2 => crate::types::Rank::Four,
-        3 => crate::types::Rank::Five,
-        4 => crate::types::Rank::Six,
-        5 => crate::types::Rank::Seven,
-        6 => crate::types::Rank::Eight,
-        7 => crate::types::Rank::Nine,
-        8 => crate::types::Rank::Ten,
-        9 => crate::types::Rank::Jack,
-        10 => crate::types::Rank::Queen,
-        11 => crate::types::Rank::King,
-        12 => crate::types::Rank::Ace,
-        _ => return Err(PokerError::InvalidCardIndex.into()),
-    };
-    
-    Ok(Card { suit, rank })
-}
+        let mut revealed = vec![NO_CARD; MAX_HOLE_CARDS];
+        revealed[..2].copy_from_slice(&[12, 40]);
+        record_shown_cards(&mut game, &revealed).unwrap();
+        assert_eq!(game.shown_cards[2].to_vec(), revealed);
 
-/// Derive actual card index from encrypted index and key material
-fn derive_card_index(
-    encrypted_index: u8,
-    key_shard: &[u8; 32],
-    session_id: &[u8; 32],
-) -> u8 {
-    // XOR-based derivation (simplified for development)
-    // In production, Arcium MPC performs secure decryption
-    let mut derived = encrypted_index;
-    derived ^= key_shard[0];
-    derived ^= session_id[0];
-    derived % 52
-}
-
-/// Derive card index for owner-specific decryption
-fn derive_card_index_for_owner(
-    encrypted_index: u8,
-    key_shard: &[u8; 32],
-    owner: &Pubkey,
-) -> u8 {
-    let owner_bytes = owner.to_bytes();
-    let mut derived = encrypted_index;
-    derived ^= key_shard[0];
-    derived ^= owner_bytes[0];
-    derived % 52
+        // Short outputs, empty hands and bad cards are rejected
+        assert!(record_shown_cards(&mut game, &[12, 40]).is_err());
+        assert!(record_shown_cards(&mut game, &[NO_CARD; MAX_HOLE_CARDS]).is_err());
+        revealed[0] = 60;
+        assert!(record_shown_cards(&mut game, &revealed).is_err());
+    }
 }
-
-/// Generate nonce for reveal operation
-fn generate_reveal_nonce(session_id: &[u8; 32], card_index: u8) -> [u8; 16] {
-    let mut nonce = [0u8; 16];
-    nonce[..8].copy_from_slice(&session_id[..8]);
-    nonce[8] = card_index;
-    nonce[9] = 0x02; // Reveal operation marker
-    nonce
-}
\ No newline at end of file
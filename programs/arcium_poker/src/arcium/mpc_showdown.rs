@@ -0,0 +1,334 @@
+use anchor_lang::prelude::*;
+use poker_shared::circuits;
+use super::backend::{ActiveBackend, MpcBackend, MxeAccounts};
+use super::integration::{callback_instruction, queue_circuit};
+use super::mpc_shuffle::mock_decrypt_deck;
+use super::types::Argument;
+use crate::cards::deck::Deck;
+use crate::game::state::Game;
+use crate::player::state::PlayerState;
+use crate::showdown::private::apply_showdown_outcome;
+use crate::shared::{constants::*, PokerError};
+use crate::types::HandRank;
+
+/// Hole cards per player in a private showdown (Texas Hold'em)
+pub const SHOWDOWN_HOLE_CARDS: usize = circuits::SHOWDOWN_HOLE_CARDS;
+
+/// What a private showdown reveals: the winning seats and, if the table
+/// shows it, the winning hand's score (0 when hidden)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ShowdownOutcome {
+    pub winners: [bool; MAX_PLAYERS],
+    pub winning_score: u32,
+}
+
+/// Parameters for evaluating a showdown inside MPC
+pub struct MxeShowdownParams<'a, 'info> {
+    /// Accounts for queueing on Arcium (required by the Arcium backend)
+    pub mxe: Option<MxeAccounts<'info>>,
+    
+    /// Deck account holding the MXE-encrypted deck
+    pub deck: &'a Deck,
+    
+    /// Deck positions of each seat's hole cards
+    pub hole_positions: [[u8; SHOWDOWN_HOLE_CARDS]; MAX_PLAYERS],
+    
+    /// Seats still in the hand
+    pub contenders: [bool; MAX_PLAYERS],
+    
+    /// Public board
+    pub board: [u8; COMMUNITY_CARDS],
+    
+    /// Reveal the winning hand's score as well as the winners
+    pub show_winning_hand: bool,
+    
+    /// Computation offset
+    pub computation_offset: u64,
+    
+    /// Registered comp def offset of `evaluate_showdown`
+    pub comp_def_offset: u32,
+    
+    /// Accounts `handle_showdown_callback` writes: the game, its Deck, then
+    /// every seat's PlayerState in seat order
+    pub callback_accounts: Vec<Pubkey>,
+}
+
+/// Evaluate a showdown using Arcium MPC
+///
+/// **REAL ARCIUM INTEGRATION**
+///
+/// Queues the `evaluate_showdown` circuit, which reads every contender's hole
+/// cards from the stored `Enc<Mxe, Deck>`, scores them against the board and
+/// reveals only the winners. The result arrives via `handle_showdown_callback`,
+/// so this returns `None` in real mode.
+///
+/// Runs on the build's `ActiveBackend`; the mock scores the mock deck with
+/// the same rules as the circuit.
+pub fn mpc_evaluate_showdown_with_mxe(
+    params: MxeShowdownParams,
+) -> Result<Option<ShowdownOutcome>> {
+    require!(params.deck.initialized, PokerError::DeckNotInitialized);
+    require!(
+        params.contenders.iter().any(|&contender| contender),
+        PokerError::NotEnoughPlayers
+    );
+    
+    msg!(
+        "[ARCIUM MPC] Evaluating private showdown for {} contenders ({})",
+        params.contenders.iter().filter(|&&contender| contender).count(),
+        ActiveBackend::NAME
+    );
+    
+    ActiveBackend::evaluate_showdown(params)
+}
+
+/// Queue the `evaluate_showdown` circuit on Arcium
+pub(super) fn queue_evaluate_showdown(mxe: &MxeAccounts, params: &MxeShowdownParams) -> Result<()> {
+    // `Enc<Mxe, &Deck>`: hole cards are read from the stored deck
+    let mut args = vec![Deck::argument(params.deck.address()?)];
+    args.extend(
+        params.hole_positions
+            .iter()
+            .flatten()
+            .map(|&position| Argument::PlaintextU8(position)),
+    );
+    args.extend(params.contenders.iter().map(|&contender| Argument::PlaintextBool(contender)));
+    args.extend(params.board.iter().map(|&card| Argument::PlaintextU8(card)));
+    args.push(Argument::PlaintextBool(params.show_winning_hand));
+    
+    let callback = callback_instruction(
+        mxe,
+        crate::instruction::HandleShowdownCallback::DISCRIMINATOR,
+        &params.callback_accounts,
+    );
+    let computation_id = queue_circuit(
+        mxe,
+        params.computation_offset,
+        params.comp_def_offset,
+        &[],
+        args,
+        callback,
+    )?;
+    
+    msg!("[ARCIUM MPC] Showdown queued, computation ID: {:?}", &computation_id[..8]);
+    Ok(())
+}
+
+/// Score the mock deck in plaintext (mock backend), logging nothing about
+/// the hands
+pub(super) fn mock_evaluate_showdown(params: &MxeShowdownParams) -> Result<ShowdownOutcome> {
+    let deck = mock_decrypt_deck(params.deck)?;
+    let mut scores = [0u32; MAX_PLAYERS];
+    for ((score, positions), _) in scores
+        .iter_mut()
+        .zip(&params.hole_positions)
+        .zip(&params.contenders)
+        .filter(|(_, &contender)| contender)
+    {
+        let mut cards = [0u8; SHOWDOWN_HOLE_CARDS + COMMUNITY_CARDS];
+        for (card, &position) in cards.iter_mut().zip(positions) {
+            require!((position as usize) < DECK_SIZE, PokerError::InvalidCardIndex);
+            *card = deck[position as usize];
+        }
+        cards[SHOWDOWN_HOLE_CARDS..].copy_from_slice(&params.board);
+        *score = best_hand_score(&cards);
+    }
+    
+    Ok(outcome_from_scores(&scores, &params.contenders, params.show_winning_hand))
+}
+
+/// Handle MXE callback with the private showdown result
+///
+/// Called by Arcium network after `evaluate_showdown` completes, once
+/// `authenticate_callback` has accepted it. Pays the pot to the revealed
+/// winners; nobody's hole cards are ever revealed.
+pub fn handle_showdown_callback(
+    game: &mut Game,
+    player_states: &mut [PlayerState],
+    computation_id: [u8; 32],
+    winners: [bool; MAX_PLAYERS],
+    winning_score: u32,
+) -> Result<()> {
+    msg!("[ARCIUM] Handling showdown callback {:?}", &computation_id[..8]);
+    
+    require!(
+        game.showdown_pending,
+        super::integration::ErrorCode::InvalidMxeCallback
+    );
+    
+    apply_showdown_outcome(game, player_states, ShowdownOutcome { winners, winning_score })
+}
+
+/// Winners are the contenders holding the best score
+fn outcome_from_scores(
+    scores: &[u32; MAX_PLAYERS],
+    contenders: &[bool; MAX_PLAYERS],
+    show_winning_hand: bool,
+) -> ShowdownOutcome {
+    let best = scores
+        .iter()
+        .zip(contenders)
+        .filter(|(_, &contender)| contender)
+        .map(|(&score, _)| score)
+        .max()
+        .unwrap_or(0);
+    
+    let mut winners = [false; MAX_PLAYERS];
+    for seat in 0..MAX_PLAYERS {
+        winners[seat] = contenders[seat] && scores[seat] == best;
+    }
+    
+    ShowdownOutcome {
+        winners,
+        winning_score: if show_winning_hand { best } else { 0 },
+    }
+}
+
+// ============================================================================
+// HAND SCORING (the evaluate_showdown circuit's own code, from poker-shared)
+// ============================================================================
+
+/// Best five-card score among the 21 ways to drop two of seven cards
+pub fn best_hand_score(cards: &[u8; 7]) -> u32 {
+    circuits::best_hand_score(*cards)
+}
+
+/// Comparable score for five canonical card indices
+///
+/// Category (0 = high card .. 8 = straight flush) sits in bits 20-23, then
+/// the five ranks ordered by group size and rank, four bits each. A wheel
+/// scores as a five-high straight.
+pub fn hand_score(hand: &[u8; 5]) -> u32 {
+    circuits::hand_score(*hand)
+}
+
+/// Hand category of a revealed score (None for 0, i.e. hidden)
+pub fn score_hand_rank(score: u32) -> Option<HandRank> {
+    let top_rank = (score >> 16) & 0xF;
+    let rank = match score >> 20 {
+        _ if score == 0 => return None,
+        0 => HandRank::HighCard,
+        1 => HandRank::OnePair,
+        2 => HandRank::TwoPair,
+        3 => HandRank::ThreeOfAKind,
+        4 => HandRank::Straight,
+        5 => HandRank::Flush,
+        6 => HandRank::FullHouse,
+        7 => HandRank::FourOfAKind,
+        8 if top_rank == 14 => HandRank::RoyalFlush,
+        8 => HandRank::StraightFlush,
+        _ => return None,
+    };
+    Some(rank)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::codec;
+    use crate::cards::fast_evaluator::evaluate_cards;
+
+    fn evaluate(cards: &[u8; 7]) -> crate::cards::evaluator::EvaluatedHand {
+        let cards: Vec<_> = cards.iter().map(|&index| codec::decode(index).unwrap()).collect();
+        evaluate_cards(&cards).unwrap()
+    }
+
+    #[test]
+    fn test_score_orders_hands_like_the_evaluator() {
+        // Deterministic deals sharing a board, compared pairwise
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let mut next = |bound: u64| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % bound) as u8
+        };
+
+        for _ in 0..500 {
+            let mut deck: Vec<u8> = (0..52).collect();
+            for i in 0..9 {
+                let j = i + next(52 - i as u64) as usize;
+                deck.swap(i, j);
+            }
+
+            let mut a = [0u8; 7];
+            let mut b = [0u8; 7];
+            a[..2].copy_from_slice(&deck[..2]);
+            b[..2].copy_from_slice(&deck[2..4]);
+            a[2..].copy_from_slice(&deck[4..9]);
+            b[2..].copy_from_slice(&deck[4..9]);
+
+            assert_eq!(
+                best_hand_score(&a).cmp(&best_hand_score(&b)),
+                evaluate(&a).cmp(&evaluate(&b)),
+                "{:?} vs {:?}",
+                a,
+                b
+            );
+            assert_eq!(score_hand_rank(best_hand_score(&a)), Some(evaluate(&a).rank));
+        }
+    }
+
+    #[test]
+    fn test_mock_scores_the_cards_at_the_hole_positions() {
+        let cards: [u8; DECK_SIZE] = core::array::from_fn(|position| (DECK_SIZE - 1 - position) as u8);
+        let deck = crate::arcium::mpc_shuffle::mock_deck_account(&cards);
+
+        // Seat 0 holds a pair of aces (positions 39, 26), seat 1 a pair of
+        // threes (positions 25, 11)
+        let mut hole_positions = [[0u8; SHOWDOWN_HOLE_CARDS]; MAX_PLAYERS];
+        hole_positions[0] = [39, 26];
+        hole_positions[1] = [25, 11];
+        let mut contenders = [false; MAX_PLAYERS];
+        contenders[..2].copy_from_slice(&[true, true]);
+
+        let outcome = mpc_evaluate_showdown_with_mxe(MxeShowdownParams {
+            mxe: None,
+            deck: &deck,
+            hole_positions,
+            contenders,
+            board: [3, 18, 33, 48, 1],
+            show_winning_hand: true,
+            computation_offset: 0,
+            comp_def_offset: 0,
+            callback_accounts: vec![],
+        })
+        .unwrap()
+        .unwrap();
+
+        let mut expected = [false; MAX_PLAYERS];
+        expected[0] = true;
+        assert_eq!(outcome.winners, expected);
+        assert_eq!(score_hand_rank(outcome.winning_score), Some(HandRank::OnePair));
+    }
+
+    #[test]
+    fn test_wheel_is_the_lowest_straight() {
+        // A-2-3-4-5 of mixed suits, then 2-3-4-5-6
+        let wheel = hand_score(&[12, 13, 27, 41, 3]);
+        let six_high = hand_score(&[0, 14, 28, 42, 4]);
+        assert_eq!(score_hand_rank(wheel), Some(HandRank::Straight));
+        assert!(wheel < six_high);
+    }
+
+    #[test]
+    fn test_only_best_contenders_win() {
+        // Seat 4 holds the best score but folded
+        let mut scores = [0u32; MAX_PLAYERS];
+        scores[..5].copy_from_slice(&[0x10_0000, 0x30_0000, 0, 0x30_0000, 0x50_0000]);
+        let mut contenders = [false; MAX_PLAYERS];
+        for seat in [0, 1, 3] {
+            contenders[seat] = true;
+        }
+
+        let outcome = outcome_from_scores(&scores, &contenders, false);
+        let mut expected = [false; MAX_PLAYERS];
+        expected[1] = true;
+        expected[3] = true;
+        assert_eq!(outcome.winners, expected);
+        assert_eq!(outcome.winning_score, 0);
+
+        let shown = outcome_from_scores(&scores, &contenders, true);
+        assert_eq!(shown.winning_score, 0x30_0000);
+    }
+}
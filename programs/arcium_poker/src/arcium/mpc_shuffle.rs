@@ -1,8 +1,13 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::program::invoke;
-use crate::shared::constants::DECK_SIZE;
+use crate::shared::constants::{DECK_SIZE, MAX_PLAYERS, MIN_PLAYERS};
 use crate::shared::PokerError;
-use super::integration::{MxeInstructionData, EncryptedData};
+use crate::types::GameVariant;
+use crate::cards::deck::Deck;
+use super::backend::{ActiveBackend, MpcBackend, MxeAccounts};
+use super::integration::{callback_instruction, queue_circuit, EncryptedData};
+use super::types::Argument;
+use super::shuffle_rng::ShuffleRng;
+use poker_shared::circuits;
 
 /// Result from Arcium MPC shuffle operation
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
@@ -10,6 +15,9 @@ pub struct ShuffleResult {
     /// Encrypted and shuffled card indices
     pub shuffled_indices: [u8; DECK_SIZE],
     
+    /// Number of valid entries in `shuffled_indices` (52, or 36 for short deck)
+    pub deck_size: u8,
+    
     /// Commitment/hash of the shuffle (for verification)
     pub commitment: [u8; 32],
     
@@ -18,60 +26,37 @@ pub struct ShuffleResult {
     
     /// Proof that shuffle was done correctly (optional)
     pub shuffle_proof: Option<Vec<u8>>,
+    
+    /// Shuffle was queued to the MXE; the encrypted deck arrives via callback
+    /// and `shuffled_indices` is a placeholder
+    pub queued: bool,
 }
 
 /// Parameters for invoking MXE shuffle
 pub struct MxeShuffleParams<'info> {
-    /// MXE program account
-    pub mxe_program: Option<AccountInfo<'info>>,
-
-    /// MXE account (persistent MXE account PDA)
-    pub mxe_account: Option<AccountInfo<'info>>,
-
-    /// Computation definition account
-    pub comp_def: Option<AccountInfo<'info>>,
-
-    /// Mempool account for queueing computation
-    pub mempool: Option<AccountInfo<'info>>,
-
-    /// Cluster account
-    pub cluster: Option<AccountInfo<'info>>,
-
-    /// Computation account (to be created/used)
-    pub computation_account: Option<AccountInfo<'info>>,
-
-    /// Authority (signer)
-    pub authority: Option<AccountInfo<'info>>,
-
-    /// Sign seed PDA
-    pub sign_seed: Option<AccountInfo<'info>>,
-
-    /// Executing pool PDA
-    pub executing_pool: Option<AccountInfo<'info>>,
-
-    /// Staking pool PDA
-    pub staking_pool: Option<AccountInfo<'info>>,
-
-    /// System program
-    pub system_program: Option<AccountInfo<'info>>,
-
-    /// Clock sysvar
-    pub clock: Option<AccountInfo<'info>>,
-
+    /// Accounts for queueing on Arcium (required by the Arcium backend)
+    pub mxe: Option<MxeAccounts<'info>>,
+    
     /// Player entropy (encrypted)
     pub encrypted_entropy: Vec<[u8; 32]>,
-
+    
     /// Computation offset (unique ID)
     pub computation_offset: u64,
-
+    
     /// Computation definition offset
     pub comp_def_offset: u32,
-
+    
     /// Player pubkeys
     pub player_pubkeys: Vec<Pubkey>,
-
+    
     /// Game ID
     pub game_id: u64,
+    
+    /// Variant being dealt (selects the deck to shuffle)
+    pub variant: GameVariant,
+    
+    /// Accounts the shuffle callback writes (game, then its Deck account)
+    pub callback_accounts: Vec<Pubkey>,
 }
 
 /// Parameters for MPC shuffle
@@ -85,6 +70,9 @@ pub struct ShuffleParams {
     
     /// Game ID for this shuffle session
     pub game_id: u64,
+    
+    /// Variant being dealt (selects the deck to shuffle)
+    pub variant: GameVariant,
 }
 
 /// Perform MPC-based shuffle using Arcium
@@ -118,9 +106,8 @@ pub struct ShuffleParams {
 /// ```
 /// Invoke Arcium MXE for deck shuffle
 /// 
-/// This function can work in two modes:
-/// 1. **Real MPC Mode**: When MXE accounts are provided, invokes actual Arcium MPC
-/// 2. **Mock Mode**: When MXE accounts are None, uses deterministic shuffle for testing
+/// Runs on the build's `ActiveBackend`: queued on Arcium (the encrypted deck
+/// arrives via `handle_shuffle_callback`), or shuffled locally by the mock.
 pub fn mpc_shuffle_deck_with_mxe<'info>(
     params: MxeShuffleParams<'info>,
 ) -> Result<ShuffleResult> {
@@ -136,129 +123,85 @@ pub fn mpc_shuffle_deck_with_mxe<'info>(
     
     msg!("[ARCIUM MPC] Initiating shuffle for game {}", params.game_id);
     msg!("[ARCIUM MPC] Players participating: {}", params.player_pubkeys.len());
+    msg!("[ARCIUM MPC] Backend: {}", ActiveBackend::NAME);
     
-    // Check if MXE accounts are provided
-    if let (Some(mxe_program), Some(mxe_account), Some(comp_def), Some(mempool), Some(cluster), Some(computation_account), Some(authority),
-            Some(sign_seed), Some(executing_pool), Some(staking_pool), Some(system_program), Some(clock)) =
-        (&params.mxe_program, &params.mxe_account, &params.comp_def, &params.mempool, &params.cluster, &params.computation_account, &params.authority,
-         &params.sign_seed, &params.executing_pool, &params.staking_pool, &params.system_program, &params.clock) {
-        
-        msg!("[ARCIUM MPC] Using REAL MPC via MXE program");
-        msg!("[ARCIUM MPC] MXE Program: {}", mxe_program.key());
-        msg!("[ARCIUM MPC] Cluster: {}", cluster.key());
-        msg!("[ARCIUM MPC] Authority: {}", authority.key());
-        
-        // Check if MXE program is the same as our program (integrated MXE)
-        let our_program_id = crate::ID;
-        if mxe_program.key() == our_program_id {
-            msg!("[ARCIUM MPC] MXE is integrated into this program - using direct shuffle");
-            msg!("[ARCIUM MPC] Skipping CPI (cannot CPI to self)");
-            
-            // Use integrated MXE shuffle (no CPI needed)
-            // Generate session ID and commitment
-            let session_id = generate_session_id(params.game_id, &params.player_pubkeys);
-            let commitment = generate_commitment(&params.encrypted_entropy, &session_id);
-            
-            // For integrated MXE, we do the shuffle directly
-            let shuffled_indices = perform_integrated_shuffle(&params.encrypted_entropy, &params.player_pubkeys, params.game_id)?;
-            
-            msg!("[ARCIUM MPC] Integrated shuffle complete!");
-            
-            return Ok(ShuffleResult {
-                shuffled_indices,
-                commitment,
-                session_id,
-                shuffle_proof: Some(vec![0; 64]),
-            });
-        }
-        
-        // Prepare encrypted inputs for MPC
-        let mut encrypted_inputs = Vec::new();
-        
-        for (i, entropy) in params.encrypted_entropy.iter().enumerate() {
-            encrypted_inputs.push(super::integration::EncryptedData {
-                ciphertext: *entropy,
-                nonce: generate_player_nonce(params.game_id, i as u8),
-                owner: Some(params.player_pubkeys[i]),
-            });
-        }
-        
-        // Queue MPC computation via CPI
-        use super::integration::queue_mxe_computation;
+    ActiveBackend::shuffle(params)
+}
 
-        let computation_id = queue_mxe_computation(
-            mxe_program,
-            authority,
-            sign_seed,
-            computation_account,
-            mxe_account,
-            executing_pool,
-            mempool,
-            comp_def,
-            cluster,
-            staking_pool,
-            system_program,
-            clock,
-            params.computation_offset,
-            params.comp_def_offset,
-            &encrypted_inputs,
-        )?;
-        
-        msg!("[ARCIUM MPC] Shuffle queued successfully!");
-        msg!("[ARCIUM MPC] Computation ID: {:?}", &computation_id[..8]);
-        msg!("[ARCIUM MPC] Result will arrive via callback");
-        
-        // Generate session ID and commitment
-        let session_id = computation_id;
-        let commitment = generate_commitment(&params.encrypted_entropy, &session_id);
-        
-        // Return placeholder result - actual shuffled deck comes from callback
-        return Ok(ShuffleResult {
-            shuffled_indices: [0; DECK_SIZE], // Will be filled by callback
-            commitment,
-            session_id,
-            shuffle_proof: Some(vec![0; 64]), // Proof from MPC
-        });
-    }
-    
-    msg!("[ARCIUM MPC] Using MOCK shuffle (MXE not provided)");
-    
-    // REAL ARCIUM MPC INTEGRATION
-    // This calls the MXE program which coordinates with Arcium network nodes
-    
-    // Step 1: Prepare encrypted inputs for MPC
-    let mut encrypted_inputs = Vec::new();
+/// Queue the shuffle circuit on Arcium
+pub(super) fn queue_shuffle(mxe: &MxeAccounts, params: &MxeShuffleParams) -> Result<ShuffleResult> {
+    msg!("[ARCIUM MPC] MXE Program: {}", mxe.mxe_program.key());
+    msg!("[ARCIUM MPC] Cluster: {}", mxe.cluster.key());
+    msg!("[ARCIUM MPC] Authority: {}", mxe.authority.key());
     
-    // Initial deck (0-51 in order)
-    let initial_deck = create_initial_deck();
-    encrypted_inputs.push(EncryptedData {
-        ciphertext: hash_to_ciphertext(&initial_deck),
-        nonce: generate_nonce(params.game_id),
-        owner: None, // Shared among all nodes
-    });
+    // Prepare encrypted inputs for MPC: one entropy slot per seat,
+    // padded to the circuit's MAX_PLAYERS; the circuit only reads the
+    // first `active_count`
+    let active_count = params.encrypted_entropy.len();
+    require!(
+        (MIN_PLAYERS..=MAX_PLAYERS).contains(&active_count),
+        PokerError::NotEnoughPlayers
+    );
     
-    // Add each player's entropy as encrypted input
-    for (i, entropy) in params.encrypted_entropy.iter().enumerate() {
+    let mut encrypted_inputs = Vec::with_capacity(MAX_PLAYERS);
+    for seat in 0..MAX_PLAYERS {
         encrypted_inputs.push(EncryptedData {
-            ciphertext: *entropy,
-            nonce: generate_player_nonce(params.game_id, i as u8),
-            owner: Some(params.player_pubkeys[i]),
+            ciphertext: params.encrypted_entropy.get(seat).copied().unwrap_or([0; 32]),
+            nonce: generate_player_nonce(params.game_id, seat as u8),
+            owner: params.player_pubkeys.get(seat).copied(),
         });
     }
     
-    // Step 2: Create MXE instruction for shuffle
-    let mxe_instruction = MxeInstructionData {
-        ix_index: 0, // shuffle_deck instruction
-        encrypted_inputs,
-        public_inputs: params.game_id.to_le_bytes().to_vec(),
-    };
+    // `mxe: Mxe` output owner: nonce for the MXE-encrypted deck
+    let mxe_nonce = u128::from_le_bytes(generate_nonce(params.game_id));
     
-    // Step 3: Generate session ID and commitment
-    let session_id = generate_session_id(params.game_id, &params.player_pubkeys);
+    // The callback stores the encrypted deck in the Deck account
+    let callback = callback_instruction(
+        mxe,
+        crate::instruction::HandleShuffleCallback::DISCRIMINATOR,
+        &params.callback_accounts,
+    );
+    
+    let computation_id = queue_circuit(
+        mxe,
+        params.computation_offset,
+        params.comp_def_offset,
+        &encrypted_inputs,
+        vec![
+            Argument::PlaintextU8(active_count as u8),
+            Argument::PlaintextU128(mxe_nonce),
+        ],
+        callback,
+    )?;
+    
+    msg!("[ARCIUM MPC] Shuffle queued successfully!");
+    msg!("[ARCIUM MPC] Computation ID: {:?}", &computation_id[..8]);
+    msg!("[ARCIUM MPC] Result will arrive via callback");
+    
+    // Generate session ID and commitment
+    let session_id = computation_id;
     let commitment = generate_commitment(&params.encrypted_entropy, &session_id);
     
+    // Return placeholder result - actual shuffled deck comes from callback
+    Ok(ShuffleResult {
+        shuffled_indices: [0; DECK_SIZE], // Encrypted deck arrives via callback
+        deck_size: params.variant.deck_size() as u8,
+        commitment,
+        session_id,
+        shuffle_proof: Some(vec![0; 64]), // Proof from MPC
+        queued: true,
+    })
+}
+
+/// Shuffle locally with the players' entropy (mock backend)
+pub(super) fn mock_shuffle(params: &MxeShuffleParams) -> Result<ShuffleResult> {
     // Mock mode: Use deterministic shuffle for testing
-    let shuffled_indices = secure_shuffle_with_entropy(&params.encrypted_entropy)?;
+    let shuffled_indices = secure_shuffle_with_entropy(
+        &params.encrypted_entropy,
+        &params.player_pubkeys,
+        params.game_id,
+        params.variant,
+    )?;
     let session_id = generate_session_id(params.game_id, &params.player_pubkeys);
     let commitment = generate_commitment(&params.encrypted_entropy, &session_id);
     
@@ -270,9 +213,11 @@ pub fn mpc_shuffle_deck_with_mxe<'info>(
     
     Ok(ShuffleResult {
         shuffled_indices,
+        deck_size: params.variant.deck_size() as u8,
         commitment,
         session_id,
         shuffle_proof: Some(shuffle_proof),
+        queued: false,
     })
 }
 
@@ -309,93 +254,72 @@ pub fn verify_shuffle(
 }
 
 /// Legacy function for backward compatibility
+///
+/// Passes no MXE accounts, so it only succeeds on a backend that computes
+/// locally (the `mock-mpc` build).
 pub fn mpc_shuffle_deck(params: ShuffleParams) -> Result<ShuffleResult> {
-    // Convert to new format without MXE accounts (mock mode)
     let mxe_params = MxeShuffleParams {
-        mxe_program: None,
-        mxe_account: None,
-        comp_def: None,
-        mempool: None,
-        cluster: None,
-        computation_account: None,
-        authority: None,
-        sign_seed: None,
-        executing_pool: None,
-        staking_pool: None,
-        system_program: None,
-        clock: None,
+        mxe: None,
         encrypted_entropy: params.player_entropy.clone(),
         computation_offset: params.game_id,
         comp_def_offset: 0,
         player_pubkeys: params.player_pubkeys.clone(),
         game_id: params.game_id,
+        variant: params.variant,
+        callback_accounts: vec![],
     };
-
+    
     mpc_shuffle_deck_with_mxe(mxe_params)
 }
 
-// ============================================================================
-// INTEGRATED MXE SHUFFLE (When MXE is same as our program)
-// ============================================================================
-
-/// Perform shuffle when MXE is integrated (no CPI needed)
-fn perform_integrated_shuffle(
-    encrypted_entropy: &[[u8; 32]],
-    player_pubkeys: &[Pubkey],
-    game_id: u64,
-) -> Result<[u8; DECK_SIZE]> {
-    msg!("[INTEGRATED MXE] Performing shuffle with {} entropy sources", encrypted_entropy.len());
-    
-    // Combine all entropy sources
-    let mut combined_entropy = [0u8; 32];
-    for (i, entropy) in encrypted_entropy.iter().enumerate() {
-        for j in 0..32 {
-            combined_entropy[j] ^= entropy[j];
-        }
-        // Mix in player pubkey
-        let player_bytes = player_pubkeys[i].to_bytes();
-        for j in 0..32 {
-            combined_entropy[j] ^= player_bytes[j].wrapping_add(i as u8);
-        }
-    }
-    
-    // Mix in game ID
-    let game_bytes = game_id.to_le_bytes();
-    for i in 0..8 {
-        combined_entropy[i] ^= game_bytes[i];
+/// Encrypt a locally shuffled deck into the Deck account layout
+/// 
+/// Mock and integrated shuffles produce plaintext indices; this stands in
+/// for the `Enc<Mxe, Deck>` the MXE would return so the Deck account always
+/// holds ciphertext, keyed by the shuffle session.
+pub fn mock_encrypt_deck(shuffled_indices: &[u8; DECK_SIZE], session_id: &[u8; 32]) -> Vec<u8> {
+    let nonce = u128::from_le_bytes(generate_nonce(u64::from_le_bytes(session_id[..8].try_into().unwrap())));
+    
+    let mut output = nonce.to_le_bytes().to_vec();
+    for (position, &card) in shuffled_indices.iter().enumerate() {
+        let mut ciphertext = super::mpc_deal::mock_keystream(session_id, nonce + position as u128);
+        ciphertext[0] ^= card;
+        output.extend_from_slice(&ciphertext);
     }
     
-    // Perform Fisher-Yates shuffle using combined entropy
-    let mut deck = create_initial_deck();
-    let mut entropy_index = 0;
-    
-    for i in (1..DECK_SIZE).rev() {
-        // Get random index from entropy
-        let random_byte = combined_entropy[entropy_index % 32];
-        entropy_index += 1;
-        
-        // Fisher-Yates: swap with random position
-        let j = (random_byte as usize) % (i + 1);
-        deck.swap(i, j);
-        
-        // Re-hash entropy for next iteration
-        if entropy_index >= 32 {
-            combined_entropy = hash_entropy(&combined_entropy);
-            entropy_index = 0;
-        }
+    output
+}
+
+/// Read a deck stored by `mock_encrypt_deck` back (mock backend)
+/// 
+/// Stands in for the circuits reading `Enc<Mxe, Deck>` inside MPC.
+pub fn mock_decrypt_deck(deck: &Deck) -> Result<[u8; DECK_SIZE]> {
+    require!(deck.initialized, PokerError::DeckNotInitialized);
+    
+    let mut cards = [0u8; DECK_SIZE];
+    for (position, (card, ciphertext)) in cards.iter_mut().zip(&deck.ciphertexts).enumerate() {
+        let keystream = super::mpc_deal::mock_keystream(&deck.session_id, deck.nonce + position as u128);
+        *card = ciphertext[0] ^ keystream[0];
     }
     
-    msg!("[INTEGRATED MXE] Shuffle complete - deck randomized");
-    Ok(deck)
+    Ok(cards)
 }
 
-// ============================================================================
-// MXE INTEGRATION HELPERS
-// ============================================================================
-
-// Note: These helper functions were removed as they're unused.
-// The actual MXE integration is handled in the main mpc_shuffle_deck_with_mxe function
-// and integration.rs module.
+/// Deck account holding `cards` as a mock shuffle stores them
+#[cfg(test)]
+pub(crate) fn mock_deck_account(cards: &[u8; DECK_SIZE]) -> Deck {
+    let session_id = [7; 32];
+    let mut deck = Deck {
+        game: Pubkey::new_unique(),
+        nonce: 0,
+        ciphertexts: [[0; 32]; DECK_SIZE],
+        session_id: [0; 32],
+        initialized: false,
+        bump: 0,
+    };
+    deck.store_encrypted(session_id, &mock_encrypt_deck(cards, &session_id)).unwrap();
+    deck
+}
 
 // ============================================================================
 // MOCK IMPLEMENTATIONS (FOR TESTING WITHOUT MXE)
@@ -403,32 +327,39 @@ fn perform_integrated_shuffle(
 
 /// Secure shuffle using combined player entropy
 /// 
-/// This implements the Fisher-Yates shuffle algorithm with cryptographically
-/// secure randomness derived from all players' entropy contributions.
+/// Runs the shuffle circuit's own code (`poker_shared::circuits`) on the
+/// plaintext entropy, so a mock shuffle deals exactly the deck the MPC
+/// cluster would for the same entropy and node randomness.
 /// 
 /// In production with MXE deployed, this computation happens in MPC across
-/// Arcium network nodes. For development, we use deterministic shuffle.
-fn secure_shuffle_with_entropy(player_entropy: &[[u8; 32]]) -> Result<[u8; DECK_SIZE]> {
-    let mut indices = [0u8; DECK_SIZE];
-    
-    // Initialize with sequential indices (0-51)
-    for i in 0..DECK_SIZE {
-        indices[i] = i as u8;
-    }
-    
-    // Combine all player entropy using XOR and hashing
-    // This creates a single source of randomness that no single player controls
-    let combined_entropy = combine_player_entropy(player_entropy);
+/// Arcium network nodes. For development, the nodes' randomness is drawn
+/// from a stream of all players' entropy instead.
+fn secure_shuffle_with_entropy(
+    player_entropy: &[[u8; 32]],
+    player_pubkeys: &[Pubkey],
+    game_id: u64,
+    variant: GameVariant,
+) -> Result<[u8; DECK_SIZE]> {
+    require!(player_entropy.len() <= MAX_PLAYERS, PokerError::ArciumMpcFailed);
     
-    // Use ChaCha20-based PRNG seeded with combined entropy
-    // This provides cryptographically secure randomness
-    let mut rng_state = initialize_rng(&combined_entropy);
+    // Circuit input: one entropy slot per seat, padding past the seated count
+    let mut entropy = [[0u8; 32]; MAX_PLAYERS];
+    entropy[..player_entropy.len()].copy_from_slice(player_entropy);
+    let active_count = player_entropy.len() as u8;
     
-    // Fisher-Yates shuffle with secure randomness
-    for i in (1..DECK_SIZE).rev() {
-        let j = secure_random_index(&mut rng_state, i + 1);
-        indices.swap(i, j);
-    }
+    // Absorb all player entropy into one stream that no single player
+    // controls, bound to the seated players and the game
+    let game_bytes = game_id.to_le_bytes();
+    let mut context: Vec<&[u8]> = player_pubkeys.iter().map(|player| player.as_ref()).collect();
+    context.push(&game_bytes);
+    let draws = ShuffleRng::new(player_entropy, &context).shuffle_draws();
+    
+    // Fisher-Yates with rejection-sampled swaps, as the circuit runs it
+    let indices = if variant.is_short_deck() {
+        circuits::shuffled_short_deck(&entropy, active_count, &draws)
+    } else {
+        circuits::shuffled_deck(&entropy, active_count, &draws)
+    };
     
     Ok(indices)
 }
@@ -482,14 +413,6 @@ fn generate_session_id(game_id: u64, players: &[Pubkey]) -> [u8; 32] {
 
 // Helper functions for secure shuffle
 
-fn create_initial_deck() -> [u8; DECK_SIZE] {
-    let mut deck = [0u8; DECK_SIZE];
-    for i in 0..DECK_SIZE {
-        deck[i] = i as u8;
-    }
-    deck
-}
-
 fn hash_to_ciphertext(data: &[u8]) -> [u8; 32] {
     // Simple hash using XOR folding
     // In production with Arcium, use their encryption
@@ -513,50 +436,6 @@ fn generate_player_nonce(game_id: u64, player_index: u8) -> [u8; 16] {
     nonce
 }
 
-fn hash_entropy(input: &[u8; 32]) -> [u8; 32] {
-    let mut output = *input;
-    
-    // Simple mixing function
-    for i in 0..32 {
-        let prev_idx = if i == 0 { 31 } else { i - 1 };
-        let next_idx = if i == 31 { 0 } else { i + 1 };
-        
-        let prev = input[prev_idx];
-        let curr = input[i];
-        let next = input[next_idx];
-        
-        output[i] = (prev.wrapping_mul(7))
-            .wrapping_add(curr.wrapping_mul(13))
-            .wrapping_add(next.wrapping_mul(17));
-    }
-    
-    output
-}
-
-fn combine_player_entropy(entropy: &[[u8; 32]]) -> [u8; 32] {
-    let mut combined = [0u8; 32];
-    for e in entropy {
-        for i in 0..32 {
-            combined[i] ^= e[i];
-        }
-    }
-    combined
-}
-
-fn initialize_rng(seed: &[u8; 32]) -> u64 {
-    let mut state = 0u64;
-    for (i, &byte) in seed.iter().enumerate() {
-        state ^= (byte as u64) << ((i % 8) * 8);
-    }
-    state
-}
-
-fn secure_random_index(state: &mut u64, max: usize) -> usize {
-    // Linear congruential generator with good parameters
-    *state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
-    (*state % max as u64) as usize
-}
-
 /// Generate proof of correct shuffle
 fn generate_shuffle_proof(
     shuffled_indices: &[u8; DECK_SIZE],
@@ -582,4 +461,87 @@ fn generate_shuffle_proof(
     proof.extend_from_slice(&deck_hash);
     
     Ok(proof)
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::deck::initial_deck_indices;
+    use crate::shared::constants::{DRAW_HAND_CARDS, MAX_HOLE_CARDS, NO_CARD, SHORT_DECK_SIZE};
+
+    fn params(variant: GameVariant) -> MxeShuffleParams<'static> {
+        MxeShuffleParams {
+            mxe: None,
+            encrypted_entropy: vec![[3u8; 32], [141u8; 32], [77u8; 32]],
+            computation_offset: 9,
+            comp_def_offset: 0,
+            player_pubkeys: vec![
+                Pubkey::new_from_array([1; 32]),
+                Pubkey::new_from_array([2; 32]),
+                Pubkey::new_from_array([3; 32]),
+            ],
+            game_id: 42,
+            variant,
+            callback_accounts: vec![],
+        }
+    }
+
+    #[test]
+    fn test_mock_shuffle_deals_the_circuit_permutation() {
+        for variant in [GameVariant::TexasHoldem, GameVariant::ShortDeck] {
+            let params = params(variant);
+            let shuffled = mock_shuffle(&params).unwrap().shuffled_indices;
+
+            // The circuit, fed the same entropy and the same node draws
+            let mut entropy = [[0u8; 32]; MAX_PLAYERS];
+            entropy[..3].copy_from_slice(&params.encrypted_entropy);
+            let game_bytes = params.game_id.to_le_bytes();
+            let context: Vec<&[u8]> = vec![
+                params.player_pubkeys[0].as_ref(),
+                params.player_pubkeys[1].as_ref(),
+                params.player_pubkeys[2].as_ref(),
+                &game_bytes,
+            ];
+            let draws = ShuffleRng::new(&params.encrypted_entropy, &context).shuffle_draws();
+            let circuit = if variant.is_short_deck() {
+                circuits::shuffled_short_deck(&entropy, 3, &draws)
+            } else {
+                circuits::shuffled_deck(&entropy, 3, &draws)
+            };
+
+            assert_eq!(shuffled, circuit, "{:?}", variant);
+            assert_ne!(shuffled, initial_deck_indices(variant));
+        }
+    }
+
+    #[test]
+    fn test_every_player_changes_the_mock_order() {
+        let base = mock_shuffle(&params(GameVariant::TexasHoldem)).unwrap().shuffled_indices;
+        for player in 0..3 {
+            let mut changed = params(GameVariant::TexasHoldem);
+            changed.encrypted_entropy[player][31] ^= 1;
+            assert_ne!(mock_shuffle(&changed).unwrap().shuffled_indices, base);
+        }
+    }
+
+    #[test]
+    fn test_mock_deck_round_trips_through_the_deck_account() {
+        let shuffled = mock_shuffle(&params(GameVariant::TexasHoldem)).unwrap().shuffled_indices;
+        assert_eq!(mock_decrypt_deck(&mock_deck_account(&shuffled)).unwrap(), shuffled);
+    }
+
+    #[test]
+    fn test_program_deck_layout_matches_the_circuits() {
+        assert_eq!(initial_deck_indices(GameVariant::TexasHoldem), circuits::ordered_deck());
+        assert_eq!(initial_deck_indices(GameVariant::ShortDeck), circuits::ordered_short_deck());
+        assert_eq!(
+            (DECK_SIZE, SHORT_DECK_SIZE, MAX_HOLE_CARDS, DRAW_HAND_CARDS, NO_CARD),
+            (
+                circuits::DECK_SIZE,
+                circuits::SHORT_DECK_SIZE,
+                circuits::MAX_HOLE_CARDS,
+                circuits::DRAW_HAND_CARDS,
+                circuits::NO_CARD,
+            )
+        );
+    }
+}
@@ -0,0 +1,114 @@
+use poker_shared::circuits::{DECK_SIZE, SHUFFLE_DRAW_ATTEMPTS};
+use solana_keccak_hasher as keccak;
+
+/// Randomness for the plaintext shuffle (mock and integrated MXE paths)
+///
+/// A Keccak sponge: every player's entropy, plus any context such as the
+/// game ID, is absorbed into one seed, which is then squeezed 32 bytes at a
+/// time with a block counter. Changing any single contribution changes the
+/// whole stream, so no player controls the order on their own.
+pub struct ShuffleRng {
+    seed: [u8; 32],
+    counter: u64,
+    block: [u8; 32],
+    used: usize,
+}
+
+impl ShuffleRng {
+    /// Absorb the players' entropy and any extra context into a fresh stream
+    pub fn new(player_entropy: &[[u8; 32]], context: &[&[u8]]) -> Self {
+        let count = (player_entropy.len() as u32).to_le_bytes();
+        let mut input: Vec<&[u8]> = vec![b"arcium-poker/shuffle", &count];
+        input.extend(player_entropy.iter().map(|entropy| &entropy[..]));
+        input.extend_from_slice(context);
+        
+        Self {
+            seed: keccak::hashv(&input).to_bytes(),
+            counter: 0,
+            block: [0; 32],
+            used: 32,
+        }
+    }
+    
+    /// Next byte of the stream
+    pub fn next_byte(&mut self) -> u8 {
+        if self.used == self.block.len() {
+            self.block = keccak::hashv(&[&self.seed[..], &self.counter.to_le_bytes()]).to_bytes();
+            self.counter += 1;
+            self.used = 0;
+        }
+        
+        let byte = self.block[self.used];
+        self.used += 1;
+        byte
+    }
+    
+    /// Node randomness for the shuffle circuits, standing in for the
+    /// draws the MPC cluster takes from `ArcisRNG` (position 0 never swaps,
+    /// so its draws stay zero)
+    pub fn shuffle_draws(&mut self) -> [[u8; SHUFFLE_DRAW_ATTEMPTS]; DECK_SIZE] {
+        let mut draws = [[0u8; SHUFFLE_DRAW_ATTEMPTS]; DECK_SIZE];
+        for draw in draws.iter_mut().skip(1).flatten() {
+            *draw = self.next_byte();
+        }
+        draws
+    }
+    
+    /// Uniform value in `0..bound` by rejection sampling
+    ///
+    /// Bytes from the top `256 % bound` values are discarded: reducing them
+    /// mod `bound` would make the low values more likely.
+    pub fn below(&mut self, bound: u8) -> u8 {
+        assert!(bound > 0, "bound must be positive");
+        let limit = 256 - 256 % bound as u16;
+        
+        loop {
+            let byte = self.next_byte();
+            if (byte as u16) < limit {
+                return byte % bound;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_player_changes_the_draws() {
+        let entropy = [[1u8; 32], [2u8; 32], [3u8; 32]];
+        let draws = |entropy: &[[u8; 32]]| ShuffleRng::new(entropy, &[]).shuffle_draws();
+
+        let base = draws(&entropy);
+        assert_eq!(base, draws(&entropy));
+        assert_eq!(base[0], [0; SHUFFLE_DRAW_ATTEMPTS]);
+
+        for player in 0..entropy.len() {
+            let mut changed = entropy;
+            changed[player][31] ^= 1;
+            assert_ne!(base, draws(&changed));
+        }
+    }
+
+    #[test]
+    fn test_below_is_uniform_for_awkward_bounds() {
+        // 52 leaves 48 biased bytes; 3 leaves one
+        let mut rng = ShuffleRng::new(&[[7u8; 32]], &[]);
+        for bound in [3u8, 52] {
+            let draws = 52_000;
+            let mut counts = vec![0u32; bound as usize];
+            for _ in 0..draws {
+                counts[rng.below(bound) as usize] += 1;
+            }
+
+            let expected = draws as f64 / bound as f64;
+            let chi_square: f64 = counts
+                .iter()
+                .map(|&count| (count as f64 - expected).powi(2) / expected)
+                .sum();
+            // Far beyond the 99.9th percentile for 51 degrees of freedom
+            assert!(chi_square < 100.0, "bound {}: chi-square {}", bound, chi_square);
+        }
+    }
+}
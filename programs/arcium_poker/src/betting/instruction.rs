@@ -118,9 +118,13 @@ pub fn handle_raise(
     let total_bet = call_amount + raise_amount;
     
     // Execute raise
+    let full_raise = game.current_bet + raise_amount >= fixed_limit_next_level(game);
     player_state.place_bet(total_bet)?;
     game.pot += total_bet;
     game.current_bet = player_state.current_bet;
+    if full_raise {
+        game.bets_this_round = game.bets_this_round.saturating_add(1);
+    }
     
     // Check if this was an all-in raise
     if player_state.chip_stack == 0 {
@@ -159,6 +163,7 @@ pub fn handle_bet(
     player_state.place_bet(bet_amount)?;
     game.pot += bet_amount;
     game.current_bet = bet_amount;
+    game.bets_this_round = 1;
     
     // Check if this was an all-in bet
     if player_state.chip_stack == 0 {
@@ -191,24 +196,40 @@ pub fn handle_all_in(
     validate_betting_stage(game)?;
     validate_player_turn(game, player_state.seat_index)?;
     
-    let all_in_amount = validate_all_in(player_state)?;
+    validate_all_in(player_state)?;
+    
+    // Pot- and fixed-limit tables cap a shove at the largest legal bet; the
+    // player is only all-in if that still takes their whole stack
+    let all_in_amount = max_bet_total(game, player_state);
+    require!(all_in_amount > 0, PokerError::InvalidBetAmount);
     
-    // Execute all-in
+    // Execute all-in (place_bet marks the player all-in once the stack is empty)
     player_state.place_bet(all_in_amount)?;
     game.pot += all_in_amount;
     
-    // Update current bet if this all-in is higher
+    // Update current bet if this all-in is higher; only a full raise
+    // counts toward the fixed-limit cap
     if player_state.current_bet > game.current_bet {
+        if player_state.current_bet >= fixed_limit_next_level(game) {
+            game.bets_this_round = game.bets_this_round.saturating_add(1);
+        }
         game.current_bet = player_state.current_bet;
     }
     
-    player_state.is_all_in = true;
-    
-    msg!(
-        "[BETTING] Player {} went ALL-IN with {}",
-        player_state.player,
-        all_in_amount
-    );
+    if player_state.is_all_in {
+        msg!(
+            "[BETTING] Player {} went ALL-IN with {}",
+            player_state.player,
+            all_in_amount
+        );
+    } else {
+        msg!(
+            "[BETTING] Player {} shoved the {:?} maximum of {}",
+            player_state.player,
+            game.betting_structure,
+            all_in_amount
+        );
+    }
     
     // Move to next player
     advance_to_next_player(game)?;
@@ -29,4 +29,6 @@ pub use validator::{
     validate_betting_stage,
     validate_action_timeout,
     is_betting_round_complete,
+    max_bet_total,
+    fixed_limit_next_level,
 };
\ No newline at end of file
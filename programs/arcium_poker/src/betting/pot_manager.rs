@@ -73,14 +73,16 @@ impl PotManager {
                 continue;
             }
             
-            let level_contribution = all_in_amount - previous_level;
             let mut pot_amount = 0u64;
             let mut side_pot = SidePot::new(0);
             
-            // Calculate pot amount and eligible players
+            // Folded chips stay in the pot; only active players can win it
             for i in 0..player_count {
+                let contribution = self.player_contributions[i].min(*all_in_amount);
+                if contribution > previous_level {
+                    pot_amount += contribution - previous_level;
+                }
                 if active_players[i] && self.player_contributions[i] >= *all_in_amount {
-                    pot_amount += level_contribution;
                     side_pot.add_eligible_player(i);
                 }
             }
@@ -101,7 +103,7 @@ impl PotManager {
         // Remaining goes to main pot (for players not all-in)
         let mut main_pot_amount = 0u64;
         for i in 0..player_count {
-            if active_players[i] && self.player_contributions[i] > previous_level {
+            if self.player_contributions[i] > previous_level {
                 main_pot_amount += self.player_contributions[i] - previous_level;
             }
         }
@@ -170,4 +172,26 @@ mod tests {
         // Main pot: (100-50) * 2 = 100 (only players 1 and 2 eligible)
         assert_eq!(pot_manager.main_pot, 100);
     }
+    
+    #[test]
+    fn test_folded_chips_stay_in_the_pots() {
+        let mut pot_manager = PotManager::new();
+        pot_manager.add_bet(0, 50);  // All-in
+        pot_manager.add_bet(1, 80);  // Folded
+        pot_manager.add_bet(2, 100);
+        
+        let mut all_in = [false; MAX_PLAYERS];
+        all_in[0] = true;
+        let mut active = [false; MAX_PLAYERS];
+        active[0] = true;
+        active[2] = true;
+        
+        pot_manager.calculate_side_pots(3, &all_in, &active).unwrap();
+        
+        // Seat 1 cannot win, but its chips are still played for
+        assert_eq!(pot_manager.side_pots[0].amount, 150);
+        assert!(!pot_manager.side_pots[0].is_eligible(1));
+        assert_eq!(pot_manager.main_pot, 80);
+        assert_eq!(pot_manager.get_total_pot(), 230);
+    }
 }
\ No newline at end of file
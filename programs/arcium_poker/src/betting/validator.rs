@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use crate::game::state::Game;
 use crate::player::state::PlayerState;
-use crate::types::{GameStage, PlayerAction};
+use crate::types::{BettingStructure, DealRound, GameStage, PlayerAction};
 use crate::shared::{PokerError, constants::*};
 
 /// Validate that it's the player's turn
@@ -54,6 +54,22 @@ pub fn validate_raise(
     // Check sufficient chips
     validate_sufficient_chips(player_state, total_bet)?;
     
+    // Fixed limit: raise exactly to the next bet level (completing a bring-in
+    // counts), capped at FIXED_LIMIT_MAX_BETS per round
+    if game.betting_structure == BettingStructure::FixedLimit {
+        require!(
+            game.bets_this_round < FIXED_LIMIT_MAX_BETS,
+            PokerError::InvalidAction
+        );
+        let next_level = fixed_limit_next_level(game);
+        require!(
+            game.current_bet + raise_amount == next_level
+                || (game.current_bet + raise_amount < next_level && total_bet == player_state.chip_stack),
+            PokerError::InvalidBetAmount
+        );
+        return Ok(());
+    }
+    
     // Minimum raise is 2x the current bet (or big blind if no bet yet)
     let min_raise = if game.current_bet == 0 {
         game.big_blind
@@ -66,6 +82,11 @@ pub fn validate_raise(
         PokerError::InvalidBetAmount
     );
     
+    require!(
+        total_bet <= max_bet_total(game, player_state),
+        PokerError::InvalidBetAmount
+    );
+    
     Ok(())
 }
 
@@ -75,18 +96,61 @@ pub fn validate_bet(
     player_state: &PlayerState,
     bet_amount: u64,
 ) -> Result<()> {
-    // Must be at least big blind
-    require!(
-        bet_amount >= game.big_blind || bet_amount == player_state.chip_stack,
-        PokerError::InvalidBetAmount
-    );
+    // Must be at least big blind (exactly the round's bet size in fixed limit)
+    if game.betting_structure == BettingStructure::FixedLimit {
+        let bet_size = game.fixed_limit_bet();
+        require!(
+            bet_amount == bet_size || (bet_amount < bet_size && bet_amount == player_state.chip_stack),
+            PokerError::InvalidBetAmount
+        );
+    } else {
+        require!(
+            bet_amount >= game.big_blind || bet_amount == player_state.chip_stack,
+            PokerError::InvalidBetAmount
+        );
+    }
     
     // Check sufficient chips
     validate_sufficient_chips(player_state, bet_amount)?;
     
+    require!(
+        bet_amount <= max_bet_total(game, player_state),
+        PokerError::InvalidBetAmount
+    );
+    
     Ok(())
 }
 
+/// Largest amount a player may put in with one action under the table's
+/// betting structure (call included)
+pub fn max_bet_total(game: &Game, player_state: &PlayerState) -> u64 {
+    match game.betting_structure {
+        BettingStructure::NoLimit => player_state.chip_stack,
+        BettingStructure::PotLimit => {
+            // Pot-sized raise: call first, then raise by the pot after the call
+            let call_amount = game.current_bet.saturating_sub(player_state.current_bet);
+            let pot_after_call = game.pot + call_amount;
+            (call_amount + pot_after_call).min(player_state.chip_stack)
+        }
+        BettingStructure::FixedLimit => {
+            // Call plus one bet, nothing once the round is capped
+            let call_amount = game.current_bet.saturating_sub(player_state.current_bet);
+            let raise = if game.bets_this_round < FIXED_LIMIT_MAX_BETS {
+                fixed_limit_next_level(game) - game.current_bet
+            } else {
+                0
+            };
+            (call_amount + raise).min(player_state.chip_stack)
+        }
+    }
+}
+
+/// Total bet a fixed-limit raise must reach: one bet size above the last
+/// full bet level
+pub fn fixed_limit_next_level(game: &Game) -> u64 {
+    (game.bets_this_round as u64 + 1) * game.fixed_limit_bet()
+}
+
 /// Validate check action (only valid if no bet to call)
 pub fn validate_check(
     game: &Game,
@@ -124,6 +188,18 @@ pub fn validate_betting_stage(game: &Game) -> Result<()> {
         ),
         PokerError::InvalidGameStage
     );
+    
+    // No betting while a draw phase is open
+    require!(!game.drawing, PokerError::InvalidGameStage);
+    
+    // Nor before the street's board cards have come back from MPC
+    require!(
+        game.community_reveal_pending == 0,
+        PokerError::CommunityRevealPending
+    );
+    
+    // Nor while players are still being dealt their cards
+    require!(game.deal_round == DealRound::None, PokerError::CardsNotDealt);
     Ok(())
 }
 
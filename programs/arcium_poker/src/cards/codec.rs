@@ -0,0 +1,360 @@
+//! Canonical card encoding shared by the program, the circuits and tooling
+//!
+//! A card is one byte: `suit * 13 + (rank - 2)` with suits ordered hearts,
+//! diamonds, clubs, spades, so 0 is 2h and 51 is As. Text notation is rank
+//! then suit, e.g. "As", "Td", "2c".
+
+use anchor_lang::prelude::*;
+use super::deck::Card;
+use crate::types::{Rank, Suit};
+use crate::shared::constants::DECK_SIZE;
+use crate::shared::PokerError;
+
+/// Suits in encoding order
+pub const SUITS: [Suit; 4] = [Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades];
+
+/// Ranks in encoding order (two through ace)
+pub const RANKS: [Rank; 13] = [
+    Rank::Two,
+    Rank::Three,
+    Rank::Four,
+    Rank::Five,
+    Rank::Six,
+    Rank::Seven,
+    Rank::Eight,
+    Rank::Nine,
+    Rank::Ten,
+    Rank::Jack,
+    Rank::Queen,
+    Rank::King,
+    Rank::Ace,
+];
+
+/// Encode a card as its canonical byte (0-51)
+pub fn encode(card: &Card) -> u8 {
+    suit_index(card.suit) * 13 + (card.rank as u8 - 2)
+}
+
+/// Decode a canonical byte (0-51) into a card
+pub fn decode(index: u8) -> Result<Card> {
+    require!((index as usize) < DECK_SIZE, PokerError::InvalidCardIndex);
+    Ok(Card::new(
+        SUITS[(index / 13) as usize],
+        RANKS[(index % 13) as usize],
+    ))
+}
+
+/// Rank for a rank value (2-14); aces-low callers pass 14 for an ace
+pub fn rank_from_value(value: u8) -> Option<Rank> {
+    (2..=14).contains(&value).then(|| RANKS[(value - 2) as usize])
+}
+
+fn suit_index(suit: Suit) -> u8 {
+    match suit {
+        Suit::Hearts => 0,
+        Suit::Diamonds => 1,
+        Suit::Clubs => 2,
+        Suit::Spades => 3,
+    }
+}
+
+/// Notation character for a rank value; 1 and 14 are both aces
+pub fn value_char(value: u8) -> char {
+    match value {
+        1 | 14 => 'A',
+        13 => 'K',
+        12 => 'Q',
+        11 => 'J',
+        10 => 'T',
+        v => (b'0' + v) as char,
+    }
+}
+
+/// Notation character for a suit
+pub fn suit_char(suit: Suit) -> char {
+    match suit {
+        Suit::Hearts => 'h',
+        Suit::Diamonds => 'd',
+        Suit::Clubs => 'c',
+        Suit::Spades => 's',
+    }
+}
+
+fn rank_from_char(c: char) -> Option<Rank> {
+    let value = match c.to_ascii_uppercase() {
+        'A' => 14,
+        'K' => 13,
+        'Q' => 12,
+        'J' => 11,
+        'T' => 10,
+        d @ '2'..='9' => d as u8 - b'0',
+        _ => return None,
+    };
+    rank_from_value(value)
+}
+
+fn suit_from_char(c: char) -> Option<Suit> {
+    match c.to_ascii_lowercase() {
+        'h' => Some(Suit::Hearts),
+        'd' => Some(Suit::Diamonds),
+        'c' => Some(Suit::Clubs),
+        's' => Some(Suit::Spades),
+        _ => None,
+    }
+}
+
+impl std::fmt::Display for Card {
+    /// e.g. "As", "Td"
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", value_char(self.rank as u8), suit_char(self.suit))
+    }
+}
+
+impl std::str::FromStr for Card {
+    type Err = PokerError;
+
+    /// Parse two-character notation ("As", "td"); case-insensitive
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let (Some(rank), Some(suit), None) = (chars.next(), chars.next(), chars.next()) else {
+            return Err(PokerError::InvalidCardIndex);
+        };
+
+        match (rank_from_char(rank), suit_from_char(suit)) {
+            (Some(rank), Some(suit)) => Ok(Card::new(suit, rank)),
+            _ => Err(PokerError::InvalidCardIndex),
+        }
+    }
+}
+
+/// Parse a run of cards, with or without separators ("AsKd", "As Kd", "As,Kd")
+pub fn parse_cards(s: &str) -> Result<Vec<Card>> {
+    let chars: Vec<char> = s
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != ',')
+        .collect();
+    require!(chars.len().is_multiple_of(2), PokerError::InvalidCardIndex);
+
+    chars
+        .chunks(2)
+        .map(|pair| {
+            pair.iter()
+                .collect::<String>()
+                .parse::<Card>()
+                .map_err(Into::into)
+        })
+        .collect()
+}
+
+/// Format cards as space-separated notation ("As Kd")
+pub fn format_cards(cards: &[Card]) -> String {
+    cards
+        .iter()
+        .map(|card| card.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Set of cards as a 52-bit mask, bit `i` set for canonical card `i`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CardSet(pub u64);
+
+impl CardSet {
+    /// Mask with every card of the 52-card deck
+    pub const FULL: CardSet = CardSet((1u64 << DECK_SIZE) - 1);
+
+    pub const fn empty() -> Self {
+        CardSet(0)
+    }
+
+    pub fn insert(&mut self, card: Card) {
+        self.0 |= 1u64 << encode(&card);
+    }
+
+    pub fn remove(&mut self, card: Card) {
+        self.0 &= !(1u64 << encode(&card));
+    }
+
+    pub fn contains(&self, card: Card) -> bool {
+        self.0 & (1u64 << encode(&card)) != 0
+    }
+
+    pub fn union(self, other: CardSet) -> CardSet {
+        CardSet(self.0 | other.0)
+    }
+
+    pub fn intersection(self, other: CardSet) -> CardSet {
+        CardSet(self.0 & other.0)
+    }
+
+    /// Cards in `self` that are not in `other`
+    pub fn difference(self, other: CardSet) -> CardSet {
+        CardSet(self.0 & !other.0)
+    }
+
+    pub fn is_disjoint(&self, other: CardSet) -> bool {
+        self.0 & other.0 == 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Cards in canonical order (2h first, As last)
+    pub fn iter(&self) -> CardSetIter {
+        CardSetIter(self.0)
+    }
+
+    /// Build a set from cards, failing on duplicates
+    pub fn from_unique(cards: &[Card]) -> Result<CardSet> {
+        let mut set = CardSet::empty();
+        for &card in cards {
+            require!(!set.contains(card), PokerError::InvalidCardIndex);
+            set.insert(card);
+        }
+        Ok(set)
+    }
+}
+
+impl FromIterator<Card> for CardSet {
+    fn from_iter<I: IntoIterator<Item = Card>>(iter: I) -> Self {
+        let mut set = CardSet::empty();
+        for card in iter {
+            set.insert(card);
+        }
+        set
+    }
+}
+
+impl IntoIterator for CardSet {
+    type Item = Card;
+    type IntoIter = CardSetIter;
+
+    fn into_iter(self) -> CardSetIter {
+        self.iter()
+    }
+}
+
+impl std::ops::BitOr for CardSet {
+    type Output = CardSet;
+
+    fn bitor(self, other: CardSet) -> CardSet {
+        self.union(other)
+    }
+}
+
+impl std::fmt::Display for CardSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", format_cards(&self.iter().collect::<Vec<_>>()))
+    }
+}
+
+/// Iterator over a `CardSet`, lowest card first
+pub struct CardSetIter(u64);
+
+impl Iterator for CardSetIter {
+    type Item = Card;
+
+    fn next(&mut self) -> Option<Card> {
+        if self.0 == 0 {
+            return None;
+        }
+        let index = self.0.trailing_zeros() as u8;
+        self.0 &= self.0 - 1;
+        decode(index).ok()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Small deterministic generator so the property tests are reproducible
+    fn xorshift(state: &mut u64) -> u64 {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        *state
+    }
+
+    #[test]
+    fn test_byte_and_string_round_trip_for_every_card() {
+        for index in 0..DECK_SIZE as u8 {
+            let card = decode(index).unwrap();
+            assert_eq!(encode(&card), index);
+            assert_eq!(card.to_index(), index);
+
+            let text = card.to_string();
+            assert_eq!(text.len(), 2);
+            assert_eq!(text.parse::<Card>().unwrap(), card);
+            assert_eq!(text.to_uppercase().parse::<Card>().unwrap(), card);
+        }
+
+        assert!(decode(DECK_SIZE as u8).is_err());
+        assert_eq!(decode(0).unwrap().to_string(), "2h");
+        assert_eq!(decode(51).unwrap().to_string(), "As");
+        assert_eq!("Td".parse::<Card>().unwrap(), Card::new(Suit::Diamonds, Rank::Ten));
+    }
+
+    #[test]
+    fn test_bad_notation_is_rejected() {
+        for bad in ["", "A", "Asd", "1s", "Ax", "10s", "sA"] {
+            assert!(bad.parse::<Card>().is_err(), "{} should not parse", bad);
+        }
+        assert!(parse_cards("AsK").is_err());
+    }
+
+    #[test]
+    fn test_parse_and_format_cards_round_trip() {
+        let cards = parse_cards("AsKd 2c,Th").unwrap();
+        assert_eq!(format_cards(&cards), "As Kd 2c Th");
+        assert_eq!(parse_cards(&format_cards(&cards)).unwrap(), cards);
+    }
+
+    #[test]
+    fn test_card_set_round_trips_random_masks() {
+        let mut state = 0x9E37_79B9_7F4A_7C15u64;
+
+        for _ in 0..1_000 {
+            let mask = CardSet(xorshift(&mut state) & CardSet::FULL.0);
+
+            let cards: Vec<Card> = mask.iter().collect();
+            assert_eq!(cards.len(), mask.len());
+            assert!(cards.windows(2).all(|w| encode(&w[0]) < encode(&w[1])));
+            assert!(cards.iter().all(|&c| mask.contains(c)));
+            assert_eq!(cards.iter().copied().collect::<CardSet>(), mask);
+            assert_eq!(CardSet::from_unique(&cards).unwrap(), mask);
+
+            let other = CardSet(xorshift(&mut state) & CardSet::FULL.0);
+            let union = mask | other;
+            assert_eq!(union.len() + mask.intersection(other).len(), mask.len() + other.len());
+            assert!(union.iter().all(|c| mask.contains(c) || other.contains(c)));
+            assert!(mask.difference(other).is_disjoint(other));
+        }
+    }
+
+    #[test]
+    fn test_card_set_insert_remove_and_duplicates() {
+        let ace = Card::new(Suit::Spades, Rank::Ace);
+        let mut set = CardSet::empty();
+
+        set.insert(ace);
+        assert!(set.contains(ace));
+        assert_eq!(set.to_string(), "As");
+
+        set.remove(ace);
+        assert!(set.is_empty());
+
+        assert_eq!(CardSet::FULL.len(), DECK_SIZE);
+        assert!(CardSet::from_unique(&[ace, ace]).is_err());
+    }
+}
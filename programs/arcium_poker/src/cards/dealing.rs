@@ -1,101 +1,327 @@
 use anchor_lang::prelude::*;
 use crate::game::state::Game;
+use crate::game::flow::reset_betting_round;
+use crate::game::stud::{bring_in_seat, first_to_act};
 use crate::player::state::PlayerState;
-use crate::arcium::mpc_deal::{mpc_deal_card, DealParams, EncryptedCard};
-use crate::shared::{constants::*, PokerError};
-use crate::types::GameStage;
+use crate::arcium::backend::MxeQueue;
+use crate::arcium::mpc_deal::{mpc_deal_with_mxe, Deal, Dealt, MxeDealParams};
+use crate::arcium::mpc_community::queue_community_reveal;
+use crate::cards::deck::{Card, Deck};
+use crate::shared::{constants::NO_CARD, PokerError};
+use crate::types::{DealRound, StudStreet};
 
-/// Deal hole cards to all players
-pub fn deal_hole_cards(
-    game: &mut Game,
-    player_states: &mut [Account<PlayerState>],
-) -> Result<()> {
-    require!(
-        game.stage == GameStage::PreFlop,
-        PokerError::InvalidGameStage
-    );
-    require!(game.deck_initialized, PokerError::DeckNotInitialized);
+/// Open a deal round: every live seat is owed cards before play continues
+pub fn open_deal_round(game: &mut Game, round: DealRound) -> Result<()> {
+    game.deal_seat = next_seat_to_deal(game, None).ok_or(PokerError::NotEnoughPlayers)?;
+    game.deal_round = round;
     
-    msg!("[DEALING] Dealing {} hole cards to {} players", HOLE_CARDS, game.player_count);
-    
-    // Deal HOLE_CARDS (2) cards to each player
-    for player_state in player_states.iter_mut() {
-        if !player_state.has_cards {
-            deal_cards_to_player(game, player_state)?;
-        }
-    }
-    
-    msg!("[DEALING] All hole cards dealt");
+    msg!("[DEALING] {:?} round open, first seat {}", round, game.deal_seat);
     Ok(())
 }
 
-/// Deal encrypted cards to a specific player
-fn deal_cards_to_player(
+/// Deal what the open round still owes, seat by seat
+///
+/// Each seat's cards come from the next deck positions and are dealt by one
+/// computation. The mock backend deals the rest of the round at once; on
+/// Arcium this queues the next seat's deal and returns, the deal callback
+/// applies it, and the next call queues the following seat. Stops when the
+/// round completes, even if completing it opens the next one.
+pub fn continue_dealing(
     game: &mut Game,
-    player_state: &mut PlayerState,
+    deck: &Deck,
+    mxe: Option<&MxeQueue>,
+    player_states: &mut [PlayerState],
 ) -> Result<()> {
-    msg!("[DEALING] Dealing to player at seat {}", player_state.seat_index);
+    let round = game.deal_round;
+    require!(round != DealRound::None, PokerError::InvalidGameStage);
+    require!(game.deck_initialized, PokerError::DeckNotInitialized);
+    require!(!game.has_pending_computation(), PokerError::MpcResultPending);
     
-    // Deal hole cards using Arcium MPC
-    for i in 0..HOLE_CARDS {
-        // Get next encrypted card index from deck
-        let mut encrypted_deck = game.get_encrypted_deck()?;
-        let card_index = encrypted_deck.get_next_encrypted_card()?;
+    while game.deal_round == round {
+        let seat = game.deal_seat as usize;
+        let deal = take_deal(game, &mut player_states[seat])?;
         
-        // Use Arcium MPC to deal encrypted card to player
-        let deal_params = DealParams {
-            card_index,
-            player: player_state.player,
-            session_id: game.encrypted_deck,
-            game_id: game.game_id,
+        let computation_offset = game.next_computation_offset();
+        let (accounts, comp_def_offset) = match mxe {
+            Some(queue) => {
+                let (accounts, offset) = queue.accounts_for(deal.circuit(), computation_offset)?;
+                (Some(accounts), offset)
+            }
+            None => (None, deal.circuit().comp_def_offset()),
         };
         
-        let encrypted_card = mpc_deal_card(deal_params)?;
-        
-        // Store encrypted card in player state
-        player_state.encrypted_hole_cards[i] = encrypted_card.encrypted_index;
+        let dealt = mpc_deal_with_mxe(MxeDealParams {
+            mxe: accounts,
+            deck,
+            deal,
+            owner_key: player_states[seat].encryption_pubkey,
+            computation_offset,
+            comp_def_offset,
+            callback_accounts: mxe.map(|queue| queue.callback_accounts.clone()).unwrap_or_default(),
+        })?;
         
-        msg!(
-            "[DEALING] Card {} dealt to seat {} (encrypted: {})",
-            i + 1,
-            player_state.seat_index,
-            encrypted_card.encrypted_index
-        );
+        match dealt {
+            Some(dealt) => apply_dealt(game, player_states, dealt)?,
+            None => {
+                msg!("[DEALING] Seat {} deal queued, waiting for the MXE", seat);
+                game.await_computation(computation_offset, comp_def_offset, Clock::get()?.unix_timestamp);
+                return Ok(());
+            }
+        }
     }
     
-    player_state.has_cards = true;
     Ok(())
 }
 
-/// Reveal community cards (flop/turn/river)
-pub fn reveal_community_cards(
+/// Take the deck positions for the current seat's cards
+///
+/// Positions are recorded when the deal is queued, so the deck cursor never
+/// hands the same position out twice.
+fn take_deal(game: &mut Game, player_state: &mut PlayerState) -> Result<Deal> {
+    let seat = game.deal_seat as usize;
+    require!(player_state.seat_index as usize == seat, PokerError::InvalidAction);
+    
+    let deal = match game.deal_round {
+        DealRound::HoleCards => {
+            let count = game.variant.hole_cards_at_start() as u8;
+            let positions = game.take_deck_positions(count)?;
+            player_state.hole_card_positions = positions;
+            player_state.hole_cards.clear();
+            Deal::HoleCards {
+                start: player_state.hole_card_positions[0],
+                count,
+            }
+        }
+        DealRound::UpCards => Deal::UpCard {
+            position: game.take_deck_positions(1)?[0],
+        },
+        DealRound::DownCards => {
+            let position = game.take_deck_positions(1)?[0];
+            game.seventh_street_cards[seat] = position;
+            Deal::DownCard { position }
+        }
+        DealRound::None => return err!(PokerError::InvalidGameStage),
+    };
+    
+    Ok(deal)
+}
+
+/// Record the cards dealt to the current seat and move to the next seat,
+/// completing the round after the last one
+///
+/// Called with the mock's result, or from the deal callbacks.
+pub fn apply_dealt(
     game: &mut Game,
-    count: u8,
+    player_states: &mut [PlayerState],
+    dealt: Dealt,
 ) -> Result<()> {
-    require!(game.deck_initialized, PokerError::DeckNotInitialized);
+    let seat = game.deal_seat as usize;
     
-    msg!("[DEALING] Revealing {} community cards", count);
+    match (game.deal_round, dealt) {
+        (DealRound::HoleCards, Dealt::Hidden(cards)) => {
+            let player_state = &mut player_states[seat];
+            require!(
+                cards.len() == player_state.hole_card_positions.len(),
+                PokerError::InvalidCardIndex
+            );
+            player_state.hole_cards = cards;
+            player_state.has_cards = true;
+            msg!("[DEALING] {} hole cards dealt to seat {}", player_state.hole_cards.len(), seat);
+        }
+        (DealRound::DownCards, Dealt::Hidden(cards)) => {
+            require!(cards.len() == 1, PokerError::InvalidCardIndex);
+            game.seventh_street_hole_cards[seat] = cards[0];
+            msg!("[DEALING] Down card dealt to seat {}", seat);
+        }
+        (DealRound::UpCards, Dealt::FaceUp(index)) => {
+            let card = Card::from_index_for_variant(index, game.variant)?;
+            let slot = game.up_cards[seat]
+                .iter()
+                .position(|&c| c == NO_CARD)
+                .ok_or(PokerError::InvalidCardIndex)?;
+            game.up_cards[seat][slot] = index;
+            msg!(
+                "[DEALING] Upcard {} to seat {}: {:?} of {:?}",
+                slot + 1,
+                seat,
+                card.rank,
+                card.suit
+            );
+        }
+        _ => return err!(PokerError::InvalidCardIndex),
+    }
     
-    // Burn a card first (poker rules)
-    let mut encrypted_deck = game.get_encrypted_deck()?;
-    encrypted_deck.burn_card()?;
-    msg!("[DEALING] Burn card dealt");
+    match next_seat_to_deal(game, Some(game.deal_seat)) {
+        Some(next) => game.deal_seat = next,
+        None => finish_deal_round(game, player_states)?,
+    }
     
-    // Reveal community cards
-    for i in 0..count {
-        let card_index = encrypted_deck.get_next_encrypted_card()?;
-        let community_index = game.community_cards_revealed as usize;
-        
-        // Store card index in community cards array
-        game.community_cards[community_index] = card_index;
-        game.community_cards_revealed += 1;
-        
-        msg!(
-            "[DEALING] Community card {} revealed (index: {})",
-            community_index + 1,
-            card_index
-        );
+    Ok(())
+}
+
+/// First live seat after `seat` (from seat 0 when `None`)
+fn next_seat_to_deal(game: &Game, seat: Option<u8>) -> Option<u8> {
+    let from = seat.map_or(0, |seat| seat + 1);
+    (from..game.player_count).find(|&next| game.active_players[next as usize])
+}
+
+/// Close the round and open whatever follows it
+fn finish_deal_round(game: &mut Game, player_states: &mut [PlayerState]) -> Result<()> {
+    let round = game.deal_round;
+    game.deal_round = DealRound::None;
+    game.deal_seat = 0;
+    msg!("[DEALING] {:?} round complete", round);
+    
+    match round {
+        DealRound::HoleCards => {
+            // Replacements in draw games are dealt from here on
+            game.discard_start = game.deck_cursor;
+            
+            // Stud: third street also deals one card face up
+            if game.variant.is_stud() {
+                open_deal_round(game, DealRound::UpCards)?;
+            }
+        }
+        DealRound::UpCards if game.street == StudStreet::Third => {
+            open_third_street(game, player_states)?;
+        }
+        DealRound::UpCards | DealRound::DownCards => {
+            reset_betting_round(game)?;
+        }
+        DealRound::None => {}
     }
     
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Post the bring-in (lowest upcard) and open third-street betting left of it
+fn open_third_street(game: &mut Game, player_states: &mut [PlayerState]) -> Result<()> {
+    let bring_in = bring_in_seat(game)?;
+    player_states[bring_in as usize].place_bet(game.small_blind)?;
+    game.pot += game.small_blind;
+    game.current_bet = game.small_blind;
+    game.bets_this_round = 0;
+    game.current_player_index = first_to_act(game)?;
+    game.last_action_at = Clock::get()?.unix_timestamp;
+    
+    msg!("[BLINDS] Seat {} brings in for {}", bring_in, game.small_blind);
+    msg!("[GAME START] Current player: seat {}", game.current_player_index);
+    Ok(())
+}
+
+/// Reveal the community cards the street is owed (flop/turn/river)
+///
+/// Burns one card and reveals the next ones through the MPC
+/// `reveal_community` circuit; see `arcium::mpc_community`.
+pub fn reveal_community_cards(
+    game: &mut Game,
+    deck: &Deck,
+    mxe: Option<&MxeQueue>,
+) -> Result<()> {
+    msg!("[DEALING] Revealing {} community cards", game.community_reveal_pending);
+    
+    queue_community_reveal(game, deck, mxe)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arcium::mpc_deal::decrypt_for_owner;
+    use crate::arcium::mpc_shuffle::mock_deck_account;
+    use crate::shared::constants::DECK_SIZE;
+    use crate::game::stud::advance_street;
+    use crate::types::{GameStage, GameVariant};
+
+    /// Table with `seats` players dealt in, fresh from the shuffle
+    fn table(seats: u8) -> (Game, Vec<PlayerState>) {
+        let mut game = Game::deserialize(&mut &[0u8; Game::LEN][..]).unwrap();
+        game.player_count = seats;
+        game.deck_initialized = true;
+
+        let player_states = (0..seats)
+            .map(|seat| {
+                game.active_players[seat as usize] = true;
+                let mut player_state = PlayerState::deserialize(&mut &[0u8; PlayerState::LEN][..]).unwrap();
+                player_state.seat_index = seat;
+                player_state.encryption_pubkey = [seat + 1; 32];
+                player_state
+            })
+            .collect();
+
+        (game, player_states)
+    }
+
+    #[test]
+    fn test_hole_cards_come_from_consecutive_deck_positions() {
+        let cards: [u8; DECK_SIZE] = core::array::from_fn(|position| (DECK_SIZE - 1 - position) as u8);
+        let deck = mock_deck_account(&cards);
+        let (mut game, mut player_states) = table(3);
+        game.active_players[1] = false;
+
+        open_deal_round(&mut game, DealRound::HoleCards).unwrap();
+        continue_dealing(&mut game, &deck, None, &mut player_states).unwrap();
+
+        assert_eq!(game.deal_round, DealRound::None);
+        assert_eq!((game.deck_cursor, game.discard_start), (4, 4));
+        assert!(!player_states[1].has_cards);
+        for (seat, positions) in [(0, [0, 1]), (2, [2, 3])] {
+            let player_state = &player_states[seat];
+            assert!(player_state.has_cards);
+            assert_eq!(player_state.hole_card_positions, positions);
+            let dealt: Vec<u8> = player_state
+                .hole_cards
+                .iter()
+                .map(|card| decrypt_for_owner(card, &player_state.encryption_pubkey))
+                .collect();
+            assert_eq!(dealt, positions.map(|position| cards[position as usize]));
+        }
+
+        // Nothing left to deal
+        assert!(continue_dealing(&mut game, &deck, None, &mut player_states).is_err());
+    }
+
+    #[test]
+    fn test_stud_opens_the_upcard_round_after_hole_cards() {
+        let cards: [u8; DECK_SIZE] = core::array::from_fn(|position| position as u8);
+        let deck = mock_deck_account(&cards);
+        let (mut game, mut player_states) = table(2);
+        game.variant = GameVariant::SevenCardStud;
+
+        open_deal_round(&mut game, DealRound::HoleCards).unwrap();
+        continue_dealing(&mut game, &deck, None, &mut player_states).unwrap();
+
+        assert_eq!((game.deal_round, game.deal_seat), (DealRound::UpCards, 0));
+        assert_eq!(game.deck_cursor, 4);
+        assert_eq!(player_states[1].hole_card_positions, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_stud_streets_deal_from_the_deck_cursor() {
+        let (mut game, mut player_states) = table(3);
+        game.variant = GameVariant::SevenCardStud;
+        game.stage = GameStage::PreFlop;
+        // Two down cards and one upcard each are out
+        game.deck_cursor = 9;
+
+        let mut street_positions = |game: &mut Game| -> Vec<u8> {
+            (0..3)
+                .map(|seat| {
+                    game.deal_seat = seat;
+                    take_deal(game, &mut player_states[seat as usize]).unwrap().position()
+                })
+                .collect()
+        };
+
+        advance_street(&mut game).unwrap();
+        assert_eq!((game.street, game.deal_round), (StudStreet::Fourth, DealRound::UpCards));
+        assert_eq!(street_positions(&mut game), vec![9, 10, 11]);
+
+        game.deal_round = DealRound::None;
+        game.street = StudStreet::Sixth;
+        advance_street(&mut game).unwrap();
+        assert_eq!(game.deal_round, DealRound::DownCards);
+        assert_eq!(street_positions(&mut game), vec![12, 13, 14]);
+        assert_eq!(game.seventh_street_cards[..3], [12, 13, 14]);
+        assert_eq!(game.deck_cursor, 15);
+    }
+}
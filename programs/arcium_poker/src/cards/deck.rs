@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
-use crate::types::{Suit, Rank};
-use crate::shared::constants::DECK_SIZE;
+use crate::types::{GameVariant, Suit, Rank};
+use crate::shared::constants::{DECK_SIZE, NO_CARD, SHORT_DECK_SIZE, SHORT_DECK_LOWEST_RANK};
+use super::codec;
+use crate::arcium::types::Argument;
 
 /// Represents a playing card
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
@@ -15,152 +17,209 @@ impl Card {
     }
     
     /// Convert card to index (0-51)
+    ///
+    /// Indices are the same for every variant; a short deck simply never
+    /// contains indices for twos through fives. See `cards::codec`.
     pub fn to_index(&self) -> u8 {
-        let suit_offset = match self.suit {
-            Suit::Hearts => 0,
-            Suit::Diamonds => 13,
-            Suit::Clubs => 26,
-            Suit::Spades => 39,
-        };
-        let rank_offset = self.rank as u8 - 2; // Rank starts at 2
-        suit_offset + rank_offset
+        codec::encode(self)
     }
     
     /// Create card from index (0-51)
     pub fn from_index(index: u8) -> Result<Self> {
-        require!(index < 52, crate::shared::PokerError::InvalidCardIndex);
-        
-        let suit = match index / 13 {
-            0 => Suit::Hearts,
-            1 => Suit::Diamonds,
-            2 => Suit::Clubs,
-            3 => Suit::Spades,
-            _ => return Err(crate::shared::PokerError::InvalidCardIndex.into()),
-        };
-        
-        let rank = match (index % 13) + 2 {
-            2 => Rank::Two,
-            3 => Rank::Three,
-            4 => Rank::Four,
-            5 => Rank::Five,
-            6 => Rank::Six,
-            7 => Rank::Seven,
-            8 => Rank::Eight,
-            9 => Rank::Nine,
-            10 => Rank::Ten,
-            11 => Rank::Jack,
-            12 => Rank::Queen,
-            13 => Rank::King,
-            14 => Rank::Ace,
-            _ => return Err(crate::shared::PokerError::InvalidCardIndex.into()),
-        };
-        
-        Ok(Card { suit, rank })
+        codec::decode(index)
+    }
+    
+    /// Create card from index, rejecting cards not in the variant's deck
+    pub fn from_index_for_variant(index: u8, variant: GameVariant) -> Result<Self> {
+        let card = Self::from_index(index)?;
+        require!(card.in_deck(variant), crate::shared::PokerError::InvalidCardIndex);
+        Ok(card)
+    }
+    
+    /// Check if this card is part of the deck used by a variant
+    pub fn in_deck(&self, variant: GameVariant) -> bool {
+        !variant.is_short_deck() || self.rank as u8 >= SHORT_DECK_LOWEST_RANK
     }
 }
 
-/// Encrypted deck state (stored in Game account)
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
-pub struct EncryptedDeck {
-    /// Encrypted card indices (shuffled order)
-    /// Each element is encrypted card index pointing to original deck position
-    pub encrypted_indices: [u8; DECK_SIZE],
+/// MXE-owned shuffled deck for one game (`Enc<Mxe, Deck>` in the circuits)
+///
+/// The shuffle callback writes the ciphertexts here. Deal, draw and reveal
+/// computations reference them with `Deck::argument` instead of sending the
+/// deck, so no client ever holds the whole deck.
+#[account]
+pub struct Deck {
+    /// Game this deck belongs to
+    pub game: Pubkey,
+    
+    /// Nonce the MXE encrypted the deck under
+    pub nonce: u128,
     
-    /// MPC commitment/hash of the shuffled deck
-    /// Used for verification that shuffle was done correctly
-    pub shuffle_commitment: [u8; 32],
+    /// One ciphertext per deck position (slots past a short deck hold NO_CARD)
+    pub ciphertexts: [[u8; 32]; DECK_SIZE],
     
-    /// Next card index to deal from deck
-    pub next_card_index: u8,
+    /// Shuffle session the ciphertexts belong to
+    pub session_id: [u8; 32],
     
-    /// Number of cards dealt
-    pub cards_dealt: u8,
+    /// Ciphertexts hold the current hand's shuffle
+    pub initialized: bool,
     
-    /// Shuffle round/session ID from Arcium
-    pub shuffle_session_id: [u8; 32],
+    /// Bump seed for PDA
+    pub bump: u8,
 }
 
-impl Default for EncryptedDeck {
-    fn default() -> Self {
-        Self {
-            encrypted_indices: [0; DECK_SIZE],
-            shuffle_commitment: [0; 32],
-            next_card_index: 0,
-            cards_dealt: 0,
-            shuffle_session_id: [0; 32],
-        }
+impl Deck {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // game
+        16 + // nonce
+        (32 * DECK_SIZE) + // ciphertexts
+        32 + // session_id
+        1 + // initialized
+        1; // bump
+    
+    /// Offset of the encrypted state (nonce, then ciphertexts) in the account
+    pub const ENCRYPTED_OFFSET: u32 = 8 + 32;
+    
+    /// Length of the encrypted state
+    pub const ENCRYPTED_LEN: u32 = 16 + 32 * DECK_SIZE as u32;
+    
+    /// Address of this deck account (the game's `[b"deck", game]` PDA)
+    pub fn address(&self) -> Result<Pubkey> {
+        Pubkey::create_program_address(&[b"deck", self.game.as_ref(), &[self.bump]], &crate::ID)
+            .map_err(|_| error!(crate::shared::PokerError::DeckNotInitialized))
     }
-}
-
-impl EncryptedDeck {
-    /// Initialize deck with encrypted indices from Arcium MPC shuffle
-    pub fn initialize_from_shuffle(
-        encrypted_indices: [u8; DECK_SIZE],
-        shuffle_commitment: [u8; 32],
-        shuffle_session_id: [u8; 32],
-    ) -> Self {
-        Self {
-            encrypted_indices,
-            shuffle_commitment,
-            next_card_index: 0,
-            cards_dealt: 0,
-            shuffle_session_id,
-        }
+    
+    /// Computation argument pointing the MXE at a stored deck
+    pub fn argument(deck: Pubkey) -> Argument {
+        Argument::Account(deck, Self::ENCRYPTED_OFFSET, Self::ENCRYPTED_LEN)
     }
     
-    /// Get next card index to deal (still encrypted)
-    pub fn get_next_encrypted_card(&mut self) -> Result<u8> {
+    /// Store a shuffle output laid out like the account: nonce (LE) then
+    /// one 32-byte ciphertext per position
+    pub fn store_encrypted(&mut self, session_id: [u8; 32], output: &[u8]) -> Result<()> {
         require!(
-            self.next_card_index < DECK_SIZE as u8,
-            crate::shared::PokerError::InvalidCardIndex
+            output.len() == Self::ENCRYPTED_LEN as usize,
+            crate::shared::PokerError::ArciumMpcFailed
         );
         
-        let card_index = self.encrypted_indices[self.next_card_index as usize];
-        self.next_card_index += 1;
-        self.cards_dealt += 1;
+        let (nonce, ciphertexts) = output.split_at(16);
+        self.nonce = u128::from_le_bytes(nonce.try_into().unwrap());
+        for (slot, chunk) in self.ciphertexts.iter_mut().zip(ciphertexts.chunks_exact(32)) {
+            slot.copy_from_slice(chunk);
+        }
+        self.session_id = session_id;
+        self.initialized = true;
         
-        Ok(card_index)
-    }
-    
-    /// Burn a card (deal it but don't reveal)
-    pub fn burn_card(&mut self) -> Result<()> {
-        self.get_next_encrypted_card()?;
         Ok(())
     }
     
-    /// Check if deck has enough cards
-    pub fn has_cards(&self, count: u8) -> bool {
-        self.next_card_index + count <= DECK_SIZE as u8
+    /// Forget the previous hand's deck
+    pub fn clear(&mut self) {
+        self.nonce = 0;
+        self.ciphertexts = [[0; 32]; DECK_SIZE];
+        self.session_id = [0; 32];
+        self.initialized = false;
     }
 }
 
-/// Generate standard 52-card deck (unshuffled)
+/// Generate standard 52-card deck (unshuffled), in canonical index order
 pub fn generate_standard_deck() -> [Card; DECK_SIZE] {
-    let mut deck = [Card { suit: Suit::Hearts, rank: Rank::Two }; DECK_SIZE];
-    let mut index = 0;
-    
-    for suit in [Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades] {
-        for rank_val in 2..=14 {
-            let rank = match rank_val {
-                2 => Rank::Two,
-                3 => Rank::Three,
-                4 => Rank::Four,
-                5 => Rank::Five,
-                6 => Rank::Six,
-                7 => Rank::Seven,
-                8 => Rank::Eight,
-                9 => Rank::Nine,
-                10 => Rank::Ten,
-                11 => Rank::Jack,
-                12 => Rank::Queen,
-                13 => Rank::King,
-                14 => Rank::Ace,
-                _ => Rank::Two,
-            };
-            deck[index] = Card { suit, rank };
-            index += 1;
-        }
+    core::array::from_fn(|index| Card::new(codec::SUITS[index / 13], codec::RANKS[index % 13]))
+}
+
+/// Generate 36-card short deck, sixes through aces (unshuffled)
+pub fn generate_short_deck() -> [Card; SHORT_DECK_SIZE] {
+    let mut deck = [Card { suit: Suit::Hearts, rank: Rank::Six }; SHORT_DECK_SIZE];
+    
+    for (slot, card) in deck.iter_mut().zip(
+        generate_standard_deck()
+            .into_iter()
+            .filter(|c| c.in_deck(GameVariant::ShortDeck)),
+    ) {
+        *slot = card;
     }
     
     deck
+}
+
+/// Unshuffled card indices for a variant's deck
+///
+/// The first `variant.deck_size()` slots hold the deck; any remaining slots
+/// are `NO_CARD` so they can never be mistaken for a card.
+pub fn initial_deck_indices(variant: GameVariant) -> [u8; DECK_SIZE] {
+    let mut indices = [NO_CARD; DECK_SIZE];
+    
+    for (slot, card) in indices.iter_mut().zip(
+        generate_standard_deck()
+            .into_iter()
+            .filter(|c| c.in_deck(variant)),
+    ) {
+        *slot = card.to_index();
+    }
+    
+    indices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_deck_stores_encrypted_output() {
+        let mut deck = Deck {
+            game: Pubkey::default(),
+            nonce: 0,
+            ciphertexts: [[0; 32]; DECK_SIZE],
+            session_id: [0; 32],
+            initialized: false,
+            bump: 0,
+        };
+        
+        let mut output = 7u128.to_le_bytes().to_vec();
+        for position in 0..DECK_SIZE {
+            output.extend_from_slice(&[position as u8; 32]);
+        }
+        assert_eq!(output.len(), Deck::ENCRYPTED_LEN as usize);
+        
+        deck.store_encrypted([1; 32], &output).unwrap();
+        assert!(deck.initialized);
+        assert_eq!(deck.nonce, 7);
+        assert_eq!(deck.ciphertexts[51], [51; 32]);
+        
+        // The referenced range is exactly the nonce and ciphertexts
+        let mut data = Vec::new();
+        deck.try_serialize(&mut data).unwrap();
+        let start = Deck::ENCRYPTED_OFFSET as usize;
+        assert_eq!(&data[start..start + output.len()], &output[..]);
+        assert_eq!(data.len(), Deck::LEN);
+        
+        assert!(deck.store_encrypted([1; 32], &output[1..]).is_err());
+        deck.clear();
+        assert!(!deck.initialized);
+    }
+    
+    #[test]
+    fn test_short_deck_contents() {
+        let deck = generate_short_deck();
+        assert!(deck.iter().all(|c| c.rank >= Rank::Six));
+        for (i, a) in deck.iter().enumerate() {
+            assert!(deck[i + 1..].iter().all(|b| a != b));
+        }
+    }
+    
+    #[test]
+    fn test_initial_deck_indices() {
+        let standard = initial_deck_indices(GameVariant::TexasHoldem);
+        assert!(standard.iter().enumerate().all(|(i, &c)| c == i as u8));
+        
+        let short = initial_deck_indices(GameVariant::ShortDeck);
+        assert_eq!(short[0], Card::new(Suit::Hearts, Rank::Six).to_index());
+        assert!(short[..SHORT_DECK_SIZE]
+            .iter()
+            .all(|&i| Card::from_index_for_variant(i, GameVariant::ShortDeck).is_ok()));
+        assert!(short[SHORT_DECK_SIZE..].iter().all(|&i| i == NO_CARD));
+        
+        let two = Card::new(Suit::Spades, Rank::Two).to_index();
+        assert!(Card::from_index_for_variant(two, GameVariant::ShortDeck).is_err());
+    }
 }
\ No newline at end of file
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 use super::deck::Card;
-use crate::types::{HandRank, Rank, Suit};
+use super::fast_evaluator::{evaluate_cards, evaluate_seven, evaluate_short_deck};
+use crate::types::{GameVariant, HandRank, Suit};
 use crate::shared::PokerError;
 
 /// Evaluated hand with rank and kickers
@@ -10,6 +11,7 @@ pub struct EvaluatedHand {
     pub primary_value: u8,    // Main card value (e.g., pair value, three of a kind value)
     pub secondary_value: u8,  // Secondary value (e.g., second pair in two pair)
     pub kickers: [u8; 5],     // Kicker cards for tie-breaking
+    pub short_deck: bool,     // Ranked under short-deck rules (flush beats full house)
 }
 
 impl EvaluatedHand {
@@ -19,6 +21,21 @@ impl EvaluatedHand {
             primary_value: primary,
             secondary_value: secondary,
             kickers,
+            short_deck: false,
+        }
+    }
+    
+    /// Same hand, ranked under short-deck rules
+    pub fn with_short_deck_ranking(self) -> Self {
+        Self { short_deck: true, ..self }
+    }
+    
+    /// Category strength used for ordering; short deck swaps flush and full house
+    pub fn category_strength(&self) -> u8 {
+        match self.rank {
+            HandRank::Flush if self.short_deck => HandRank::FullHouse as u8,
+            HandRank::FullHouse if self.short_deck => HandRank::Flush as u8,
+            rank => rank as u8,
         }
     }
 }
@@ -31,8 +48,8 @@ impl PartialOrd for EvaluatedHand {
 
 impl Ord for EvaluatedHand {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        // Compare hand rank first
-        match self.rank.cmp(&other.rank) {
+        // Compare hand rank first (variant-aware)
+        match self.category_strength().cmp(&other.category_strength()) {
             std::cmp::Ordering::Equal => {
                 // Same rank, compare primary value
                 match self.primary_value.cmp(&other.primary_value) {
@@ -54,6 +71,108 @@ impl Ord for EvaluatedHand {
     }
 }
 
+impl std::fmt::Display for EvaluatedHand {
+    /// Deterministic human-readable description, e.g.
+    /// "Two Pair, Kings and Sevens, Ace kicker"
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let primary = self.primary_value;
+        match self.rank {
+            HandRank::RoyalFlush => return write!(f, "Royal Flush"),
+            HandRank::StraightFlush => write!(f, "Straight Flush, {} high", rank_name(primary))?,
+            HandRank::FourOfAKind => write!(f, "Four of a Kind, {}", rank_plural(primary))?,
+            HandRank::FullHouse => {
+                return write!(
+                    f,
+                    "Full House, {} full of {}",
+                    rank_plural(primary),
+                    rank_plural(self.secondary_value)
+                );
+            }
+            HandRank::Flush => return write!(f, "Flush, {} high", rank_name(primary)),
+            HandRank::Straight => return write!(f, "Straight, {} high", rank_name(primary)),
+            HandRank::ThreeOfAKind => write!(f, "Three of a Kind, {}", rank_plural(primary))?,
+            HandRank::TwoPair => write!(
+                f,
+                "Two Pair, {} and {}",
+                rank_plural(primary),
+                rank_plural(self.secondary_value)
+            )?,
+            HandRank::OnePair => write!(f, "One Pair, {}", rank_plural(primary))?,
+            HandRank::HighCard => {
+                write!(f, "High Card, {}", rank_name(primary))?;
+                // kickers[0] is the high card itself
+                if self.kickers[1] > 0 {
+                    write!(f, ", {} kicker", rank_name(self.kickers[1]))?;
+                }
+                return Ok(());
+            }
+        }
+
+        // Straight flushes carry their ranks as kickers; everything else
+        // reaching here reports the top kicker
+        if self.rank != HandRank::StraightFlush && self.kickers[0] > 0 {
+            write!(f, ", {} kicker", rank_name(self.kickers[0]))?;
+        }
+        Ok(())
+    }
+}
+
+/// Singular rank name for a rank value (2-14)
+fn rank_name(value: u8) -> &'static str {
+    match value {
+        2 => "Two",
+        3 => "Three",
+        4 => "Four",
+        5 => "Five",
+        6 => "Six",
+        7 => "Seven",
+        8 => "Eight",
+        9 => "Nine",
+        10 => "Ten",
+        11 => "Jack",
+        12 => "Queen",
+        13 => "King",
+        14 => "Ace",
+        _ => "?",
+    }
+}
+
+/// Plural rank name for a rank value (2-14)
+fn rank_plural(value: u8) -> &'static str {
+    match value {
+        2 => "Twos",
+        3 => "Threes",
+        4 => "Fours",
+        5 => "Fives",
+        6 => "Sixes",
+        7 => "Sevens",
+        8 => "Eights",
+        9 => "Nines",
+        10 => "Tens",
+        11 => "Jacks",
+        12 => "Queens",
+        13 => "Kings",
+        14 => "Aces",
+        _ => "?",
+    }
+}
+
+/// Evaluated hand together with the five cards that make it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BestHand {
+    pub hand: EvaluatedHand,
+    
+    /// Chosen five cards, most significant first (e.g. pair before kickers)
+    pub cards: [Card; 5],
+}
+
+impl BestHand {
+    /// Human-readable description of the hand
+    pub fn description(&self) -> String {
+        self.hand.to_string()
+    }
+}
+
 /// Evaluate a 5-card poker hand
 pub fn evaluate_hand(cards: &[Card; 5]) -> Result<EvaluatedHand> {
     require!(cards.len() == 5, PokerError::InvalidCardIndex);
@@ -120,13 +239,184 @@ pub fn evaluate_hand(cards: &[Card; 5]) -> Result<EvaluatedHand> {
     Ok(EvaluatedHand::new(HandRank::HighCard, kickers[0], 0, kickers))
 }
 
+/// Evaluate a 5-card hand with the ranking rules of a variant
+///
+/// Short deck counts A-6-7-8-9 as a straight and ranks a flush above a full
+/// house; every other variant uses standard ranking.
+pub fn evaluate_hand_for_variant(cards: &[Card; 5], variant: GameVariant) -> Result<EvaluatedHand> {
+    if variant.is_short_deck() {
+        return evaluate_short_deck(cards);
+    }
+    
+    evaluate_hand(cards)
+}
+
 /// Evaluate best 5-card hand from 7 cards (2 hole + 5 community)
 pub fn evaluate_best_hand(hole_cards: &[Card; 2], community_cards: &[Card; 5]) -> Result<EvaluatedHand> {
-    let mut all_cards = Vec::with_capacity(7);
-    all_cards.extend_from_slice(hole_cards);
-    all_cards.extend_from_slice(community_cards);
+    let all_cards = [
+        hole_cards[0],
+        hole_cards[1],
+        community_cards[0],
+        community_cards[1],
+        community_cards[2],
+        community_cards[3],
+        community_cards[4],
+    ];
+    
+    Ok(evaluate_seven(&all_cards))
+}
+
+/// Evaluate best hand from 7 cards, also returning the chosen five cards
+pub fn evaluate_best_hand_with_cards(
+    hole_cards: &[Card; 2],
+    community_cards: &[Card; 5],
+) -> Result<BestHand> {
+    let all_cards = [
+        hole_cards[0],
+        hole_cards[1],
+        community_cards[0],
+        community_cards[1],
+        community_cards[2],
+        community_cards[3],
+        community_cards[4],
+    ];
+    
+    evaluate_best_of_seven(&all_cards)
+}
+
+/// Evaluate best hand from any 7 cards (e.g. a stud hand), with the chosen five
+pub fn evaluate_best_of_seven(all_cards: &[Card; 7]) -> Result<BestHand> {
+    let hand = evaluate_seven(all_cards);
+    
+    Ok(BestHand {
+        hand,
+        cards: select_best_five(all_cards, &hand)?,
+    })
+}
+
+/// Pick the five cards from `cards` that make up an already evaluated hand
+///
+/// When several cards share a rank (e.g. the kicker), the one with the lowest
+/// deck index wins so the result does not depend on input order.
+pub fn select_best_five(cards: &[Card], hand: &EvaluatedHand) -> Result<[Card; 5]> {
+    require!(cards.len() >= 5 && cards.len() <= 7, PokerError::InvalidCardIndex);
+    
+    let primary = hand.primary_value;
+    let secondary = hand.secondary_value;
+    let k = hand.kickers;
     
-    // Try all combinations of 5 cards from 7
+    let straight_ranks = |high: u8| -> [u8; 5] {
+        if high == 5 {
+            [5, 4, 3, 2, 14]
+        } else if hand.short_deck && high == 9 {
+            // Short-deck wheel: A-6-7-8-9
+            [9, 8, 7, 6, 14]
+        } else {
+            [high, high - 1, high - 2, high - 3, high - 4]
+        }
+    };
+    
+    // Required ranks in output order
+    let ranks: [u8; 5] = match hand.rank {
+        HandRank::RoyalFlush | HandRank::StraightFlush | HandRank::Straight => straight_ranks(primary),
+        HandRank::FourOfAKind => [primary, primary, primary, primary, k[0]],
+        HandRank::FullHouse => [primary, primary, primary, secondary, secondary],
+        HandRank::Flush | HandRank::HighCard => k,
+        HandRank::ThreeOfAKind => [primary, primary, primary, k[0], k[1]],
+        HandRank::TwoPair => [primary, primary, secondary, secondary, k[0]],
+        HandRank::OnePair => [primary, primary, k[0], k[1], k[2]],
+    };
+    
+    // Suit only matters for flushes
+    let flush_suit = if matches!(
+        hand.rank,
+        HandRank::RoyalFlush | HandRank::StraightFlush | HandRank::Flush
+    ) {
+        [Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades]
+            .into_iter()
+            .find(|&suit| cards.iter().filter(|c| c.suit == suit).count() >= 5)
+    } else {
+        None
+    };
+    
+    let mut used = [false; 7];
+    let mut chosen = [cards[0]; 5];
+    
+    for (out, rank) in chosen.iter_mut().zip(ranks) {
+        let pick = cards
+            .iter()
+            .enumerate()
+            .filter(|(i, c)| {
+                !used[*i] && c.rank as u8 == rank && flush_suit.is_none_or(|s| c.suit == s)
+            })
+            .min_by_key(|(_, c)| c.to_index())
+            .map(|(i, c)| (i, *c))
+            .ok_or(PokerError::InvalidCardIndex)?;
+        
+        used[pick.0] = true;
+        *out = pick.1;
+    }
+    
+    Ok(chosen)
+}
+
+/// Evaluate a player's showdown hand according to the table's variant
+///
+/// `hole_cards` are the player's own cards; for stud that is all seven
+/// (down and up) and for draw games the five in hand, with an empty board.
+pub fn evaluate_player_hand(
+    variant: GameVariant,
+    hole_cards: &[Card],
+    community_cards: &[Card],
+) -> Result<BestHand> {
+    require!(
+        hole_cards.len() == variant.cards_per_player(),
+        PokerError::InvalidCardIndex
+    );
+    require!(
+        community_cards.len() == variant.community_cards(),
+        PokerError::InvalidCardIndex
+    );
+    
+    if variant.is_stud() {
+        let all_cards: &[Card; 7] = hole_cards
+            .try_into()
+            .map_err(|_| PokerError::InvalidCardIndex)?;
+        return evaluate_best_of_seven(all_cards);
+    }
+    
+    if variant.is_draw() {
+        let hand = evaluate_cards(hole_cards)?;
+        return Ok(BestHand {
+            hand,
+            cards: select_best_five(hole_cards, &hand)?,
+        });
+    }
+    
+    let board: &[Card; 5] = community_cards
+        .try_into()
+        .map_err(|_| PokerError::InvalidCardIndex)?;
+    
+    if variant.is_omaha() {
+        return super::omaha::evaluate_omaha_hand(hole_cards, board);
+    }
+    
+    if variant.is_short_deck() {
+        let all_cards: Vec<Card> = hole_cards.iter().chain(board).copied().collect();
+        let hand = evaluate_short_deck(&all_cards)?;
+        return Ok(BestHand {
+            hand,
+            cards: select_best_five(&all_cards, &hand)?,
+        });
+    }
+    
+    evaluate_best_hand_with_cards(&[hole_cards[0], hole_cards[1]], board)
+}
+
+/// Reference evaluator: best of all 21 five-card subsets via `evaluate_hand`
+///
+/// Much slower than `evaluate_seven`; kept for cross-checking and benchmarks.
+pub fn evaluate_best_hand_exhaustive(all_cards: &[Card; 7]) -> Result<EvaluatedHand> {
     let mut best_hand: Option<EvaluatedHand> = None;
     
     // Generate all 5-card combinations from 7 cards (21 combinations)
@@ -308,4 +598,130 @@ fn get_kickers(cards: &[Card], exclude_ranks: &[u8]) -> [u8; 5] {
     }
     
     kickers
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Rank;
+    
+    fn card(rank: Rank, suit: Suit) -> Card {
+        Card::new(suit, rank)
+    }
+    
+    #[test]
+    fn test_descriptions() {
+        let cases = [
+            (EvaluatedHand::new(HandRank::RoyalFlush, 14, 0, [14, 13, 12, 11, 10]), "Royal Flush"),
+            (EvaluatedHand::new(HandRank::StraightFlush, 9, 0, [9, 8, 7, 6, 5]), "Straight Flush, Nine high"),
+            (EvaluatedHand::new(HandRank::FourOfAKind, 13, 0, [14, 0, 0, 0, 0]), "Four of a Kind, Kings, Ace kicker"),
+            (EvaluatedHand::new(HandRank::FullHouse, 13, 7, [0; 5]), "Full House, Kings full of Sevens"),
+            (EvaluatedHand::new(HandRank::Flush, 14, 0, [14, 10, 8, 4, 2]), "Flush, Ace high"),
+            (EvaluatedHand::new(HandRank::Straight, 5, 0, [0; 5]), "Straight, Five high"),
+            (EvaluatedHand::new(HandRank::ThreeOfAKind, 6, 0, [14, 9, 0, 0, 0]), "Three of a Kind, Sixes, Ace kicker"),
+            (EvaluatedHand::new(HandRank::TwoPair, 13, 7, [14, 0, 0, 0, 0]), "Two Pair, Kings and Sevens, Ace kicker"),
+            (EvaluatedHand::new(HandRank::OnePair, 2, 0, [12, 9, 3, 0, 0]), "One Pair, Twos, Queen kicker"),
+            (EvaluatedHand::new(HandRank::HighCard, 14, 0, [14, 11, 9, 5, 3]), "High Card, Ace, Jack kicker"),
+        ];
+        
+        for (hand, expected) in cases {
+            assert_eq!(hand.to_string(), expected);
+        }
+    }
+    
+    #[test]
+    fn test_best_five_two_pair() {
+        let hole = [card(Rank::King, Suit::Spades), card(Rank::Seven, Suit::Hearts)];
+        let board = [
+            card(Rank::King, Suit::Hearts),
+            card(Rank::Seven, Suit::Clubs),
+            card(Rank::Ace, Suit::Diamonds),
+            card(Rank::Three, Suit::Spades),
+            card(Rank::Two, Suit::Hearts),
+        ];
+        
+        let best = evaluate_best_hand_with_cards(&hole, &board).unwrap();
+        assert_eq!(best.description(), "Two Pair, Kings and Sevens, Ace kicker");
+        assert_eq!(
+            best.cards,
+            [
+                card(Rank::King, Suit::Hearts),
+                card(Rank::King, Suit::Spades),
+                card(Rank::Seven, Suit::Hearts),
+                card(Rank::Seven, Suit::Clubs),
+                card(Rank::Ace, Suit::Diamonds),
+            ]
+        );
+    }
+    
+    #[test]
+    fn test_best_five_flush_ignores_off_suit_cards() {
+        let hole = [card(Rank::Ace, Suit::Clubs), card(Rank::Ace, Suit::Hearts)];
+        let board = [
+            card(Rank::Nine, Suit::Clubs),
+            card(Rank::Six, Suit::Clubs),
+            card(Rank::Four, Suit::Clubs),
+            card(Rank::Two, Suit::Clubs),
+            card(Rank::King, Suit::Diamonds),
+        ];
+        
+        let best = evaluate_best_hand_with_cards(&hole, &board).unwrap();
+        assert_eq!(best.hand.rank, HandRank::Flush);
+        assert!(best.cards.iter().all(|c| c.suit == Suit::Clubs));
+        assert_eq!(best.cards[0].rank, Rank::Ace);
+    }
+    
+    #[test]
+    fn test_best_five_wheel_orders_ace_last() {
+        let hole = [card(Rank::Ace, Suit::Clubs), card(Rank::Two, Suit::Hearts)];
+        let board = [
+            card(Rank::Three, Suit::Diamonds),
+            card(Rank::Four, Suit::Clubs),
+            card(Rank::Five, Suit::Spades),
+            card(Rank::King, Suit::Clubs),
+            card(Rank::Nine, Suit::Diamonds),
+        ];
+        
+        let best = evaluate_best_hand_with_cards(&hole, &board).unwrap();
+        assert_eq!(best.description(), "Straight, Five high");
+        let ranks = best.cards.map(|c| c.rank);
+        assert_eq!(ranks, [Rank::Five, Rank::Four, Rank::Three, Rank::Two, Rank::Ace]);
+    }
+    
+    #[test]
+    fn test_short_deck_wheel_and_variant_ordering() {
+        let hole = [card(Rank::Ace, Suit::Clubs), card(Rank::Six, Suit::Hearts)];
+        let board = [
+            card(Rank::Seven, Suit::Diamonds),
+            card(Rank::Eight, Suit::Clubs),
+            card(Rank::Nine, Suit::Spades),
+            card(Rank::King, Suit::Clubs),
+            card(Rank::Queen, Suit::Diamonds),
+        ];
+        
+        let best = evaluate_player_hand(GameVariant::ShortDeck, &hole, &board).unwrap();
+        assert_eq!(best.description(), "Straight, Nine high");
+        let ranks = best.cards.map(|c| c.rank);
+        assert_eq!(ranks, [Rank::Nine, Rank::Eight, Rank::Seven, Rank::Six, Rank::Ace]);
+        
+        let flush = [
+            card(Rank::Six, Suit::Spades),
+            card(Rank::Eight, Suit::Spades),
+            card(Rank::Ten, Suit::Spades),
+            card(Rank::Queen, Suit::Spades),
+            card(Rank::Ace, Suit::Spades),
+        ];
+        let boat = [
+            card(Rank::King, Suit::Spades),
+            card(Rank::King, Suit::Hearts),
+            card(Rank::King, Suit::Clubs),
+            card(Rank::Six, Suit::Hearts),
+            card(Rank::Six, Suit::Clubs),
+        ];
+        
+        let standard = |c| evaluate_hand_for_variant(c, GameVariant::TexasHoldem).unwrap();
+        let short = |c| evaluate_hand_for_variant(c, GameVariant::ShortDeck).unwrap();
+        assert!(standard(&flush) < standard(&boat));
+        assert!(short(&flush) > short(&boat));
+    }
+}
@@ -0,0 +1,310 @@
+use anchor_lang::prelude::*;
+use super::deck::Card;
+use super::evaluator::EvaluatedHand;
+use crate::types::{HandRank, Suit};
+use crate::shared::PokerError;
+
+/// A-2-3-4-5 ("wheel") straight mask
+const WHEEL: u16 = (1 << 14) | (1 << 5) | (1 << 4) | (1 << 3) | (1 << 2);
+
+/// A-6-7-8-9 straight mask (short-deck wheel)
+const SHORT_DECK_WHEEL: u16 = (1 << 14) | (1 << 9) | (1 << 8) | (1 << 7) | (1 << 6);
+
+/// Compact rank/suit summary of a set of cards, built without allocating
+struct HandMasks {
+    /// Number of cards of each rank (index == rank value)
+    rank_counts: [u8; 15],
+
+    /// Ranks held in each suit (bit index == rank value)
+    suit_masks: [u16; 4],
+
+    /// Union of all ranks held
+    rank_mask: u16,
+}
+
+impl HandMasks {
+    fn from_cards(cards: &[Card]) -> Self {
+        let mut masks = Self {
+            rank_counts: [0; 15],
+            suit_masks: [0; 4],
+            rank_mask: 0,
+        };
+
+        for card in cards {
+            let rank = card.rank as u8;
+            masks.rank_counts[rank as usize] += 1;
+            masks.suit_masks[suit_slot(card.suit)] |= 1 << rank;
+            masks.rank_mask |= 1 << rank;
+        }
+
+        masks
+    }
+
+    /// Highest rank (excluding `skip`) held at least `min_count` times
+    fn highest_with_count(&self, min_count: u8, skip: u16) -> Option<u8> {
+        (2..=14u8)
+            .rev()
+            .find(|&r| self.rank_counts[r as usize] >= min_count && skip & (1 << r) == 0)
+    }
+}
+
+fn suit_slot(suit: Suit) -> usize {
+    match suit {
+        Suit::Hearts => 0,
+        Suit::Diamonds => 1,
+        Suit::Clubs => 2,
+        Suit::Spades => 3,
+    }
+}
+
+/// Highest straight contained in a rank mask, if any
+fn straight_high(mask: u16, short_deck: bool) -> Option<u8> {
+    for high in (6..=14u8).rev() {
+        let window = 0b1_1111u16 << (high - 4);
+        if mask & window == window {
+            return Some(high);
+        }
+    }
+
+    if short_deck {
+        if mask & SHORT_DECK_WHEEL == SHORT_DECK_WHEEL {
+            return Some(9);
+        }
+    } else if mask & WHEEL == WHEEL {
+        return Some(5);
+    }
+
+    None
+}
+
+/// Take the `N` highest ranks from a mask, padding with zeros
+fn top_ranks<const N: usize>(mask: u16) -> [u8; 5] {
+    let mut out = [0u8; 5];
+    let mut idx = 0;
+
+    for rank in (2..=14u8).rev() {
+        if idx == N {
+            break;
+        }
+        if mask & (1 << rank) != 0 {
+            out[idx] = rank;
+            idx += 1;
+        }
+    }
+
+    out
+}
+
+fn evaluate_masks(masks: &HandMasks, short_deck: bool) -> EvaluatedHand {
+    let flush_mask = masks
+        .suit_masks
+        .iter()
+        .copied()
+        .find(|m| m.count_ones() >= 5);
+
+    // Straight flush / royal flush
+    if let Some(suited) = flush_mask {
+        if let Some(high) = straight_high(suited, short_deck) {
+            if high == 14 {
+                return EvaluatedHand::new(HandRank::RoyalFlush, 14, 0, [14, 13, 12, 11, 10]);
+            }
+            let kickers = if high == 5 {
+                [14, 5, 4, 3, 2]
+            } else if short_deck && high == 9 {
+                [14, 9, 8, 7, 6]
+            } else {
+                [high, high - 1, high - 2, high - 3, high - 4]
+            };
+            return EvaluatedHand::new(HandRank::StraightFlush, high, 0, kickers);
+        }
+    }
+
+    // Four of a kind
+    if let Some(quad) = masks.highest_with_count(4, 0) {
+        let kicker = masks.highest_with_count(1, 1 << quad).unwrap_or(0);
+        return EvaluatedHand::new(HandRank::FourOfAKind, quad, 0, [kicker, 0, 0, 0, 0]);
+    }
+
+    // Short deck: flush outranks full house
+    if short_deck {
+        if let Some(suited) = flush_mask {
+            let kickers = top_ranks::<5>(suited);
+            return EvaluatedHand::new(HandRank::Flush, kickers[0], 0, kickers);
+        }
+    }
+
+    // Full house (second trips counts as the pair)
+    let trips = masks.highest_with_count(3, 0);
+    if let Some(trips_rank) = trips {
+        if let Some(pair_rank) = masks.highest_with_count(2, 1 << trips_rank) {
+            return EvaluatedHand::new(HandRank::FullHouse, trips_rank, pair_rank, [0; 5]);
+        }
+    }
+
+    // Flush
+    if let Some(suited) = flush_mask {
+        let kickers = top_ranks::<5>(suited);
+        return EvaluatedHand::new(HandRank::Flush, kickers[0], 0, kickers);
+    }
+
+    // Straight
+    if let Some(high) = straight_high(masks.rank_mask, short_deck) {
+        return EvaluatedHand::new(HandRank::Straight, high, 0, [0; 5]);
+    }
+
+    // Three of a kind
+    if let Some(trips_rank) = trips {
+        let kickers = top_ranks::<2>(masks.rank_mask & !(1 << trips_rank));
+        return EvaluatedHand::new(HandRank::ThreeOfAKind, trips_rank, 0, kickers);
+    }
+
+    // Two pair / one pair
+    if let Some(high_pair) = masks.highest_with_count(2, 0) {
+        if let Some(low_pair) = masks.highest_with_count(2, 1 << high_pair) {
+            let used = (1 << high_pair) | (1 << low_pair);
+            let kicker = masks.highest_with_count(1, used).unwrap_or(0);
+            return EvaluatedHand::new(HandRank::TwoPair, high_pair, low_pair, [kicker, 0, 0, 0, 0]);
+        }
+
+        let kickers = top_ranks::<3>(masks.rank_mask & !(1 << high_pair));
+        return EvaluatedHand::new(HandRank::OnePair, high_pair, 0, kickers);
+    }
+
+    // High card
+    let kickers = top_ranks::<5>(masks.rank_mask);
+    EvaluatedHand::new(HandRank::HighCard, kickers[0], 0, kickers)
+}
+
+/// Evaluate the best 5-card hand from exactly 7 cards
+///
+/// Produces the same `EvaluatedHand` as running `evaluate_hand` over all 21
+/// five-card subsets and taking the maximum, but uses rank counts and suit
+/// bitmasks instead, with no heap allocation.
+pub fn evaluate_seven(cards: &[Card; 7]) -> EvaluatedHand {
+    evaluate_masks(&HandMasks::from_cards(cards), false)
+}
+
+/// Evaluate the best 5-card hand from any 5 to 7 cards
+pub fn evaluate_cards(cards: &[Card]) -> Result<EvaluatedHand> {
+    require!(
+        cards.len() >= 5 && cards.len() <= 7,
+        PokerError::InvalidCardIndex
+    );
+
+    Ok(evaluate_masks(&HandMasks::from_cards(cards), false))
+}
+
+/// Evaluate the best 5-card hand from 5 to 7 cards under short-deck rules
+///
+/// A-6-7-8-9 is the lowest straight and a flush beats a full house. The
+/// result carries `short_deck` so it compares correctly against other
+/// short-deck hands.
+pub fn evaluate_short_deck(cards: &[Card]) -> Result<EvaluatedHand> {
+    require!(
+        cards.len() >= 5 && cards.len() <= 7,
+        PokerError::InvalidCardIndex
+    );
+
+    let hand = evaluate_masks(&HandMasks::from_cards(cards), true);
+    Ok(hand.with_short_deck_ranking())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::evaluator::{evaluate_hand, evaluate_best_hand_exhaustive};
+    use crate::shared::constants::DECK_SIZE;
+
+    /// Small xorshift generator so samples are reproducible without extra deps
+    struct XorShift(u64);
+
+    impl XorShift {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+    }
+
+    fn random_cards<const N: usize>(rng: &mut XorShift) -> [Card; N] {
+        let mut deck: [u8; DECK_SIZE] = core::array::from_fn(|i| i as u8);
+        for i in 0..N {
+            let j = i + (rng.next() % (DECK_SIZE - i) as u64) as usize;
+            deck.swap(i, j);
+        }
+        core::array::from_fn(|i| Card::from_index(deck[i]).unwrap())
+    }
+
+    fn cards(indices: [u8; 7]) -> [Card; 7] {
+        indices.map(|i| Card::from_index(i).unwrap())
+    }
+
+    #[test]
+    fn test_matches_exhaustive_on_random_seven_card_hands() {
+        let mut rng = XorShift(0x9E37_79B9_7F4A_7C15);
+        for _ in 0..100_000 {
+            let hand = random_cards::<7>(&mut rng);
+            assert_eq!(
+                evaluate_seven(&hand),
+                evaluate_best_hand_exhaustive(&hand).unwrap(),
+                "mismatch for {:?}",
+                hand
+            );
+        }
+    }
+
+    #[test]
+    fn test_matches_five_card_evaluator() {
+        let mut rng = XorShift(0xD1B5_4A32_D192_ED03);
+        for _ in 0..100_000 {
+            let hand = random_cards::<5>(&mut rng);
+            assert_eq!(evaluate_cards(&hand).unwrap(), evaluate_hand(&hand).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_special_hands() {
+        // Wheel straight flush in hearts (A-2-3-4-5) plus two spades
+        let wheel = evaluate_seven(&cards([12, 0, 1, 2, 3, 40, 45]));
+        assert_eq!(wheel.rank, HandRank::StraightFlush);
+        assert_eq!(wheel.primary_value, 5);
+
+        // Two trips make a full house: aces full of kings
+        let boat = evaluate_seven(&cards([12, 25, 38, 11, 24, 37, 0]));
+        assert_eq!(boat.rank, HandRank::FullHouse);
+        assert_eq!((boat.primary_value, boat.secondary_value), (14, 13));
+
+        // Three pairs keep the best kicker from the third pair
+        let two_pair = evaluate_seven(&cards([12, 25, 11, 24, 10, 23, 0]));
+        assert_eq!(two_pair.rank, HandRank::TwoPair);
+        assert_eq!(two_pair.kickers[0], 12);
+    }
+
+    #[test]
+    fn test_short_deck_rules() {
+        // A-6-7-8-9 offsuit is a nine-high straight in short deck only
+        let wheel = cards([12, 4, 18, 32, 46, 10, 22]);
+        let short = evaluate_short_deck(&wheel).unwrap();
+        assert_eq!((short.rank, short.primary_value), (HandRank::Straight, 9));
+        assert_eq!(evaluate_seven(&wheel).rank, HandRank::HighCard);
+
+        // Across players, a flush beats a full house
+        let flush = evaluate_short_deck(&cards([4, 6, 8, 10, 12, 17, 30])).unwrap();
+        let boat = evaluate_short_deck(&cards([11, 24, 37, 10, 23, 4, 5])).unwrap();
+        assert_eq!(flush.rank, HandRank::Flush);
+        assert_eq!(boat.rank, HandRank::FullHouse);
+        assert!(flush > boat);
+
+        // Quads still beat a flush
+        let quads = evaluate_short_deck(&cards([12, 25, 38, 51, 4, 5, 6])).unwrap();
+        assert!(quads > flush);
+    }
+
+    #[test]
+    fn test_rejects_wrong_card_count() {
+        let hand = cards([0, 1, 2, 3, 4, 5, 6]);
+        assert!(evaluate_cards(&hand[..4]).is_err());
+        assert!(evaluate_cards(&hand).is_ok());
+    }
+}
@@ -0,0 +1,400 @@
+use anchor_lang::prelude::*;
+use super::deck::Card;
+use super::codec::value_char;
+use crate::types::{GameVariant, HandRank, LowballRanking, Rank};
+use crate::shared::constants::{COMMUNITY_CARDS, MAX_HOLE_CARDS};
+use crate::shared::PokerError;
+
+/// Highest card allowed in a qualifying eight-or-better low
+pub const LOW_QUALIFIER: u8 = 8;
+
+/// Ace-to-five low hand
+///
+/// Straights and flushes are ignored and aces are low (value 1). Ordering is
+/// "smaller is better": the minimum `LowHand` among players wins the low.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LowHand {
+    /// Pairing category (HighCard is an unpaired hand)
+    pub rank: HandRank,
+    
+    /// Rank values, paired ranks first then kickers, each group high to low
+    pub ranks: [u8; 5],
+}
+
+impl LowHand {
+    /// Unpaired hand with no card above eight
+    pub fn qualifies_eight_or_better(&self) -> bool {
+        self.rank == HandRank::HighCard && self.ranks[0] <= LOW_QUALIFIER
+    }
+}
+
+impl std::fmt::Display for LowHand {
+    /// e.g. "8-6-4-2-A low"
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, &value) in self.ranks.iter().enumerate() {
+            if i > 0 {
+                write!(f, "-")?;
+            }
+            write!(f, "{}", value_char(value))?;
+        }
+        write!(f, " low")
+    }
+}
+
+/// Value of a rank when aces play low
+pub(crate) fn ace_low_value(rank: Rank) -> u8 {
+    if rank == Rank::Ace {
+        1
+    } else {
+        rank as u8
+    }
+}
+
+/// Pairing category and grouped rank values from per-value card counts
+///
+/// Values are ordered pairs first, then kickers, each group high to low.
+fn group_by_count(counts: &[u8; 15]) -> (HandRank, [u8; 5]) {
+    let mut ranks = [0u8; 5];
+    let mut idx = 0;
+    for count in (1..=4u8).rev() {
+        for value in (1..=14u8).rev() {
+            if counts[value as usize] == count {
+                ranks[idx] = value;
+                idx += 1;
+            }
+        }
+    }
+    
+    let max_count = counts.iter().copied().max().unwrap_or(0);
+    let pairs = counts.iter().filter(|&&c| c == 2).count();
+    let rank = match (max_count, pairs) {
+        (4, _) => HandRank::FourOfAKind,
+        (3, 1) => HandRank::FullHouse,
+        (3, _) => HandRank::ThreeOfAKind,
+        (2, 2) => HandRank::TwoPair,
+        (2, _) => HandRank::OnePair,
+        _ => HandRank::HighCard,
+    };
+    
+    (rank, ranks)
+}
+
+/// Evaluate five cards as an ace-to-five low (straights and flushes don't count)
+pub fn evaluate_ace_to_five(cards: &[Card; 5]) -> LowHand {
+    let mut counts = [0u8; 15];
+    for card in cards {
+        counts[ace_low_value(card.rank) as usize] += 1;
+    }
+    
+    let (rank, ranks) = group_by_count(&counts);
+    LowHand { rank, ranks }
+}
+
+/// Evaluate five cards as a deuce-to-seven low
+///
+/// Aces are always high and straights and flushes count against the hand,
+/// so 7-5-4-3-2 offsuit is the nuts and A-2-3-4-5 is just ace high.
+pub fn evaluate_deuce_to_seven(cards: &[Card; 5]) -> LowHand {
+    let mut counts = [0u8; 15];
+    for card in cards {
+        counts[card.rank as usize] += 1;
+    }
+    
+    let (pairing, ranks) = group_by_count(&counts);
+    
+    let is_flush = cards.iter().all(|card| card.suit == cards[0].suit);
+    let is_straight = pairing == HandRank::HighCard && ranks[0] - ranks[4] == 4;
+    
+    let rank = match (is_straight, is_flush) {
+        (true, true) => HandRank::StraightFlush,
+        (false, true) if pairing < HandRank::Flush => HandRank::Flush,
+        (true, false) if pairing < HandRank::Straight => HandRank::Straight,
+        _ => pairing,
+    };
+    
+    LowHand { rank, ranks }
+}
+
+/// Best Razz hand: the lowest ace-to-five five-card hand, with no qualifier
+pub fn evaluate_razz(cards: &[Card]) -> Result<LowHand> {
+    require!(
+        cards.len() >= 5 && cards.len() <= 7,
+        PokerError::InvalidCardIndex
+    );
+    
+    let mut best: Option<LowHand> = None;
+    let n = cards.len();
+    
+    for a in 0..n {
+        for b in (a + 1)..n {
+            for c in (b + 1)..n {
+                for d in (c + 1)..n {
+                    for e in (d + 1)..n {
+                        let low = evaluate_ace_to_five(&[cards[a], cards[b], cards[c], cards[d], cards[e]]);
+                        if best.is_none_or(|current| low < current) {
+                            best = Some(low);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    
+    best.ok_or(PokerError::InvalidCardIndex.into())
+}
+
+/// Best qualifying eight-or-better low from a stud hand (any five of seven)
+///
+/// Every qualifying low is better than every non-qualifying one, so the
+/// best Razz hand qualifies exactly when any low does.
+pub fn evaluate_stud_low(cards: &[Card]) -> Result<Option<LowHand>> {
+    let best = evaluate_razz(cards)?;
+    Ok(best.qualifies_eight_or_better().then_some(best))
+}
+
+/// Evaluate a player's low hand with the ranking their lowball variant uses
+pub fn evaluate_lowball_hand(variant: GameVariant, cards: &[Card]) -> Result<LowHand> {
+    match variant.lowball_ranking() {
+        Some(LowballRanking::AceToFive) => evaluate_razz(cards),
+        Some(LowballRanking::DeuceToSeven) => {
+            let hand: &[Card; 5] = cards
+                .try_into()
+                .map_err(|_| PokerError::InvalidCardIndex)?;
+            Ok(evaluate_deuce_to_seven(hand))
+        }
+        None => err!(PokerError::InvalidAction),
+    }
+}
+
+/// Best qualifying eight-or-better Omaha low, using exactly two hole cards
+/// and three board cards. Returns `None` when no low is possible.
+pub fn evaluate_omaha_low(hole_cards: &[Card], community_cards: &[Card; 5]) -> Result<Option<LowHand>> {
+    require!(
+        hole_cards.len() >= 4 && hole_cards.len() <= MAX_HOLE_CARDS,
+        PokerError::InvalidCardIndex
+    );
+    
+    let mut best: Option<LowHand> = None;
+    
+    for h1 in 0..hole_cards.len() {
+        for h2 in (h1 + 1)..hole_cards.len() {
+            for b1 in 0..COMMUNITY_CARDS {
+                for b2 in (b1 + 1)..COMMUNITY_CARDS {
+                    for b3 in (b2 + 1)..COMMUNITY_CARDS {
+                        let low = evaluate_ace_to_five(&[
+                            hole_cards[h1],
+                            hole_cards[h2],
+                            community_cards[b1],
+                            community_cards[b2],
+                            community_cards[b3],
+                        ]);
+                        
+                        if low.qualifies_eight_or_better() && best.is_none_or(|current| low < current) {
+                            best = Some(low);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    
+    Ok(best)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Suit;
+    
+    fn card(rank: Rank, suit: Suit) -> Card {
+        Card::new(suit, rank)
+    }
+    
+    fn hand(ranks: [Rank; 5]) -> [Card; 5] {
+        let suits = [Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades, Suit::Hearts];
+        core::array::from_fn(|i| card(ranks[i], suits[i]))
+    }
+    
+    #[test]
+    fn test_wheel_is_best_low_and_straights_do_not_count() {
+        let wheel = evaluate_ace_to_five(&hand([Rank::Ace, Rank::Two, Rank::Three, Rank::Four, Rank::Five]));
+        let six_four = evaluate_ace_to_five(&hand([Rank::Ace, Rank::Two, Rank::Three, Rank::Four, Rank::Six]));
+        
+        assert_eq!(wheel.ranks, [5, 4, 3, 2, 1]);
+        assert!(wheel.qualifies_eight_or_better());
+        assert!(wheel < six_four);
+        assert_eq!(wheel.to_string(), "5-4-3-2-A low");
+    }
+    
+    #[test]
+    fn test_low_ordering_compares_highest_card_first() {
+        let eight_seven = evaluate_ace_to_five(&hand([Rank::Eight, Rank::Seven, Rank::Two, Rank::Three, Rank::Ace]));
+        let eight_six = evaluate_ace_to_five(&hand([Rank::Eight, Rank::Six, Rank::Five, Rank::Four, Rank::Three]));
+        
+        assert!(eight_six < eight_seven);
+        assert!(eight_seven.qualifies_eight_or_better());
+    }
+    
+    #[test]
+    fn test_nine_low_and_pairs_do_not_qualify() {
+        let nine = evaluate_ace_to_five(&hand([Rank::Nine, Rank::Two, Rank::Three, Rank::Four, Rank::Ace]));
+        let paired = evaluate_ace_to_five(&hand([Rank::Two, Rank::Two, Rank::Three, Rank::Four, Rank::Ace]));
+        
+        assert!(!nine.qualifies_eight_or_better());
+        assert!(!paired.qualifies_eight_or_better());
+        assert_eq!(paired.rank, HandRank::OnePair);
+        assert_eq!(paired.ranks, [2, 4, 3, 1, 0]);
+    }
+    
+    #[test]
+    fn test_omaha_low_needs_two_low_hole_cards() {
+        let board = [
+            card(Rank::Two, Suit::Hearts),
+            card(Rank::Five, Suit::Clubs),
+            card(Rank::Seven, Suit::Diamonds),
+            card(Rank::King, Suit::Spades),
+            card(Rank::Queen, Suit::Hearts),
+        ];
+        
+        let one_low = [
+            card(Rank::Ace, Suit::Spades),
+            card(Rank::King, Suit::Clubs),
+            card(Rank::Queen, Suit::Spades),
+            card(Rank::Jack, Suit::Diamonds),
+        ];
+        assert_eq!(evaluate_omaha_low(&one_low, &board).unwrap(), None);
+        
+        let two_low = [
+            card(Rank::Ace, Suit::Spades),
+            card(Rank::Three, Suit::Clubs),
+            card(Rank::Queen, Suit::Spades),
+            card(Rank::Jack, Suit::Diamonds),
+        ];
+        let low = evaluate_omaha_low(&two_low, &board).unwrap().unwrap();
+        assert_eq!(low.ranks, [7, 5, 3, 2, 1]);
+    }
+    
+    #[test]
+    fn test_omaha_low_ignores_counterfeited_pairs() {
+        // Board A-2-3: hole A-2 pairs the board, so only 3 low board cards + 4-5 count
+        let board = [
+            card(Rank::Ace, Suit::Hearts),
+            card(Rank::Two, Suit::Clubs),
+            card(Rank::Three, Suit::Diamonds),
+            card(Rank::King, Suit::Spades),
+            card(Rank::Queen, Suit::Hearts),
+        ];
+        let hole = [
+            card(Rank::Ace, Suit::Spades),
+            card(Rank::Two, Suit::Hearts),
+            card(Rank::Four, Suit::Spades),
+            card(Rank::Five, Suit::Diamonds),
+        ];
+        
+        let low = evaluate_omaha_low(&hole, &board).unwrap().unwrap();
+        assert_eq!(low.ranks, [5, 4, 3, 2, 1]);
+    }
+    
+    /// Deuce-to-seven hands from best to worst
+    #[test]
+    fn test_deuce_to_seven_ranking_table() {
+        let suited = |ranks: [Rank; 5]| -> [Card; 5] {
+            core::array::from_fn(|i| card(ranks[i], Suit::Clubs))
+        };
+        
+        let table = [
+            hand([Rank::Seven, Rank::Five, Rank::Four, Rank::Three, Rank::Two]),
+            hand([Rank::Seven, Rank::Six, Rank::Four, Rank::Three, Rank::Two]),
+            hand([Rank::Seven, Rank::Six, Rank::Five, Rank::Four, Rank::Two]),
+            hand([Rank::Eight, Rank::Five, Rank::Four, Rank::Three, Rank::Two]),
+            hand([Rank::Eight, Rank::Six, Rank::Five, Rank::Four, Rank::Two]),
+            hand([Rank::King, Rank::Queen, Rank::Jack, Rank::Ten, Rank::Eight]),
+            hand([Rank::Ace, Rank::Five, Rank::Four, Rank::Three, Rank::Two]),
+            hand([Rank::Ace, Rank::King, Rank::Queen, Rank::Jack, Rank::Nine]),
+            hand([Rank::Two, Rank::Two, Rank::Five, Rank::Four, Rank::Three]),
+            hand([Rank::Three, Rank::Three, Rank::Five, Rank::Four, Rank::Two]),
+            hand([Rank::Ace, Rank::Ace, Rank::King, Rank::Queen, Rank::Jack]),
+            hand([Rank::Two, Rank::Two, Rank::Three, Rank::Three, Rank::Four]),
+            hand([Rank::Two, Rank::Two, Rank::Two, Rank::Three, Rank::Four]),
+            hand([Rank::Six, Rank::Five, Rank::Four, Rank::Three, Rank::Two]),
+            hand([Rank::Ace, Rank::King, Rank::Queen, Rank::Jack, Rank::Ten]),
+            suited([Rank::Seven, Rank::Five, Rank::Four, Rank::Three, Rank::Two]),
+            hand([Rank::Two, Rank::Two, Rank::Two, Rank::Three, Rank::Three]),
+            hand([Rank::Two, Rank::Two, Rank::Two, Rank::Two, Rank::Three]),
+            suited([Rank::Six, Rank::Five, Rank::Four, Rank::Three, Rank::Two]),
+        ];
+        
+        let lows: Vec<LowHand> = table.iter().map(evaluate_deuce_to_seven).collect();
+        for pair in lows.windows(2) {
+            assert!(pair[0] < pair[1], "{} should beat {}", pair[0], pair[1]);
+        }
+        
+        assert_eq!(lows[0].rank, HandRank::HighCard);
+        assert_eq!(lows[6].rank, HandRank::HighCard);
+        assert_eq!(lows[13].rank, HandRank::Straight);
+        assert_eq!(lows[15].rank, HandRank::Flush);
+        assert_eq!(lows[18].rank, HandRank::StraightFlush);
+        assert_eq!(lows[0].to_string(), "7-5-4-3-2 low");
+    }
+    
+    /// Razz (ace-to-five) hands from best to worst, picked from seven cards
+    #[test]
+    fn test_razz_ranking_table() {
+        let seven = |ranks: [Rank; 7]| -> Vec<Card> {
+            let suits = [Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades];
+            ranks.iter().enumerate().map(|(i, &rank)| card(rank, suits[i % 4])).collect()
+        };
+        
+        let table = [
+            seven([Rank::Ace, Rank::Two, Rank::Three, Rank::Four, Rank::Five, Rank::King, Rank::King]),
+            seven([Rank::Six, Rank::Four, Rank::Three, Rank::Two, Rank::Ace, Rank::Queen, Rank::Jack]),
+            seven([Rank::Six, Rank::Five, Rank::Four, Rank::Three, Rank::Two, Rank::Six, Rank::Five]),
+            seven([Rank::Seven, Rank::Six, Rank::Five, Rank::Four, Rank::Two, Rank::Seven, Rank::Six]),
+            seven([Rank::King, Rank::Queen, Rank::Jack, Rank::Ten, Rank::Nine, Rank::King, Rank::Queen]),
+            seven([Rank::Ace, Rank::Ace, Rank::Two, Rank::Two, Rank::Three, Rank::Three, Rank::Four]),
+            seven([Rank::Two, Rank::Two, Rank::Three, Rank::Three, Rank::Four, Rank::Four, Rank::Five]),
+            seven([Rank::Ace, Rank::Ace, Rank::Two, Rank::Two, Rank::Three, Rank::Three, Rank::Ace]),
+        ];
+        
+        let lows: Vec<LowHand> = table.iter().map(|cards| evaluate_razz(cards).unwrap()).collect();
+        for pair in lows.windows(2) {
+            assert!(pair[0] < pair[1], "{} should beat {}", pair[0], pair[1]);
+        }
+        
+        assert_eq!(lows[0].to_string(), "5-4-3-2-A low");
+        assert_eq!(lows[5].rank, HandRank::OnePair);
+        assert_eq!(lows[5].ranks, [1, 4, 3, 2, 0]);
+        assert_eq!(lows[7].rank, HandRank::TwoPair);
+        assert!(evaluate_razz(&table[0][..4]).is_err());
+    }
+    
+    #[test]
+    fn test_stud_low_needs_five_cards_eight_or_lower() {
+        let suits = [Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades];
+        let seven = |ranks: [Rank; 7]| -> Vec<Card> {
+            ranks.iter().enumerate().map(|(i, &rank)| card(rank, suits[i % 4])).collect()
+        };
+        
+        let low = evaluate_stud_low(&seven([
+            Rank::King, Rank::Eight, Rank::Seven, Rank::Four, Rank::Four, Rank::Two, Rank::Ace,
+        ])).unwrap().unwrap();
+        assert_eq!(low.ranks, [8, 7, 4, 2, 1]);
+        
+        let no_low = evaluate_stud_low(&seven([
+            Rank::King, Rank::Nine, Rank::Seven, Rank::Four, Rank::Four, Rank::Two, Rank::Ace,
+        ])).unwrap();
+        assert_eq!(no_low, None);
+    }
+    
+    #[test]
+    fn test_lowball_hand_picks_ranking_by_variant() {
+        let wheel = hand([Rank::Ace, Rank::Two, Rank::Three, Rank::Four, Rank::Five]);
+        
+        let razz = evaluate_lowball_hand(GameVariant::Razz, &wheel).unwrap();
+        let deuce = evaluate_lowball_hand(GameVariant::TripleDraw27, &wheel).unwrap();
+        
+        assert_eq!(razz.ranks, [5, 4, 3, 2, 1]);
+        assert_eq!(deuce.ranks, [14, 5, 4, 3, 2]);
+        assert!(evaluate_lowball_hand(GameVariant::TexasHoldem, &wheel).is_err());
+    }
+}
@@ -1,10 +1,38 @@
 // Cards module - to be implemented with Arcium integration
+pub mod codec;
 pub mod deck;
 pub mod dealing;
 pub mod reveal;
 pub mod evaluator;
+pub mod fast_evaluator;
+pub mod omaha;
+pub mod lowball;
+pub mod stud;
 
 // Export specific types only, not glob
-pub use deck::{Card, EncryptedDeck, generate_standard_deck};
-pub use dealing::{deal_hole_cards, reveal_community_cards};
-pub use evaluator::{EvaluatedHand, evaluate_hand, evaluate_best_hand};
\ No newline at end of file
+pub use codec::{CardSet, parse_cards, format_cards};
+pub use deck::{Card, generate_standard_deck, generate_short_deck, initial_deck_indices};
+pub use dealing::{open_deal_round, continue_dealing, apply_dealt, reveal_community_cards};
+pub use evaluator::{
+    EvaluatedHand,
+    BestHand,
+    evaluate_hand,
+    evaluate_hand_for_variant,
+    evaluate_best_hand,
+    evaluate_best_hand_with_cards,
+    evaluate_best_of_seven,
+    evaluate_player_hand,
+    select_best_five,
+};
+pub use fast_evaluator::{evaluate_seven, evaluate_cards, evaluate_short_deck};
+pub use omaha::evaluate_omaha_hand;
+pub use lowball::{
+    LowHand,
+    evaluate_ace_to_five,
+    evaluate_deuce_to_seven,
+    evaluate_razz,
+    evaluate_stud_low,
+    evaluate_lowball_hand,
+    evaluate_omaha_low,
+};
+pub use stud::{ShowingHand, evaluate_showing, evaluate_showing_low, evaluate_stud_hand, bring_in_key, razz_bring_in_key};
@@ -0,0 +1,141 @@
+use anchor_lang::prelude::*;
+use super::deck::Card;
+use super::evaluator::{BestHand, EvaluatedHand, select_best_five};
+use super::fast_evaluator::evaluate_cards;
+use crate::shared::constants::{COMMUNITY_CARDS, MAX_HOLE_CARDS};
+use crate::shared::PokerError;
+
+/// Evaluate an Omaha hand: exactly two hole cards plus exactly three board cards
+///
+/// Works for 4-, 5- and 6-card Omaha by trying every pair of hole cards with
+/// every three-card combination from the board (up to 15 x 10 candidates).
+pub fn evaluate_omaha_hand(hole_cards: &[Card], community_cards: &[Card; 5]) -> Result<BestHand> {
+    require!(
+        hole_cards.len() >= 4 && hole_cards.len() <= MAX_HOLE_CARDS,
+        PokerError::InvalidCardIndex
+    );
+    
+    let mut best: Option<(EvaluatedHand, [Card; 5])> = None;
+    
+    for h1 in 0..hole_cards.len() {
+        for h2 in (h1 + 1)..hole_cards.len() {
+            for b1 in 0..COMMUNITY_CARDS {
+                for b2 in (b1 + 1)..COMMUNITY_CARDS {
+                    for b3 in (b2 + 1)..COMMUNITY_CARDS {
+                        let candidate = [
+                            hole_cards[h1],
+                            hole_cards[h2],
+                            community_cards[b1],
+                            community_cards[b2],
+                            community_cards[b3],
+                        ];
+                        let hand = evaluate_cards(&candidate)?;
+                        
+                        if best.is_none_or(|(current, _)| hand > current) {
+                            best = Some((hand, candidate));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    
+    let (hand, cards) = best.ok_or(PokerError::InvalidCardIndex)?;
+    
+    Ok(BestHand {
+        hand,
+        cards: select_best_five(&cards, &hand)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{HandRank, Rank, Suit};
+    
+    fn card(rank: Rank, suit: Suit) -> Card {
+        Card::new(suit, rank)
+    }
+    
+    fn board() -> [Card; 5] {
+        [
+            card(Rank::Ace, Suit::Hearts),
+            card(Rank::King, Suit::Hearts),
+            card(Rank::Queen, Suit::Hearts),
+            card(Rank::Seven, Suit::Clubs),
+            card(Rank::Two, Suit::Diamonds),
+        ]
+    }
+    
+    #[test]
+    fn test_single_suited_hole_card_is_not_a_flush() {
+        // Hold'em would call this a flush (four hearts on board + one in hand)
+        let mut board = board();
+        board[3] = card(Rank::Seven, Suit::Hearts);
+        let hole = [
+            card(Rank::Jack, Suit::Hearts),
+            card(Rank::Three, Suit::Spades),
+            card(Rank::Four, Suit::Clubs),
+            card(Rank::Eight, Suit::Diamonds),
+        ];
+        
+        let best = evaluate_omaha_hand(&hole, &board).unwrap();
+        assert_ne!(best.hand.rank, HandRank::Flush);
+        assert_ne!(best.hand.rank, HandRank::Straight);
+    }
+    
+    #[test]
+    fn test_uses_exactly_two_hole_cards() {
+        let hole = [
+            card(Rank::Jack, Suit::Hearts),
+            card(Rank::Ten, Suit::Hearts),
+            card(Rank::Ace, Suit::Spades),
+            card(Rank::Ace, Suit::Clubs),
+        ];
+        let board = board();
+        
+        let best = evaluate_omaha_hand(&hole, &board).unwrap();
+        assert_eq!(best.hand.rank, HandRank::RoyalFlush);
+        
+        let from_hole = best.cards.iter().filter(|c| hole.contains(c)).count();
+        let from_board = best.cards.iter().filter(|c| board.contains(c)).count();
+        assert_eq!((from_hole, from_board), (2, 3));
+    }
+    
+    #[test]
+    fn test_four_to_board_paired_hand_is_trips_not_quads() {
+        // Four kings in hand can only contribute two
+        let hole = [
+            card(Rank::King, Suit::Spades),
+            card(Rank::King, Suit::Clubs),
+            card(Rank::King, Suit::Diamonds),
+            card(Rank::Five, Suit::Diamonds),
+        ];
+        
+        let best = evaluate_omaha_hand(&hole, &board()).unwrap();
+        assert_eq!(best.hand.rank, HandRank::ThreeOfAKind);
+        assert_eq!(best.hand.primary_value, 13);
+    }
+    
+    #[test]
+    fn test_six_card_omaha() {
+        let hole = [
+            card(Rank::Two, Suit::Spades),
+            card(Rank::Three, Suit::Spades),
+            card(Rank::Seven, Suit::Hearts),
+            card(Rank::Seven, Suit::Spades),
+            card(Rank::Nine, Suit::Clubs),
+            card(Rank::Ten, Suit::Diamonds),
+        ];
+        
+        let best = evaluate_omaha_hand(&hole, &board()).unwrap();
+        assert_eq!(best.hand.rank, HandRank::ThreeOfAKind);
+        assert_eq!(best.hand.primary_value, 7);
+    }
+    
+    #[test]
+    fn test_rejects_holdem_hand() {
+        let hole = [card(Rank::Two, Suit::Spades), card(Rank::Three, Suit::Spades)];
+        assert!(evaluate_omaha_hand(&hole, &board()).is_err());
+    }
+}
@@ -0,0 +1,178 @@
+use anchor_lang::prelude::*;
+use super::deck::Card;
+use super::evaluator::{BestHand, evaluate_best_of_seven};
+use super::lowball::ace_low_value;
+use crate::shared::constants::{STUD_DOWN_CARDS, STUD_UP_CARDS};
+use crate::shared::PokerError;
+use crate::types::Suit;
+
+/// Strength of a player's face-up cards, used to pick who acts first
+///
+/// Only pairs, trips and quads count (straights and flushes on board do not).
+/// Groups are ordered by size then rank, so comparing two values compares the
+/// boards the way a stud dealer would.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ShowingHand {
+    /// (count, rank) per distinct rank, largest group first, zero padded
+    pub groups: [(u8, u8); STUD_UP_CARDS],
+}
+
+/// Rank the cards a stud player is showing (1 to 4 upcards)
+pub fn evaluate_showing(up_cards: &[Card]) -> Result<ShowingHand> {
+    group_showing(up_cards, |card| card.rank as u8)
+}
+
+/// Rank the cards a Razz player is showing with aces low
+///
+/// The best Razz board is the *lowest* value: unpaired boards beat paired
+/// ones and a lower top card beats a higher one.
+pub fn evaluate_showing_low(up_cards: &[Card]) -> Result<ShowingHand> {
+    group_showing(up_cards, |card| ace_low_value(card.rank))
+}
+
+/// Group upcards by count then rank, using `value` to rank each card
+fn group_showing(up_cards: &[Card], value: impl Fn(&Card) -> u8) -> Result<ShowingHand> {
+    require!(
+        !up_cards.is_empty() && up_cards.len() <= STUD_UP_CARDS,
+        PokerError::InvalidCardIndex
+    );
+
+    let mut counts = [0u8; 15];
+    for card in up_cards {
+        counts[value(card) as usize] += 1;
+    }
+
+    let mut groups = [(0u8, 0u8); STUD_UP_CARDS];
+    let mut len = 0;
+    for rank in (1..=14u8).rev() {
+        if counts[rank as usize] > 0 {
+            groups[len] = (counts[rank as usize], rank);
+            len += 1;
+        }
+    }
+
+    // Bigger groups first; ranks are already descending within a size
+    groups[..len].sort_by(|a, b| b.0.cmp(&a.0));
+
+    Ok(ShowingHand { groups })
+}
+
+/// Suit order used to break bring-in ties (clubs lowest, spades highest)
+fn suit_order(suit: Suit) -> u8 {
+    match suit {
+        Suit::Clubs => 0,
+        Suit::Diamonds => 1,
+        Suit::Hearts => 2,
+        Suit::Spades => 3,
+    }
+}
+
+/// Ordering key for the bring-in: the lowest upcard by rank, then by suit
+pub fn bring_in_key(card: &Card) -> (u8, u8) {
+    (card.rank as u8, suit_order(card.suit))
+}
+
+/// Ordering key for the Razz bring-in: the highest upcard with aces low,
+/// then by suit
+pub fn razz_bring_in_key(card: &Card) -> (u8, u8) {
+    (ace_low_value(card.rank), suit_order(card.suit))
+}
+
+/// Evaluate a stud hand: best five of the three down and four up cards
+pub fn evaluate_stud_hand(down_cards: &[Card], up_cards: &[Card]) -> Result<BestHand> {
+    require!(
+        down_cards.len() == STUD_DOWN_CARDS && up_cards.len() == STUD_UP_CARDS,
+        PokerError::InvalidCardIndex
+    );
+
+    let all_cards = [
+        down_cards[0],
+        down_cards[1],
+        up_cards[0],
+        up_cards[1],
+        up_cards[2],
+        up_cards[3],
+        down_cards[2],
+    ];
+
+    evaluate_best_of_seven(&all_cards)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{HandRank, Rank};
+
+    fn card(rank: Rank, suit: Suit) -> Card {
+        Card::new(suit, rank)
+    }
+
+    #[test]
+    fn test_showing_hand_order() {
+        let pair = evaluate_showing(&[card(Rank::Four, Suit::Clubs), card(Rank::Four, Suit::Hearts)]).unwrap();
+        let ace_king = evaluate_showing(&[card(Rank::Ace, Suit::Clubs), card(Rank::King, Suit::Hearts)]).unwrap();
+        let ace_queen = evaluate_showing(&[card(Rank::Queen, Suit::Spades), card(Rank::Ace, Suit::Hearts)]).unwrap();
+        let trips = evaluate_showing(&[
+            card(Rank::Two, Suit::Clubs),
+            card(Rank::Two, Suit::Hearts),
+            card(Rank::Two, Suit::Spades),
+        ]).unwrap();
+
+        assert!(pair > ace_king);
+        assert!(ace_king > ace_queen);
+        assert!(trips > pair);
+
+        // Four to a flush on board is still just high cards
+        let suited = evaluate_showing(&[
+            card(Rank::Nine, Suit::Hearts),
+            card(Rank::Seven, Suit::Hearts),
+            card(Rank::Five, Suit::Hearts),
+            card(Rank::Three, Suit::Hearts),
+        ]).unwrap();
+        assert!(suited < pair);
+    }
+
+    #[test]
+    fn test_bring_in_breaks_ties_by_suit() {
+        let clubs = card(Rank::Two, Suit::Clubs);
+        let spades = card(Rank::Two, Suit::Spades);
+        let three = card(Rank::Three, Suit::Clubs);
+
+        assert!(bring_in_key(&clubs) < bring_in_key(&spades));
+        assert!(bring_in_key(&spades) < bring_in_key(&three));
+    }
+
+    #[test]
+    fn test_razz_showing_and_bring_in_play_aces_low() {
+        let ace_two = evaluate_showing_low(&[card(Rank::Ace, Suit::Clubs), card(Rank::Two, Suit::Hearts)]).unwrap();
+        let seven_four = evaluate_showing_low(&[card(Rank::Seven, Suit::Clubs), card(Rank::Four, Suit::Hearts)]).unwrap();
+        let pair = evaluate_showing_low(&[card(Rank::Two, Suit::Clubs), card(Rank::Two, Suit::Hearts)]).unwrap();
+
+        assert!(ace_two < seven_four);
+        assert!(seven_four < pair);
+
+        let ace = card(Rank::Ace, Suit::Spades);
+        let king = card(Rank::King, Suit::Clubs);
+        assert!(razz_bring_in_key(&king) > razz_bring_in_key(&ace));
+    }
+
+    #[test]
+    fn test_stud_hand_uses_all_seven_cards() {
+        let down = [
+            card(Rank::Ace, Suit::Spades),
+            card(Rank::Ace, Suit::Hearts),
+            card(Rank::King, Suit::Diamonds),
+        ];
+        let up = [
+            card(Rank::Ace, Suit::Clubs),
+            card(Rank::King, Suit::Spades),
+            card(Rank::Seven, Suit::Hearts),
+            card(Rank::Two, Suit::Clubs),
+        ];
+
+        let best = evaluate_stud_hand(&down, &up).unwrap();
+        assert_eq!(best.hand.rank, HandRank::FullHouse);
+        assert_eq!(best.description(), "Full House, Aces full of Kings");
+        assert!(evaluate_stud_hand(&down[..2], &up).is_err());
+    }
+}
@@ -0,0 +1,346 @@
+use anchor_lang::prelude::*;
+use super::rng::EquityRng;
+use crate::cards::codec::CardSet;
+use crate::cards::deck::Card;
+use crate::cards::evaluator::{evaluate_best_hand, EvaluatedHand};
+use crate::shared::constants::{COMMUNITY_CARDS, MAX_PLAYERS};
+use crate::shared::PokerError;
+
+/// How to run an equity calculation
+#[derive(Clone, Copy, Debug)]
+pub struct EquityConfig {
+    /// Run-outs sampled when the board is too early to enumerate (preflop, flop)
+    pub iterations: u32,
+
+    /// Seed for the Monte Carlo sampler; the same seed gives the same result
+    pub seed: u64,
+}
+
+impl Default for EquityConfig {
+    fn default() -> Self {
+        Self {
+            iterations: 20_000,
+            seed: 0x5EED_CAFE_F00D_D00D,
+        }
+    }
+}
+
+/// One player's results over every run-out considered
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PlayerEquity {
+    /// Run-outs won outright
+    pub wins: u64,
+
+    /// Run-outs split with at least one other player
+    pub ties: u64,
+
+    /// Pot share won across all run-outs (a win counts 1, a two-way split 1/2)
+    pub pot_share: f64,
+}
+
+/// Equity for every player in a hand
+#[derive(Clone, Debug, PartialEq)]
+pub struct EquityResult {
+    /// Per player, in the order the hole cards were given
+    pub players: Vec<PlayerEquity>,
+
+    /// Run-outs evaluated
+    pub trials: u64,
+
+    /// Every possible run-out was enumerated (turn or river)
+    pub exhaustive: bool,
+}
+
+impl EquityResult {
+    /// Fraction of run-outs the player wins outright
+    pub fn win(&self, player: usize) -> f64 {
+        self.players[player].wins as f64 / self.trials as f64
+    }
+
+    /// Fraction of run-outs the player splits
+    pub fn tie(&self, player: usize) -> f64 {
+        self.players[player].ties as f64 / self.trials as f64
+    }
+
+    /// Expected share of the pot (win plus split shares)
+    pub fn equity(&self, player: usize) -> f64 {
+        self.players[player].pot_share / self.trials as f64
+    }
+}
+
+/// Cards that put a player currently behind into the lead (or a tie for it)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PlayerOuts {
+    pub player: usize,
+    pub outs: CardSet,
+}
+
+/// Check the inputs and return the cards still left to deal from
+fn remaining_deck(
+    hole_cards: &[[Card; 2]],
+    board: &[Card],
+    dead_cards: &[Card],
+) -> Result<Vec<Card>> {
+    require!(
+        hole_cards.len() >= 2 && hole_cards.len() <= MAX_PLAYERS,
+        PokerError::InvalidGameConfig
+    );
+    require!(
+        matches!(board.len(), 0 | 3 | 4 | 5),
+        PokerError::InvalidCardIndex
+    );
+
+    let known: Vec<Card> = hole_cards
+        .iter()
+        .flatten()
+        .chain(board)
+        .chain(dead_cards)
+        .copied()
+        .collect();
+    let known = CardSet::from_unique(&known)?;
+
+    Ok(CardSet::FULL.difference(known).iter().collect())
+}
+
+/// Score one complete board, crediting the winner(s)
+fn score_board(hole_cards: &[[Card; 2]], board: &[Card; 5], players: &mut [PlayerEquity]) -> Result<()> {
+    let hands = hole_cards
+        .iter()
+        .map(|hole| evaluate_best_hand(hole, board))
+        .collect::<Result<Vec<EvaluatedHand>>>()?;
+
+    let best = hands.iter().max().copied();
+    let winners = hands.iter().filter(|hand| Some(**hand) == best).count();
+
+    for (player, hand) in players.iter_mut().zip(&hands) {
+        if Some(*hand) != best {
+            continue;
+        }
+        if winners == 1 {
+            player.wins += 1;
+        } else {
+            player.ties += 1;
+        }
+        player.pot_share += 1.0 / winners as f64;
+    }
+
+    Ok(())
+}
+
+/// Hold'em equity for each player's known hole cards
+///
+/// With four or five board cards every run-out is enumerated; preflop and on
+/// the flop `config.iterations` run-outs are sampled with a seeded RNG, so
+/// results are reproducible. Dead cards (folded or burned cards someone has
+/// seen) are removed from the deck.
+pub fn calculate_equity(
+    hole_cards: &[[Card; 2]],
+    board: &[Card],
+    dead_cards: &[Card],
+    config: EquityConfig,
+) -> Result<EquityResult> {
+    let mut deck = remaining_deck(hole_cards, board, dead_cards)?;
+    let to_come = COMMUNITY_CARDS - board.len();
+    // Enough dead cards can leave too few to finish the board
+    require!(deck.len() >= to_come, PokerError::DeckExhausted);
+
+    let mut full_board = [board.first().copied().unwrap_or_else(|| deck[0]); COMMUNITY_CARDS];
+    full_board[..board.len()].copy_from_slice(board);
+
+    let mut players = vec![PlayerEquity::default(); hole_cards.len()];
+    let mut trials = 0u64;
+    let exhaustive = to_come <= 1;
+
+    if exhaustive {
+        if to_come == 0 {
+            score_board(hole_cards, &full_board, &mut players)?;
+            trials = 1;
+        } else {
+            for &card in &deck {
+                full_board[COMMUNITY_CARDS - 1] = card;
+                score_board(hole_cards, &full_board, &mut players)?;
+                trials += 1;
+            }
+        }
+    } else {
+        require!(config.iterations > 0, PokerError::InvalidGameConfig);
+        let mut rng = EquityRng::new(config.seed);
+
+        for _ in 0..config.iterations {
+            // Partial Fisher-Yates: the first `to_come` cards become the run-out
+            for i in 0..to_come {
+                let j = i + rng.below((deck.len() - i) as u64) as usize;
+                deck.swap(i, j);
+                full_board[board.len() + i] = deck[i];
+            }
+            score_board(hole_cards, &full_board, &mut players)?;
+            trials += 1;
+        }
+    }
+
+    Ok(EquityResult {
+        players,
+        trials,
+        exhaustive,
+    })
+}
+
+/// Outs for every player not currently ahead, on the flop or turn
+///
+/// A card is an out for a player when dealing it next leaves them with the
+/// best hand (alone or tied) while they are behind now.
+pub fn calculate_outs(
+    hole_cards: &[[Card; 2]],
+    board: &[Card],
+    dead_cards: &[Card],
+) -> Result<Vec<PlayerOuts>> {
+    require!(
+        board.len() == 3 || board.len() == 4,
+        PokerError::InvalidCardIndex
+    );
+    let deck = remaining_deck(hole_cards, board, dead_cards)?;
+
+    // Current standing: best five of the cards out so far
+    let current = hole_cards
+        .iter()
+        .map(|hole| {
+            let cards: Vec<Card> = hole.iter().chain(board).copied().collect();
+            crate::cards::evaluate_cards(&cards)
+        })
+        .collect::<Result<Vec<EvaluatedHand>>>()?;
+    let leader = current.iter().max().copied();
+
+    let mut outs: Vec<PlayerOuts> = (0..hole_cards.len())
+        .filter(|&player| Some(current[player]) != leader)
+        .map(|player| PlayerOuts { player, outs: CardSet::empty() })
+        .collect();
+
+    for &card in &deck {
+        let next = hole_cards
+            .iter()
+            .map(|hole| {
+                let cards: Vec<Card> = hole.iter().chain(board).chain([&card]).copied().collect();
+                crate::cards::evaluate_cards(&cards)
+            })
+            .collect::<Result<Vec<EvaluatedHand>>>()?;
+        let best = next.iter().max().copied();
+
+        for entry in outs.iter_mut() {
+            if Some(next[entry.player]) == best {
+                entry.outs.insert(card);
+            }
+        }
+    }
+
+    Ok(outs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::codec::parse_cards;
+
+    fn hole(s: &str) -> [Card; 2] {
+        parse_cards(s).unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn test_river_is_decided() {
+        let board = parse_cards("Ah Kd 7c 4s 2h").unwrap();
+        let result = calculate_equity(&[hole("As Ad"), hole("Kh Ks")], &board, &[], EquityConfig::default()).unwrap();
+
+        assert!(result.exhaustive);
+        assert_eq!(result.trials, 1);
+        assert_eq!(result.win(0), 1.0);
+        assert_eq!(result.equity(1), 0.0);
+    }
+
+    #[test]
+    fn test_turn_enumerates_every_river() {
+        // Set under set: only the last king saves the underdog
+        let board = parse_cards("Ah Kd 7c 4s").unwrap();
+        let result = calculate_equity(&[hole("As Ad"), hole("Kh Ks")], &board, &[], EquityConfig::default()).unwrap();
+
+        assert!(result.exhaustive);
+        assert_eq!(result.trials, 44);
+        assert_eq!(result.players[1].wins, 1);
+        assert_eq!(result.players[0].wins, 43);
+
+        // Killing the case king leaves no outs at all
+        let dead = parse_cards("Kc").unwrap();
+        let result = calculate_equity(&[hole("As Ad"), hole("Kh Ks")], &board, &dead, EquityConfig::default()).unwrap();
+        assert_eq!(result.trials, 43);
+        assert_eq!(result.players[1].wins, 0);
+    }
+
+    #[test]
+    fn test_too_many_dead_cards_is_an_error() {
+        let hands = [hole("As Ad"), hole("Kh Ks")];
+        let board = parse_cards("Ah Kd 7c").unwrap();
+        let known = CardSet::from_unique(&parse_cards("As Ad Kh Ks Ah Kd 7c").unwrap()).unwrap();
+
+        // One card left for a turn and a river
+        let dead: Vec<Card> = CardSet::FULL.difference(known).iter().skip(1).collect();
+        let result = calculate_equity(&hands, &board, &dead, EquityConfig::default());
+        assert!(result.is_err());
+
+        // Every card dead on a complete board is fine
+        let board = parse_cards("Ah Kd 7c 4s 2h").unwrap();
+        let known = CardSet::from_unique(&parse_cards("As Ad Kh Ks Ah Kd 7c 4s 2h").unwrap()).unwrap();
+        let dead: Vec<Card> = CardSet::FULL.difference(known).iter().collect();
+        assert_eq!(calculate_equity(&hands, &board, &dead, EquityConfig::default()).unwrap().trials, 1);
+    }
+
+    #[test]
+    fn test_split_pot_shares() {
+        // Broadway on board plays for both players
+        let board = parse_cards("Ah Kd Qc Js Th").unwrap();
+        let result = calculate_equity(&[hole("2c 3d"), hole("4c 5d")], &board, &[], EquityConfig::default()).unwrap();
+
+        assert_eq!(result.tie(0), 1.0);
+        assert_eq!(result.equity(0), 0.5);
+        assert_eq!(result.equity(1), 0.5);
+    }
+
+    #[test]
+    fn test_monte_carlo_is_seeded_and_close_to_known_odds() {
+        let hands = [hole("As Ah"), hole("Kc Kd")];
+        let config = EquityConfig { iterations: 20_000, seed: 42 };
+
+        let first = calculate_equity(&hands, &[], &[], config).unwrap();
+        let second = calculate_equity(&hands, &[], &[], config).unwrap();
+        assert_eq!(first, second);
+        assert!(!first.exhaustive);
+
+        // AA vs KK preflop is roughly 82% / 18%
+        let aces = first.equity(0);
+        assert!((0.79..0.85).contains(&aces), "aces equity {}", aces);
+
+        let total: f64 = (0..2).map(|p| first.equity(p)).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_flush_draw_outs() {
+        // Four hearts on the turn against top pair: the nine hearts left win
+        let board = parse_cards("Kh 8h 3c 2d").unwrap();
+
+        let outs = calculate_outs(&[hole("9h Th"), hole("Ks Qd")], &board, &[]).unwrap();
+        assert_eq!(outs.len(), 1);
+        assert_eq!(outs[0].player, 0);
+        assert_eq!(outs[0].outs.len(), 9);
+
+        // Dead hearts are not outs
+        let dead = parse_cards("Ah 4h").unwrap();
+        let outs = calculate_outs(&[hole("9h Th"), hole("Ks Qd")], &board, &dead).unwrap();
+        assert_eq!(outs[0].outs.len(), 7);
+
+        // Only players behind are listed
+        let outs = calculate_outs(&[hole("8c 8d"), hole("Ks Qd")], &board, &[]).unwrap();
+        assert_eq!(outs.len(), 1);
+        assert_eq!(outs[0].player, 1);
+
+        assert!(calculate_outs(&[hole("8c 8d"), hole("Ks Qd")], &board[..2], &[]).is_err());
+        assert!(calculate_equity(&[hole("Ah 5h"), hole("Ah Qd")], &board, &[], EquityConfig::default()).is_err());
+    }
+}
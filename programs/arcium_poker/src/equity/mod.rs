@@ -0,0 +1,18 @@
+// Equity module - off-chain odds for clients, bots and all-in features
+//
+// Built on the same evaluator the program uses at showdown, so equities and
+// outs always agree with how the chain will settle the hand. Only compiled
+// with the `no-entrypoint` feature (library builds), never into the program.
+pub mod rng;
+pub mod calculator;
+
+// Export specific items
+pub use rng::EquityRng;
+pub use calculator::{
+    EquityConfig,
+    EquityResult,
+    PlayerEquity,
+    PlayerOuts,
+    calculate_equity,
+    calculate_outs,
+};
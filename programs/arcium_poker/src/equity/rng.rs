@@ -0,0 +1,32 @@
+/// Seeded SplitMix64 generator for reproducible Monte Carlo runs
+///
+/// Not cryptographic: it only samples run-outs for equity estimates.
+#[derive(Clone, Debug)]
+pub struct EquityRng {
+    state: u64,
+}
+
+impl EquityRng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+    
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+    
+    /// Uniform value in `0..bound` (bound > 0), without modulo bias
+    pub fn below(&mut self, bound: u64) -> u64 {
+        let zone = u64::MAX - (u64::MAX % bound);
+        loop {
+            let value = self.next_u64();
+            if value < zone {
+                return value % bound;
+            }
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use super::state::Game;
+use super::events::PendingComputationAborted;
+use crate::player::state::{load_player_states, store_player_states, PlayerState};
+use crate::types::GameStage;
+use crate::shared::{constants::*, PokerError};
+
+/// Void the hand when its MPC computation never called back
+///
+/// Permissionless once the `ArciumConfig` timeout has passed since the
+/// computation was queued. Every bet of the hand, blinds and antes included,
+/// goes back to its player and the table returns to `Waiting`, where players
+/// commit fresh entropy and the authority can start the hand again.
+///
+/// Remaining accounts: every seat's PlayerState, in seat order.
+pub fn handler(ctx: Context<crate::AbortPendingComputation>) -> Result<()> {
+    let game_key = ctx.accounts.game.key();
+    let timeout = ctx.accounts.arcium_config.timeout;
+    let game = &mut ctx.accounts.game;
+    
+    require!(game.has_pending_computation(), PokerError::NoPendingComputation);
+    
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now >= game.computation_queued_at.saturating_add(timeout),
+        PokerError::ComputationNotTimedOut
+    );
+    
+    let mut player_states = load_player_states(game_key, game.player_count, ctx.remaining_accounts)?;
+    
+    let event = PendingComputationAborted {
+        game_id: game.game_id,
+        stage: game.stage,
+        computation_offset: game.pending_computation,
+        comp_def_offset: game.pending_comp_def_offset,
+        queued_at: game.computation_queued_at,
+        aborted_at: now,
+        refunded: void_hand(game, &mut player_states)?,
+    };
+    
+    store_player_states(ctx.remaining_accounts, &player_states)?;
+    
+    msg!(
+        "[ARCIUM MPC] Computation {} queued at {} timed out; hand voided, {} refunded",
+        event.computation_offset,
+        event.queued_at,
+        event.refunded
+    );
+    
+    emit!(event);
+    
+    Ok(())
+}
+
+/// Return every bet of the hand and reset the table for a new start
+///
+/// Returns the chips refunded, which must account for the whole pot.
+fn void_hand(game: &mut Game, player_states: &mut [PlayerState]) -> Result<u64> {
+    let mut refunded = 0u64;
+    for player_state in player_states.iter_mut() {
+        let bet = player_state.total_bet_this_hand;
+        player_state.chip_stack = player_state
+            .chip_stack
+            .checked_add(bet)
+            .ok_or(PokerError::InvalidBetAmount)?;
+        refunded += bet;
+        player_state.reset_for_new_hand();
+        player_state.clear_entropy();
+    }
+    require!(refunded == game.pot, PokerError::InvalidBetAmount);
+    
+    game.clear_pending_computation();
+    game.stage = GameStage::Waiting;
+    game.pot = 0;
+    game.current_bet = 0;
+    game.bets_this_round = 0;
+    game.players_acted = [false; MAX_PLAYERS];
+    game.community_cards = [0; COMMUNITY_CARDS];
+    game.community_cards_revealed = 0;
+    game.community_reveal_pending = 0;
+    game.showdown_pending = false;
+    game.shown_cards = [[NO_CARD; MAX_HOLE_CARDS]; MAX_PLAYERS];
+    game.deck_initialized = false;
+    super::stud::reset_stud_hand(game);
+    super::draw::reset_draw_hand(game);
+    
+    for seat in 0..game.player_count as usize {
+        game.active_players[seat] = game.players[seat] != Pubkey::default();
+    }
+    
+    Ok(refunded)
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::arcium::backend::{mxe_accounts, MxeQueue};
+use crate::cards::dealing::reveal_community_cards;
+use super::flow::advance_game_stage;
+
+/// Advance the hand to its next stage, then reveal the board cards it opens
+///
+/// A flop, turn or river that is still owed its cards (for example after
+/// betting closed the round) is revealed instead of advancing again. The MXE
+/// accounts are those for `reveal_community` at the game's next computation
+/// offset; on Arcium betting reopens with `handle_community_callback`.
+pub fn handler(ctx: Context<crate::AdvanceStage>) -> Result<()> {
+    let mxe = MxeQueue {
+        accounts: mxe_accounts!(ctx.accounts, signer),
+        arcium_config: &ctx.accounts.arcium_config,
+        circuit_config: &ctx.accounts.circuit_config,
+        callback_accounts: vec![ctx.accounts.game.key(), ctx.accounts.deck.key()],
+    };
+    let game = &mut ctx.accounts.game;
+    
+    if game.community_reveal_pending == 0 {
+        advance_game_stage(game)?;
+    }
+    
+    if game.community_reveal_pending > 0 {
+        reveal_community_cards(game, &ctx.accounts.deck, Some(&mxe))?;
+    }
+    
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use super::state::Game;
+use crate::types::{GameStage, GameVariant, BettingStructure};
+use crate::shared::PokerError;
+
+/// Choose the variant, betting structure and forced bets before the game starts
+///
+/// Stud tables use the small blind as the bring-in and the big blind as the
+/// completion/small bet; `ante` is posted by every player each hand.
+pub fn handler(
+    ctx: Context<crate::ConfigureGame>,
+    variant: GameVariant,
+    betting_structure: Option<BettingStructure>,
+    button_ante: Option<u64>,
+    ante: Option<u64>,
+) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+    
+    require!(
+        game.stage == GameStage::Waiting,
+        PokerError::GameAlreadyStarted
+    );
+    
+    validate_variant(game, variant)?;
+    
+    // A button ante replaces the blinds; a minimum buy-in must cover it
+    let button_ante = button_ante.unwrap_or(0);
+    require!(
+        button_ante <= game.min_buy_in,
+        PokerError::InvalidGameConfig
+    );
+    
+    let ante = ante.unwrap_or(0);
+    require!(ante <= game.min_buy_in, PokerError::InvalidGameConfig);
+    
+    // A fixed variant replaces any mixed-game rotation
+    game.rotation_len = 0;
+    game.variant = variant;
+    game.betting_structure = betting_structure.unwrap_or(variant.default_betting_structure());
+    game.button_ante = button_ante;
+    game.ante = ante;
+    
+    msg!(
+        "Game {} configured: {:?} ({:?}), button ante {}, ante {}",
+        game.game_id,
+        game.variant,
+        game.betting_structure,
+        game.button_ante,
+        game.ante
+    );
+    
+    Ok(())
+}
+
+/// Check a variant can be dealt at this table
+pub fn validate_variant(game: &Game, variant: GameVariant) -> Result<()> {
+    // Every seat's hole cards plus the board and burns must fit in one deck
+    let cards_needed = variant.cards_needed(game.max_players as usize);
+    require!(cards_needed <= variant.deck_size(), PokerError::InvalidGameConfig);
+    
+    // Private showdowns are only evaluated for Hold'em
+    require!(
+        !game.private_showdown || variant.supports_private_showdown(),
+        PokerError::InvalidGameConfig
+    );
+    Ok(())
+}
+
+/// Settle showdowns inside MPC so losing hands are never revealed
+///
+/// With `show_winning_hand` the winning hand's score is revealed alongside
+/// the winners; otherwise only the winning seats are. Every game the table
+/// plays, including each game of a rotation, must support it.
+pub fn showdown_handler(
+    ctx: Context<crate::ConfigureGame>,
+    private_showdown: bool,
+    show_winning_hand: bool,
+) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+    
+    require!(
+        game.stage == GameStage::Waiting,
+        PokerError::GameAlreadyStarted
+    );
+    
+    game.private_showdown = private_showdown;
+    game.show_winning_hand = private_showdown && show_winning_hand;
+    
+    validate_variant(game, game.variant)?;
+    for entry in &game.rotation[..game.rotation_len as usize] {
+        validate_variant(game, entry.variant)?;
+    }
+    
+    msg!(
+        "Game {} private showdown: {}, show winning hand: {}",
+        game.game_id,
+        game.private_showdown,
+        game.show_winning_hand
+    );
+    
+    Ok(())
+}
+
+// ConfigureGame struct lives in lib.rs at crate root (required by Anchor)
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::arcium::backend::{mxe_accounts, MxeQueue};
+use crate::cards::dealing::continue_dealing;
+use crate::player::state::{load_player_states, store_player_states};
+
+/// Deal the cards the table owes: hole cards once the shuffle is stored,
+/// stud upcards and seventh-street down cards after each betting round
+///
+/// Permissionless. The MXE accounts are those for the next seat's circuit
+/// (`deal_hole_cards`, `deal_up_card` or `deal_card`) at the game's next
+/// computation offset. On Arcium each call deals one seat and the next call
+/// waits for its callback; the mock deals the rest of the round at once.
+///
+/// Remaining accounts: every seat's PlayerState, in seat order.
+pub fn handler(ctx: Context<crate::DealCards>) -> Result<()> {
+    let game_key = ctx.accounts.game.key();
+    let player_count = ctx.accounts.game.player_count;
+    let mut player_states = load_player_states(game_key, player_count, ctx.remaining_accounts)?;
+    
+    // The deal callback writes the game and the seats' PlayerStates
+    let mut callback_accounts = vec![game_key, ctx.accounts.deck.key()];
+    callback_accounts.extend(
+        ctx.remaining_accounts
+            .iter()
+            .take(player_count as usize)
+            .map(|account| account.key()),
+    );
+    let mxe = MxeQueue {
+        accounts: mxe_accounts!(ctx.accounts, payer),
+        arcium_config: &ctx.accounts.arcium_config,
+        circuit_config: &ctx.accounts.circuit_config,
+        callback_accounts,
+    };
+    
+    continue_dealing(
+        &mut ctx.accounts.game,
+        &ctx.accounts.deck,
+        Some(&mxe),
+        &mut player_states,
+    )?;
+    
+    store_player_states(ctx.remaining_accounts, &player_states)
+}
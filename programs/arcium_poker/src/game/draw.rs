@@ -0,0 +1,323 @@
+use anchor_lang::prelude::*;
+use super::state::Game;
+use super::flow::reset_betting_round;
+use crate::arcium::backend::{mxe_accounts, MxeQueue};
+use crate::arcium::comp_defs::Circuit;
+use crate::arcium::mpc_draw::{
+    generate_draw_nonce, mpc_draw_cards, mpc_recycle_discards, Drawn, MxeDrawParams, MxeRecycleParams,
+};
+use crate::cards::deck::Deck;
+use crate::player::state::PlayerState;
+use crate::types::GameStage;
+use crate::shared::{constants::*, PokerError};
+
+/// After a betting round in a draw game: open the next draw phase, or go to
+/// showdown once every draw has been taken
+pub fn advance_draw_round(game: &mut Game) -> Result<()> {
+    require!(
+        game.stage == GameStage::PreFlop && !game.drawing,
+        PokerError::InvalidGameStage
+    );
+    
+    if game.draws_completed >= game.variant.draw_rounds() {
+        msg!("[DRAW] Final betting round complete, advancing to Showdown");
+        game.stage = GameStage::Showdown;
+        return Ok(());
+    }
+    
+    game.drawing = true;
+    game.players_drawn = [false; MAX_PLAYERS];
+    game.current_player_index = next_to_draw(game, game.dealer_position)
+        .ok_or(PokerError::NotEnoughPlayers)?;
+    game.last_action_at = Clock::get()?.unix_timestamp;
+    
+    msg!(
+        "[DRAW] Draw {} of {} open. First to draw: seat {}",
+        game.draws_completed + 1,
+        game.variant.draw_rounds(),
+        game.current_player_index
+    );
+    
+    Ok(())
+}
+
+/// Next active seat after `seat` that has not drawn yet this phase
+fn next_to_draw(game: &Game, seat: u8) -> Option<u8> {
+    (1..=game.player_count)
+        .map(|offset| (seat + offset) % game.player_count)
+        .find(|&next| game.active_players[next as usize] && !game.players_drawn[next as usize])
+}
+
+/// Discard 0-5 encrypted hole cards and receive replacements from the deck
+///
+/// `discard_slots` are positions in the player's hand. Discards are swapped
+/// into the dealt part of the encrypted deck and are never revealed. When
+/// the stock is short, the discards are first reshuffled back into it with
+/// `recycle_entropy`, fresh entropy the player encrypted to the MXE.
+///
+/// On Arcium a short stock queues `recycle_discards` and the player draws
+/// again once `handle_recycle_callback` lands; the draw itself queues
+/// `draw_cards` and the turn passes in `handle_draw_callback`. The MXE
+/// accounts are those for the circuit queued at the game's next
+/// computation offset.
+pub fn handler(
+    ctx: Context<crate::DrawCards>,
+    discard_slots: Vec<u8>,
+    recycle_entropy: [u8; 32],
+) -> Result<()> {
+    let mxe = MxeQueue {
+        accounts: mxe_accounts!(ctx.accounts, player),
+        arcium_config: &ctx.accounts.arcium_config,
+        circuit_config: &ctx.accounts.circuit_config,
+        callback_accounts: vec![
+            ctx.accounts.game.key(),
+            ctx.accounts.deck.key(),
+            ctx.accounts.player_state.key(),
+        ],
+    };
+    let game = &mut ctx.accounts.game;
+    let deck = &mut ctx.accounts.deck;
+    let player_state = &mut ctx.accounts.player_state;
+    
+    require!(game.variant.is_draw(), PokerError::InvalidAction);
+    require!(
+        game.stage == GameStage::PreFlop && game.drawing,
+        PokerError::InvalidGameStage
+    );
+    require!(
+        game.current_player_index == player_state.seat_index,
+        PokerError::NotPlayerTurn
+    );
+    require!(!player_state.has_folded, PokerError::InvalidAction);
+    require!(!game.has_pending_computation(), PokerError::MpcResultPending);
+    
+    // Each slot at most once, and only slots the player actually holds
+    let hand_size = player_state.hole_card_positions.len();
+    require!(hand_size == DRAW_HAND_CARDS, PokerError::CardsNotDealt);
+    require!(discard_slots.len() <= hand_size, PokerError::InvalidDiscard);
+    for (i, &slot) in discard_slots.iter().enumerate() {
+        require!((slot as usize) < hand_size, PokerError::InvalidDiscard);
+        require!(!discard_slots[..i].contains(&slot), PokerError::InvalidDiscard);
+    }
+    
+    // Standing pat needs no computation
+    if discard_slots.is_empty() {
+        return finish_draw(game, player_state.seat_index, 0);
+    }
+    
+    let count = discard_slots.len() as u8;
+    if game.deck_cursor + count > game.variant.deck_size() as u8
+        && !recycle_discards(game, deck, Some(&mxe), recycle_entropy)?
+    {
+        return Ok(());
+    }
+    
+    let mut discard = [false; DRAW_HAND_CARDS];
+    for &slot in &discard_slots {
+        discard[slot as usize] = true;
+    }
+    draw_replacements(game, deck, Some(&mxe), player_state, discard)
+}
+
+/// Reshuffle the discards back into the stock before a draw it cannot cover
+///
+/// Runs before the drawing player's own discards are added, so nobody can be
+/// dealt back a card they just threw away. Returns whether the stock was
+/// refilled; `false` means the recycle was queued.
+pub fn recycle_discards(
+    game: &mut Game,
+    deck: &mut Deck,
+    mxe: Option<&MxeQueue>,
+    entropy: [u8; 32],
+) -> Result<bool> {
+    require!(game.discard_start < game.deck_cursor, PokerError::DeckExhausted);
+    
+    msg!(
+        "[DECK] Stock exhausted, recycling {} discards",
+        game.deck_cursor - game.discard_start
+    );
+    
+    let computation_offset = game.next_computation_offset();
+    let (accounts, comp_def_offset) = match mxe {
+        Some(queue) => {
+            let (accounts, offset) = queue.accounts_for(Circuit::RecycleDiscards, computation_offset)?;
+            (Some(accounts), offset)
+        }
+        None => (None, Circuit::RecycleDiscards.comp_def_offset()),
+    };
+    
+    let recycled = mpc_recycle_discards(MxeRecycleParams {
+        mxe: accounts,
+        deck,
+        start: game.discard_start,
+        entropy,
+        computation_offset,
+        comp_def_offset,
+        callback_accounts: mxe.map(|queue| queue.callback_accounts[..2].to_vec()).unwrap_or_default(),
+    })?;
+    
+    match recycled {
+        Some(output) => {
+            apply_recycle(game, deck, &output)?;
+            Ok(true)
+        }
+        None => {
+            game.await_computation(computation_offset, comp_def_offset, Clock::get()?.unix_timestamp);
+            Ok(false)
+        }
+    }
+}
+
+/// Store the reshuffled deck and deal from the first discard position again
+///
+/// Called with the mock's result, or from `handle_recycle_callback`.
+pub fn apply_recycle(game: &mut Game, deck: &mut Deck, output: &[u8]) -> Result<()> {
+    deck.store_encrypted(game.encrypted_deck, output)?;
+    game.deck_cursor = game.discard_start;
+    
+    msg!("[DECK] Discards reshuffled, dealing from position {}", game.deck_cursor);
+    Ok(())
+}
+
+/// Swap the player's discards with the next undealt cards
+fn draw_replacements(
+    game: &mut Game,
+    deck: &mut Deck,
+    mxe: Option<&MxeQueue>,
+    player_state: &mut PlayerState,
+    discard: [bool; DRAW_HAND_CARDS],
+) -> Result<()> {
+    let count = discard.iter().filter(|&&discard| discard).count() as u8;
+    let cursor = game.take_deck_positions(count)?[0];
+    
+    let mut hand_positions = [0u8; DRAW_HAND_CARDS];
+    hand_positions.copy_from_slice(&player_state.hole_card_positions);
+    
+    let computation_offset = game.next_computation_offset();
+    let (accounts, comp_def_offset) = match mxe {
+        Some(queue) => {
+            let (accounts, offset) = queue.accounts_for(Circuit::DrawCards, computation_offset)?;
+            (Some(accounts), offset)
+        }
+        None => (None, Circuit::DrawCards.comp_def_offset()),
+    };
+    
+    let drawn = mpc_draw_cards(MxeDrawParams {
+        mxe: accounts,
+        deck,
+        hand_positions,
+        discard,
+        cursor,
+        owner_key: player_state.encryption_pubkey,
+        nonce: generate_draw_nonce(&game.encrypted_deck, game.draws_completed, player_state.seat_index),
+        computation_offset,
+        comp_def_offset,
+        callback_accounts: mxe.map(|queue| queue.callback_accounts.clone()).unwrap_or_default(),
+    })?;
+    
+    match drawn {
+        Some(drawn) => apply_draw(game, deck, player_state, drawn),
+        None => {
+            msg!("[DRAW] Seat {} draw queued, waiting for the MXE", player_state.seat_index);
+            game.await_computation(computation_offset, comp_def_offset, Clock::get()?.unix_timestamp);
+            Ok(())
+        }
+    }
+}
+
+/// Store the updated deck and the player's new hand, then pass the turn
+///
+/// Called with the mock's result, or from `handle_draw_callback`.
+pub fn apply_draw(
+    game: &mut Game,
+    deck: &mut Deck,
+    player_state: &mut PlayerState,
+    drawn: Drawn,
+) -> Result<()> {
+    require!(
+        game.current_player_index == player_state.seat_index,
+        PokerError::NotPlayerTurn
+    );
+    require!(
+        drawn.hand.len() == player_state.hole_card_positions.len(),
+        PokerError::InvalidCardIndex
+    );
+    
+    // Drawn cards sit at the hand's positions; the discards at the cursor's
+    let drawn_count = drawn.hand
+        .iter()
+        .zip(&player_state.hole_cards)
+        .filter(|(new, old)| new != old)
+        .count();
+    deck.store_encrypted(game.encrypted_deck, &drawn.deck)?;
+    player_state.hole_cards = drawn.hand;
+    
+    finish_draw(game, player_state.seat_index, drawn_count)
+}
+
+/// Mark the seat as drawn and pass the turn, reopening betting after the
+/// last seat
+fn finish_draw(game: &mut Game, seat: u8, drawn: usize) -> Result<()> {
+    msg!("[DRAW] Seat {} drew {} card(s)", seat, drawn);
+    
+    game.players_drawn[seat as usize] = true;
+    
+    match next_to_draw(game, seat) {
+        Some(next) => {
+            game.current_player_index = next;
+            game.last_action_at = Clock::get()?.unix_timestamp;
+        }
+        None => {
+            // Everyone has drawn: close the phase and reopen betting
+            game.drawing = false;
+            game.draws_completed += 1;
+            msg!("[DRAW] Draw {} complete", game.draws_completed);
+            reset_betting_round(game)?;
+        }
+    }
+    
+    Ok(())
+}
+
+/// Clear draw state for a new hand
+pub fn reset_draw_hand(game: &mut Game) {
+    game.deck_cursor = 0;
+    game.discard_start = 0;
+    game.draws_completed = 0;
+    game.drawing = false;
+    game.players_drawn = [false; MAX_PLAYERS];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arcium::mpc_shuffle::{mock_deck_account, mock_decrypt_deck};
+    use crate::types::GameVariant;
+
+    #[test]
+    fn test_recycle_deals_the_discards_again() {
+        let cards: [u8; DECK_SIZE] = core::array::from_fn(|position| position as u8);
+        let mut deck = mock_deck_account(&cards);
+        let mut game = Game::deserialize(&mut &[0u8; Game::LEN][..]).unwrap();
+        game.variant = GameVariant::FiveCardDraw;
+        game.deck_initialized = true;
+        game.encrypted_deck = deck.session_id;
+        game.discard_start = 20;
+        game.deck_cursor = 50;
+
+        assert!(recycle_discards(&mut game, &mut deck, None, [9; 32]).unwrap());
+        assert_eq!(game.deck_cursor, 20);
+        assert_eq!(game.take_deck_positions(5).unwrap(), vec![20, 21, 22, 23, 24]);
+
+        // Dealt hands keep their cards; only the stock and discards move
+        let recycled = mock_decrypt_deck(&deck).unwrap();
+        assert_eq!(recycled[..20], cards[..20]);
+        let mut rest = recycled[20..].to_vec();
+        rest.sort_unstable();
+        assert_eq!(rest, cards[20..]);
+
+        // Nothing to recycle with no discards since the deal
+        game.deck_cursor = 20;
+        assert!(recycle_discards(&mut game, &mut deck, None, [9; 32]).is_err());
+    }
+}
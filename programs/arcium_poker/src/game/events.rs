@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::types::{BettingStructure, GameStage, GameVariant, HandRank};
+
+/// A new hand was dealt; clients re-render for the variant being played
+#[event]
+pub struct HandStarted {
+    pub game_id: u64,
+    pub dealer_position: u8,
+    pub variant: GameVariant,
+    pub betting_structure: BettingStructure,
+    pub rotation_index: u8,
+}
+
+/// A mixed-game table moved on to the next game of its rotation
+#[event]
+pub struct VariantRotated {
+    pub game_id: u64,
+    pub previous_variant: GameVariant,
+    pub variant: GameVariant,
+    pub betting_structure: BettingStructure,
+    pub rotation_index: u8,
+}
+
+/// Board cards for a street came back from the MPC reveal
+#[event]
+pub struct CommunityCardsRevealed {
+    pub game_id: u64,
+    pub stage: GameStage,
+    pub cards: Vec<u8>,
+}
+
+/// A private showdown paid out; only the winners (and, if the table shows
+/// it, the winning hand) were revealed
+#[event]
+pub struct PrivateShowdownResolved {
+    pub game_id: u64,
+    pub winners: Vec<u8>,
+    pub pot: u64,
+    pub winning_hand: Option<HandRank>,
+    pub winning_score: u32,
+}
+
+/// A queued MPC computation never called back; the hand was voided and
+/// every bet returned
+#[event]
+pub struct PendingComputationAborted {
+    pub game_id: u64,
+    pub stage: GameStage,
+    pub computation_offset: u64,
+    pub comp_def_offset: u32,
+    pub queued_at: i64,
+    pub aborted_at: i64,
+    pub refunded: u64,
+}
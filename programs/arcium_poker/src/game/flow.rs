@@ -1,9 +1,10 @@
 use anchor_lang::prelude::*;
 use super::state::Game;
+use super::events::HandStarted;
 use crate::player::state::PlayerState;
-use crate::types::GameStage;
+use crate::types::{DealRound, GameStage};
 use crate::shared::{PokerError, constants::*};
-use crate::cards::dealing::reveal_community_cards;
+use crate::arcium::mpc_community::open_board_reveal;
 use crate::betting::is_betting_round_complete;
 
 /// Advance game to next stage (PreFlop -> Flop -> Turn -> River -> Showdown)
@@ -11,6 +12,28 @@ use crate::betting::is_betting_round_complete;
 pub fn advance_game_stage(
     game: &mut Game,
 ) -> Result<()> {
+    // Cards still owed to the seats or the board come first
+    require!(game.deal_round == DealRound::None, PokerError::CardsNotDealt);
+    require!(
+        game.community_reveal_pending == 0,
+        PokerError::CommunityRevealPending
+    );
+    
+    // Stud runs its own street machine (Third..Seventh) inside PreFlop;
+    // betting reopens once the street's cards are dealt
+    if game.variant.is_stud() {
+        return super::stud::advance_street(game);
+    }
+    
+    // Draw games alternate betting rounds and draw phases inside PreFlop
+    if game.variant.is_draw() {
+        super::draw::advance_draw_round(game)?;
+        if game.stage != GameStage::Showdown {
+            msg!("[GAME FLOW] Waiting for players to draw");
+        }
+        return Ok(());
+    }
+    
     let next_stage = match game.stage {
         GameStage::Waiting => {
             return Err(PokerError::InvalidGameStage.into());
@@ -41,19 +64,19 @@ pub fn advance_game_stage(
     // Reset betting state for new round
     reset_betting_round(game)?;
     
-    // Reveal community cards based on stage
+    // Owe the board its community cards; `advance_stage` reveals them
     match next_stage {
         GameStage::Flop => {
             // Reveal 3 cards for flop
-            reveal_community_cards(game, 3)?;
+            open_board_reveal(game, 3)?;
         }
         GameStage::Turn => {
             // Reveal 1 card for turn
-            reveal_community_cards(game, 1)?;
+            open_board_reveal(game, 1)?;
         }
         GameStage::River => {
             // Reveal 1 card for river
-            reveal_community_cards(game, 1)?;
+            open_board_reveal(game, 1)?;
         }
         GameStage::Showdown => {
             // No cards to reveal, proceed to showdown
@@ -69,6 +92,7 @@ pub fn advance_game_stage(
 pub fn reset_betting_round(game: &mut Game) -> Result<()> {
     // Reset current bet to 0
     game.current_bet = 0;
+    game.bets_this_round = 0;
     
     // Reset players_acted flags
     game.players_acted = [false; crate::shared::constants::MAX_PLAYERS];
@@ -89,9 +113,19 @@ pub fn reset_betting_round(game: &mut Game) -> Result<()> {
 
 /// Get first player to act in a betting round
 pub fn get_first_player_for_round(game: &Game) -> u8 {
+    // Stud: decided by the cards showing, not the button
+    if game.variant.is_stud() {
+        return super::stud::first_to_act(game).unwrap_or(game.dealer_position);
+    }
+    
     // In pre-flop, first to act is after big blind (dealer + 3)
-    // In post-flop rounds, first to act is after dealer (dealer + 1)
-    let offset = if game.stage == GameStage::PreFlop {
+    // In post-flop rounds, or with a button ante instead of blinds,
+    // first to act is after dealer (dealer + 1)
+    // Draw games bet "pre-flop" only before the first draw
+    let offset = if game.stage == GameStage::PreFlop
+        && !game.uses_button_ante()
+        && game.draws_completed == 0
+    {
         3 // After big blind
     } else {
         1 // After dealer
@@ -255,6 +289,9 @@ pub fn check_all_players_all_in(
 
 /// Start new hand (reset for next hand)
 pub fn start_new_hand(game: &mut Game) -> Result<()> {
+    // Mixed games: count the finished hand and switch games when due
+    super::rotation::advance_rotation(game)?;
+    
     // Rotate dealer button
     rotate_dealer_button(game)?;
     
@@ -264,7 +301,12 @@ pub fn start_new_hand(game: &mut Game) -> Result<()> {
     game.current_bet = 0;
     game.community_cards = [0; COMMUNITY_CARDS];
     game.community_cards_revealed = 0;
+    game.community_reveal_pending = 0;
+    game.showdown_pending = false;
+    game.shown_cards = [[NO_CARD; MAX_HOLE_CARDS]; MAX_PLAYERS];
     game.deck_initialized = false;
+    super::stud::reset_stud_hand(game);
+    super::draw::reset_draw_hand(game);
     
     // Reset active players (all players who haven't left)
     for i in 0..game.player_count as usize {
@@ -275,6 +317,14 @@ pub fn start_new_hand(game: &mut Game) -> Result<()> {
     
     msg!("[GAME FLOW] New hand started. Dealer at seat {}", game.dealer_position);
     
+    emit!(HandStarted {
+        game_id: game.game_id,
+        dealer_position: game.dealer_position,
+        variant: game.variant,
+        betting_structure: game.betting_structure,
+        rotation_index: game.rotation_index,
+    });
+    
     Ok(())
 }
 
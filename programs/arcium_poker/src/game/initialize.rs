@@ -44,6 +44,12 @@ pub fn handler(
     
     **game = initialized_game;
     
+    // Empty deck account; the shuffle callback fills it each hand
+    let deck = &mut ctx.accounts.deck;
+    deck.game = game.key();
+    deck.clear();
+    deck.bump = ctx.bumps.deck;
+    
     msg!("Game {} initialized by {}", game_id, ctx.accounts.authority.key());
     msg!("Blinds: {}/{}, Buy-in: {}-{}", small_blind, big_blind, min_buy_in, max_buy_in);
     
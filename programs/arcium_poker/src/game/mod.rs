@@ -1,14 +1,29 @@
 pub mod state;
 pub mod initialize;
+pub mod configure;
 pub mod start;
+pub mod deal;
+pub mod advance;
+pub mod abort;
 pub mod logic;
 pub mod flow;
+pub mod stud;
+pub mod draw;
+pub mod rotation;
+pub mod events;
 
 pub use state::*;
 
 // Export the handler functions
 pub use initialize::handler as initialize_handler;
+pub use configure::handler as configure_handler;
+pub use configure::showdown_handler as configure_showdown_handler;
 pub use start::handler as start_handler;
+pub use deal::handler as deal_handler;
+pub use advance::handler as advance_handler;
+pub use abort::handler as abort_handler;
+pub use draw::handler as draw_handler;
+pub use rotation::handler as rotation_handler;
 
 // Export flow control functions
 pub use flow::{
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use super::state::Game;
+use super::events::VariantRotated;
+use super::configure::validate_variant;
+use crate::types::{GameStage, RotationEntry};
+use crate::shared::{constants::*, PokerError};
+
+/// Set up a mixed-game rotation (e.g. HORSE: limit Hold'em, Omaha Hi/Lo,
+/// Razz, Stud, Stud Hi/Lo)
+///
+/// The table plays `hands_per_variant` hands of each game in order, or one
+/// orbit of the table when it is 0. The first game starts immediately.
+/// Call after `configure_game`, which clears any rotation; its antes are kept.
+pub fn handler(
+    ctx: Context<crate::ConfigureGame>,
+    schedule: Vec<RotationEntry>,
+    hands_per_variant: u8,
+) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+    
+    require!(
+        game.stage == GameStage::Waiting,
+        PokerError::GameAlreadyStarted
+    );
+    require!(
+        !schedule.is_empty() && schedule.len() <= MAX_ROTATION_GAMES,
+        PokerError::InvalidGameConfig
+    );
+    
+    for entry in &schedule {
+        validate_variant(game, entry.variant)?;
+    }
+    
+    game.rotation = [RotationEntry::default(); MAX_ROTATION_GAMES];
+    game.rotation[..schedule.len()].copy_from_slice(&schedule);
+    game.rotation_len = schedule.len() as u8;
+    game.rotation_index = 0;
+    game.hands_per_variant = hands_per_variant;
+    game.hands_in_variant = 0;
+    
+    game.variant = schedule[0].variant;
+    game.betting_structure = schedule[0].betting_structure;
+    
+    msg!(
+        "Game {} rotating {} games, {} hands each (0 = one orbit)",
+        game.game_id,
+        game.rotation_len,
+        game.hands_per_variant
+    );
+    
+    Ok(())
+}
+
+/// Count a completed hand and move to the next game when its turn is up
+///
+/// Called between hands, before the next hand is dealt.
+pub fn advance_rotation(game: &mut Game) -> Result<()> {
+    if !game.is_mixed_game() {
+        return Ok(());
+    }
+    
+    game.hands_in_variant += 1;
+    
+    let hands_per_variant = if game.hands_per_variant == 0 {
+        game.player_count
+    } else {
+        game.hands_per_variant
+    };
+    
+    if game.hands_in_variant < hands_per_variant {
+        return Ok(());
+    }
+    
+    let previous_variant = game.variant;
+    game.rotation_index = (game.rotation_index + 1) % game.rotation_len;
+    game.hands_in_variant = 0;
+    
+    let entry = game.rotation[game.rotation_index as usize];
+    game.variant = entry.variant;
+    game.betting_structure = entry.betting_structure;
+    
+    msg!(
+        "[ROTATION] Switching from {:?} to {:?} ({:?})",
+        previous_variant,
+        game.variant,
+        game.betting_structure
+    );
+    
+    emit!(VariantRotated {
+        game_id: game.game_id,
+        previous_variant,
+        variant: game.variant,
+        betting_structure: game.betting_structure,
+        rotation_index: game.rotation_index,
+    });
+    
+    Ok(())
+}
@@ -1,16 +1,24 @@
 use anchor_lang::prelude::*;
 use super::state::Game;
+use super::events::HandStarted;
 use crate::player::state::PlayerState;
-use crate::arcium::mpc_shuffle::{mpc_shuffle_deck, ShuffleParams};
-use crate::arcium::mpc_deal::{mpc_deal_card, DealParams};
-use crate::types::GameStage;
+use crate::arcium::mpc_shuffle::{mpc_shuffle_deck, mock_encrypt_deck, ShuffleParams};
+use crate::arcium::backend::mxe_accounts;
+use crate::arcium::comp_defs::Circuit;
+use crate::cards::dealing::open_deal_round;
+use super::stud::next_active_seat;
+use crate::player::entropy::{collect_revealed_entropy, reset_entropy_round};
+use crate::player::state::{load_player_states, store_player_states};
+use crate::types::{DealRound, GameStage};
 use crate::shared::{constants::*, PokerError};
 
-/// Start the poker game - triggers MPC shuffle and deals hole cards
-pub fn handler(
-    ctx: Context<crate::StartGame>,
-    player_entropy: Vec<[u8; 32]>, // Each player provides randomness
-) -> Result<()> {
+/// Start the poker game - triggers MPC shuffle and posts the blinds
+///
+/// Remaining accounts: every seat's PlayerState, in seat order. The shuffle
+/// uses the entropy players revealed through `commit_entropy` and
+/// `reveal_entropy`; seats that never revealed sit out the hand. Hole cards
+/// are then dealt from the shuffled deck by `deal_cards`.
+pub fn handler(ctx: Context<crate::StartGame>) -> Result<()> {
     let game = &mut ctx.accounts.game;
     
     // Validate game can start
@@ -22,52 +30,59 @@ pub fn handler(
         game.player_count >= MIN_PLAYERS as u8,
         PokerError::NotEnoughPlayers
     );
-    require!(
-        player_entropy.len() == game.player_count as usize,
-        PokerError::InvalidGameConfig
-    );
     
-    msg!("[GAME START] Starting game {} with {} players", game.game_id, game.player_count);
+    // Only players who revealed their committed entropy are dealt in
+    let game_key = game.key();
+    let mut player_states = load_player_states(game_key, game.player_count, ctx.remaining_accounts)?;
+    let (player_entropy, contributors) =
+        collect_revealed_entropy(game, &player_states, Clock::get()?.unix_timestamp)?;
+    
+    for (seat, player_state) in player_states.iter_mut().enumerate() {
+        game.active_players[seat] = contributors.contains(&(seat as u8));
+        if !game.active_players[seat] {
+            msg!("[GAME START] Seat {} never revealed entropy, sitting out", seat);
+            player_state.fold();
+        }
+        player_state.clear_entropy();
+    }
+    reset_entropy_round(game);
+    
+    msg!("[GAME START] Starting game {} with {} players", game.game_id, contributors.len());
     
     // ========================================================================
     // STEP 1: ARCIUM MPC SHUFFLE 🔐
     // ========================================================================
     msg!("[ARCIUM MPC] Initiating secure shuffle...");
     
-    // Collect all player pubkeys
-    let players: Vec<Pubkey> = game.players[..game.player_count as usize]
+    // Collect the contributing players' pubkeys
+    let players: Vec<Pubkey> = contributors
         .iter()
-        .copied()
+        .map(|&seat| game.players[seat as usize])
         .collect();
     
-    // Perform MPC shuffle with all players contributing entropy
-    // Use REAL Arcium MPC with MXE accounts
+    // Perform MPC shuffle with all players contributing entropy on the
+    // build's MPC backend
     use crate::arcium::mpc_shuffle::MxeShuffleParams;
     
     // Generate computation offset (unique ID for this computation)
-    let computation_offset = game.game_id;
-
-    // Computation definition offset (from init-mxe)
-    let comp_def_offset = 1u32; // Using offset 1 (initialized in MXE)
-
+    let computation_offset = game.next_computation_offset();
+    
+    // Computation definition offset, as registered by `init_comp_def`
+    // Short deck uses its own shuffle circuit over 36 cards
+    let comp_def_offset = ctx.accounts.circuit_config.comp_def_offset(Circuit::shuffle(game.variant))?;
+    
+    let mxe = mxe_accounts!(ctx.accounts, authority);
+    ctx.accounts.arcium_config.validate_mxe_accounts(&mxe, comp_def_offset, computation_offset)?;
+    
     let mxe_shuffle_params = MxeShuffleParams {
-        mxe_program: Some(ctx.accounts.mxe_program.clone()),
-        mxe_account: Some(ctx.accounts.mxe_account.clone()),
-        comp_def: Some(ctx.accounts.comp_def_account.clone()),
-        mempool: Some(ctx.accounts.mempool_account.clone()),
-        cluster: Some(ctx.accounts.cluster_account.clone()),
-        computation_account: Some(ctx.accounts.computation_account.clone()),
-        authority: Some(ctx.accounts.authority.to_account_info()),
-        sign_seed: Some(ctx.accounts.sign_seed.clone()),
-        executing_pool: Some(ctx.accounts.executing_pool_account.clone()),
-        staking_pool: Some(ctx.accounts.staking_pool.clone()),
-        system_program: Some(ctx.accounts.system_program.to_account_info()),
-        clock: Some(ctx.accounts.clock.clone()),
-        encrypted_entropy: player_entropy.clone(),
+        mxe: Some(mxe),
+        encrypted_entropy: player_entropy,
         computation_offset,
         comp_def_offset,
         player_pubkeys: players.clone(),
         game_id: game.game_id,
+        variant: game.variant,
+        callback_accounts: vec![game.key(), ctx.accounts.deck.key()],
     };
     
     let shuffle_result = crate::arcium::mpc_shuffle::mpc_shuffle_deck_with_mxe(mxe_shuffle_params)?;
@@ -82,78 +97,46 @@ pub fn handler(
     );
     
     // Store shuffle result in game state
+    // A queued deck is ready once the shuffle callback stores it
     game.encrypted_deck = shuffle_result.session_id;
-    game.deck_initialized = true;
-    
-    // ========================================================================
-    // STEP 2: DEAL ENCRYPTED HOLE CARDS 🎴
-    // ========================================================================
-    msg!("[DEALING] Dealing encrypted hole cards to all players...");
+    game.deck_initialized = !shuffle_result.queued;
+    if shuffle_result.queued {
+        game.await_computation(computation_offset, comp_def_offset, Clock::get()?.unix_timestamp);
+    }
     
-    // Deal 2 hole cards to each player (encrypted via Arcium MPC)
-    let mut card_index = 0u8;
-
-    for (i, player_account) in ctx.remaining_accounts.iter().enumerate() {
-        if i >= game.player_count as usize {
-            break;
-        }
-
-        let player_pubkey = game.players[i];
-        msg!("[DEALING] Dealing to player {} at seat {}", player_pubkey, i);
-
-        // Borrow and deserialize player state to update encrypted_hole_cards
-        let mut data = player_account.try_borrow_mut_data()?;
-        let mut player_data = &data[..];
-        let mut player_state = PlayerState::try_deserialize(&mut player_data)?;
-
-        // Deal hole cards using Arcium MPC
-        for hole_card_num in 0..HOLE_CARDS {
-            let deal_params = DealParams {
-                card_index: shuffle_result.shuffled_indices[card_index as usize],
-                player: player_pubkey,
-                session_id: shuffle_result.session_id,
-                game_id: game.game_id,
-            };
-
-            let encrypted_card = mpc_deal_card(deal_params)?;
-
-            // ✅ FIX: Store encrypted card in PlayerState
-            player_state.encrypted_hole_cards[hole_card_num as usize] = encrypted_card.encrypted_index;
-
-            msg!(
-                "[DEALING] Card {}/{} dealt to seat {} (encrypted index: {})",
-                hole_card_num + 1,
-                HOLE_CARDS,
-                i,
-                encrypted_card.encrypted_index
-            );
-
-            card_index += 1;
-        }
-
-        // ✅ FIX: Serialize updated player state back to account
-        let mut writer = &mut data[..];
-        player_state.try_serialize(&mut writer)?;
-
-        msg!("[DEALING] Updated PlayerState for seat {} with encrypted hole cards", i);
+    // Queued shuffles store the MXE-encrypted deck from the callback; a
+    // local shuffle is encrypted into the Deck account here
+    let deck = &mut ctx.accounts.deck;
+    deck.clear();
+    if !shuffle_result.queued {
+        deck.store_encrypted(
+            shuffle_result.session_id,
+            &mock_encrypt_deck(&shuffle_result.shuffled_indices, &shuffle_result.session_id),
+        )?;
     }
     
     // ========================================================================
-    // STEP 3: INITIALIZE GAME STATE & POST BLINDS
+    // STEP 2: INITIALIZE GAME STATE
     // ========================================================================
     
     // Set game stage to PreFlop
     game.stage = GameStage::PreFlop;
     
-    // Set dealer button (starts at position 0)
-    game.dealer_position = 0;
+    // Set dealer button (first seat dealt in)
+    game.dealer_position = contributors[0];
     
-    // Calculate blind positions
-    let small_blind_seat = (game.dealer_position + 1) % game.player_count;
-    let big_blind_seat = (game.dealer_position + 2) % game.player_count;
+    // Calculate blind positions, skipping seats sitting out
+    let small_blind_seat = next_active_seat(game, game.dealer_position)?;
+    let big_blind_seat = next_active_seat(game, small_blind_seat)?;
     
-    // First player after big blind acts first
-    game.current_player_index = (game.dealer_position + 3) % game.player_count;
+    // First player after big blind acts first; with a button ante there are
+    // no blinds and the player left of the button opens. Stud seats its
+    // first player with the bring-in, once the upcards are dealt.
+    game.current_player_index = if game.uses_button_ante() {
+        next_active_seat(game, game.dealer_position)?
+    } else {
+        next_active_seat(game, big_blind_seat)?
+    };
     
     // Set timestamp
     game.started_at = Clock::get()?.unix_timestamp;
@@ -161,7 +144,13 @@ pub fn handler(
     
     // Reset pot and bets
     game.pot = 0;
-    game.current_bet = game.big_blind;
+    game.current_bet = if game.variant.is_stud() || game.uses_button_ante() {
+        0
+    } else {
+        game.big_blind
+    };
+    // The big blind is the round's first full bet
+    game.bets_this_round = if game.variant.is_stud() || game.uses_button_ante() { 0 } else { 1 };
     
     msg!("[GAME START] Game initialized!");
     msg!("[GAME START] Dealer button at seat {}", game.dealer_position);
@@ -169,50 +158,79 @@ pub fn handler(
     msg!("[GAME START] Current player: seat {}", game.current_player_index);
     msg!("[GAME START] Stage: {:?}", game.stage);
     
+    emit!(HandStarted {
+        game_id: game.game_id,
+        dealer_position: game.dealer_position,
+        variant: game.variant,
+        betting_structure: game.betting_structure,
+        rotation_index: game.rotation_index,
+    });
+    
     // ========================================================================
-    // STEP 4: POST BLINDS AUTOMATICALLY
+    // STEP 3: POST ANTES AND BLINDS AUTOMATICALLY
     // ========================================================================
-    msg!("[BLINDS] Posting blinds automatically...");
-    
-    // Post blinds if player accounts are provided in remaining_accounts
-    if ctx.remaining_accounts.len() >= game.player_count as usize {
-        post_blind(
-            &ctx.remaining_accounts[small_blind_seat as usize],
-            game.small_blind,
-            small_blind_seat,
-            &mut game.pot,
-        )?;
+    if game.ante > 0 {
+        for seat in 0..game.player_count {
+            if game.active_players[seat as usize] {
+                post_ante(&mut player_states[seat as usize], game.ante, seat, &mut game.pot)?;
+            }
+        }
         
-        post_blind(
-            &ctx.remaining_accounts[big_blind_seat as usize],
-            game.big_blind,
-            big_blind_seat,
-            &mut game.pot,
-        )?;
+        msg!("[BLINDS] Antes posted. Pot: {}", game.pot);
+    }
+    
+    if game.variant.is_stud() {
+        msg!("[BLINDS] Bring-in is posted once the third-street upcards are dealt");
+    } else if game.uses_button_ante() {
+        let button_seat = game.dealer_position;
+        post_blind(&mut player_states[button_seat as usize], game.button_ante, button_seat, &mut game.pot)?;
         
-        msg!("[BLINDS] Blinds posted successfully. Pot: {}", game.pot);
+        msg!("[BLINDS] Button ante posted. Pot: {}", game.pot);
     } else {
-        msg!("[BLINDS] No player accounts - blinds enforced via current_bet");
-        msg!("[BLINDS] Players must call {} to match big blind", game.current_bet);
+        post_blind(&mut player_states[small_blind_seat as usize], game.small_blind, small_blind_seat, &mut game.pot)?;
+        post_blind(&mut player_states[big_blind_seat as usize], game.big_blind, big_blind_seat, &mut game.pot)?;
+        
+        msg!("[BLINDS] Blinds posted successfully. Pot: {}", game.pot);
     }
     
-    Ok(())
+    // ========================================================================
+    // STEP 4: DEAL ENCRYPTED HOLE CARDS 🎴
+    // ========================================================================
+    // Every seat is owed its hole cards from the stored deck; betting opens
+    // once they are dealt (see `deal_cards`)
+    open_deal_round(game, DealRound::HoleCards)?;
+    
+    store_player_states(ctx.remaining_accounts, &player_states)
 }
 
 /// Helper function to post a blind
-fn post_blind<'info>(
-    player_account_info: &AccountInfo<'info>,
+fn post_blind(
+    player_state: &mut PlayerState,
     blind_amount: u64,
     seat_index: u8,
     pot: &mut u64,
 ) -> Result<()> {
-    // Borrow and deserialize player state
-    let mut data = player_account_info.try_borrow_mut_data()?;
-    
-    // Deserialize (try_deserialize handles discriminator automatically)
-    let mut player_data = &data[..];
-    let mut player_state = crate::player::state::PlayerState::try_deserialize(&mut player_data)?;
-    
+    post_forced_bet(player_state, blind_amount, seat_index, pot, true)
+}
+
+/// Helper function to post an ante (dead money, not part of the round's bet)
+fn post_ante(
+    player_state: &mut PlayerState,
+    ante_amount: u64,
+    seat_index: u8,
+    pot: &mut u64,
+) -> Result<()> {
+    post_forced_bet(player_state, ante_amount, seat_index, pot, false)
+}
+
+/// Move a forced bet from a player's stack into the pot
+fn post_forced_bet(
+    player_state: &mut PlayerState,
+    amount: u64,
+    seat_index: u8,
+    pot: &mut u64,
+    is_live: bool,
+) -> Result<()> {
     // Verify seat
     require!(
         player_state.seat_index == seat_index,
@@ -220,16 +238,17 @@ fn post_blind<'info>(
     );
     
     // Post blind
-    player_state.place_bet(blind_amount)?;
-    *pot += blind_amount;
+    player_state.place_bet(amount)?;
+    *pot += amount;
     
-    // Serialize back (includes discriminator)
-    let mut writer = &mut data[..];
-    player_state.try_serialize(&mut writer)?;
+    // Antes count toward the pot but not toward matching the current bet
+    if !is_live {
+        player_state.reset_for_new_round();
+    }
     
-    msg!("[BLINDS] Posted {} chips from seat {}", blind_amount, seat_index);
+    msg!("[BLINDS] Posted {} chips from seat {}", amount, seat_index);
     
     Ok(())
 }
 
-// StartGame struct moved to lib.rs at crate root (required by Anchor)
\ No newline at end of file
+// StartGame struct moved to lib.rs at crate root (required by Anchor)
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
-use crate::types::GameStage;
+use solana_keccak_hasher as keccak;
+use crate::types::{GameStage, GameVariant, BettingStructure, DealRound, EncryptedHoleCard, RotationEntry, StudStreet};
 use crate::shared::constants::*;
 
 /// Main game account
@@ -14,6 +15,12 @@ pub struct Game {
     /// Current game stage
     pub stage: GameStage,
     
+    /// Poker variant played at this table
+    pub variant: GameVariant,
+    
+    /// Betting structure (no-limit, pot-limit)
+    pub betting_structure: BettingStructure,
+    
     /// Small blind amount
     pub small_blind: u64,
     
@@ -26,6 +33,33 @@ pub struct Game {
     /// Maximum buy-in
     pub max_buy_in: u64,
     
+    /// Ante posted by the button instead of blinds (0 = play with blinds)
+    pub button_ante: u64,
+    
+    /// Ante posted by every player each hand (0 = no ante)
+    pub ante: u64,
+    
+    /// Mixed-game schedule (HORSE, 8-Game); only the first `rotation_len` are used
+    pub rotation: [RotationEntry; MAX_ROTATION_GAMES],
+    
+    /// Games in the rotation (0 = single-variant table)
+    pub rotation_len: u8,
+    
+    /// Index of the game currently being played
+    pub rotation_index: u8,
+    
+    /// Hands dealt per game before rotating (0 = one orbit of the table)
+    pub hands_per_variant: u8,
+    
+    /// Hands completed in the current game of the rotation
+    pub hands_in_variant: u8,
+    
+    /// Settle showdowns inside MPC, revealing only the winners (Hold'em only)
+    pub private_showdown: bool,
+    
+    /// Private showdowns also reveal the winning hand's score
+    pub show_winning_hand: bool,
+    
     /// Maximum number of players (4-6)
     pub max_players: u8,
     
@@ -38,6 +72,10 @@ pub struct Game {
     /// Active player flags
     pub active_players: [bool; MAX_PLAYERS],
     
+    /// x25519 public key each seat registered on join (mirrors PlayerState),
+    /// used by dealing paths that do not load player accounts
+    pub encryption_keys: [[u8; 32]; MAX_PLAYERS],
+    
     /// Current dealer button position
     pub dealer_position: u8,
     
@@ -50,6 +88,9 @@ pub struct Game {
     /// Current bet amount in this round
     pub current_bet: u64,
     
+    /// Full bets and raises made this round (fixed-limit cap; blinds count as one)
+    pub bets_this_round: u8,
+    
     /// Players who have acted in current betting round
     pub players_acted: [bool; MAX_PLAYERS],
     
@@ -59,15 +100,83 @@ pub struct Game {
     /// Number of community cards revealed
     pub community_cards_revealed: u8,
     
+    /// Board cards queued for MPC reveal and not yet delivered (0 = none);
+    /// betting waits until the callback writes them
+    pub community_reveal_pending: u8,
+    
+    /// A private showdown is queued and waits for `handle_showdown_callback`
+    pub showdown_pending: bool,
+    
+    /// Hole cards each seat showed at a public showdown, in the order
+    /// `reveal_hole_cards` read them (NO_CARD until revealed)
+    pub shown_cards: [[u8; MAX_HOLE_CARDS]; MAX_PLAYERS],
+    
+    /// Seat whose hole cards the pending `reveal_hole_cards` shows
+    pub reveal_seat: u8,
+    
+    /// MPC computations queued so far (seeds unique computation offsets)
+    pub computations_queued: u64,
+    
+    /// Offset of the computation awaiting its MXE callback
+    pub pending_computation: u64,
+    
+    /// Comp def of the pending computation's circuit (0 = none pending)
+    pub pending_comp_def_offset: u32,
+    
+    /// When the pending computation was queued (unix timestamp)
+    pub computation_queued_at: i64,
+    
+    /// Cards being dealt to the seats; betting waits until it is `None`
+    pub deal_round: DealRound,
+    
+    /// Next seat the open deal round deals to
+    pub deal_seat: u8,
+    
+    /// Current street (seven-card stud only)
+    pub street: StudStreet,
+    
+    /// Face-up cards per seat (seven-card stud only, NO_CARD when not dealt)
+    pub up_cards: [[u8; STUD_UP_CARDS]; MAX_PLAYERS],
+    
+    /// Encrypted seventh-street down card per seat (seven-card stud only)
+    /// Kept here because street transitions do not load player accounts
+    pub seventh_street_cards: [u8; MAX_PLAYERS],
+    
+    /// Seventh-street down card encrypted to each seat's x25519 key
+    pub seventh_street_hole_cards: [EncryptedHoleCard; MAX_PLAYERS],
+    
     /// Encrypted deck state (managed by Arcium MPC)
     pub encrypted_deck: [u8; 32], // Hash or reference to encrypted deck
     
     /// Deck initialized flag
     pub deck_initialized: bool,
     
+    /// Next undealt position in the shuffled deck
+    pub deck_cursor: u8,
+    
+    /// First deck position used for draw replacements; everything from here
+    /// to `deck_cursor` holds discards once swapped by the draw circuit
+    pub discard_start: u8,
+    
+    /// Draw phases completed this hand (draw games only)
+    pub draws_completed: u8,
+    
+    /// A draw phase is open and betting is paused (draw games only)
+    pub drawing: bool,
+    
+    /// Players who have drawn in the current draw phase
+    pub players_drawn: [bool; MAX_PLAYERS],
+    
     /// Game started timestamp
     pub started_at: i64,
     
+    /// Entropy reveals close at this time; seats that haven't revealed by
+    /// then sit out the hand (0 = nobody has committed yet)
+    pub entropy_deadline: i64,
+    
+    /// A player has revealed, so no more commitments are accepted
+    pub entropy_commits_closed: bool,
+    
     /// Last action timestamp
     pub last_action_at: i64,
     
@@ -84,24 +193,58 @@ impl Game {
         32 + // authority
         8 + // game_id
         1 + // stage
+        1 + // variant
+        1 + // betting_structure
         8 + // small_blind
         8 + // big_blind
         8 + // min_buy_in
         8 + // max_buy_in
+        8 + // button_ante
+        8 + // ante
+        (2 * MAX_ROTATION_GAMES) + // rotation
+        1 + // rotation_len
+        1 + // rotation_index
+        1 + // hands_per_variant
+        1 + // hands_in_variant
+        1 + // private_showdown
+        1 + // show_winning_hand
         1 + // max_players
         1 + // player_count
         (32 * MAX_PLAYERS) + // players
         (1 * MAX_PLAYERS) + // active_players
+        (32 * MAX_PLAYERS) + // encryption_keys
         1 + // dealer_position
         1 + // current_player_index
         8 + // pot
         8 + // current_bet
+        1 + // bets_this_round
         (1 * MAX_PLAYERS) + // players_acted
         (1 * COMMUNITY_CARDS) + // community_cards
         1 + // community_cards_revealed
+        1 + // community_reveal_pending
+        1 + // showdown_pending
+        (MAX_HOLE_CARDS * MAX_PLAYERS) + // shown_cards
+        1 + // reveal_seat
+        8 + // computations_queued
+        8 + // pending_computation
+        4 + // pending_comp_def_offset
+        8 + // computation_queued_at
+        1 + // deal_round
+        1 + // deal_seat
+        1 + // street
+        (STUD_UP_CARDS * MAX_PLAYERS) + // up_cards
+        MAX_PLAYERS + // seventh_street_cards
+        (EncryptedHoleCard::LEN * MAX_PLAYERS) + // seventh_street_hole_cards
         32 + // encrypted_deck
         1 + // deck_initialized
+        1 + // deck_cursor
+        1 + // discard_start
+        1 + // draws_completed
+        1 + // drawing
+        MAX_PLAYERS + // players_drawn
         8 + // started_at
+        8 + // entropy_deadline
+        1 + // entropy_commits_closed
         8 + // last_action_at
         32 + // shuffle_session_id
         1; // bump
@@ -121,24 +264,58 @@ impl Game {
             authority,
             game_id,
             stage: GameStage::Waiting,
+            variant: GameVariant::default(),
+            betting_structure: BettingStructure::default(),
             small_blind,
             big_blind,
             min_buy_in: min_buyin,
             max_buy_in: max_buyin,
+            button_ante: 0,
+            ante: 0,
+            rotation: [RotationEntry::default(); MAX_ROTATION_GAMES],
+            rotation_len: 0,
+            rotation_index: 0,
+            hands_per_variant: 0,
+            hands_in_variant: 0,
+            private_showdown: false,
+            show_winning_hand: false,
             max_players,
             player_count: 0,
             players: [Pubkey::default(); MAX_PLAYERS],
             active_players: [false; MAX_PLAYERS],
+            encryption_keys: [[0; 32]; MAX_PLAYERS],
             dealer_position: 0,
             current_player_index: 0,
             pot: 0,
             current_bet: 0,
+            bets_this_round: 0,
             players_acted: [false; MAX_PLAYERS],
             community_cards: [0; COMMUNITY_CARDS],
             community_cards_revealed: 0,
+            community_reveal_pending: 0,
+            showdown_pending: false,
+            shown_cards: [[NO_CARD; MAX_HOLE_CARDS]; MAX_PLAYERS],
+            reveal_seat: 0,
+            computations_queued: 0,
+            pending_computation: 0,
+            pending_comp_def_offset: 0,
+            computation_queued_at: 0,
+            deal_round: DealRound::None,
+            deal_seat: 0,
+            street: StudStreet::default(),
+            up_cards: [[NO_CARD; STUD_UP_CARDS]; MAX_PLAYERS],
+            seventh_street_cards: [NO_CARD; MAX_PLAYERS],
+            seventh_street_hole_cards: [EncryptedHoleCard::default(); MAX_PLAYERS],
             encrypted_deck: [0; 32],
             deck_initialized: false,
+            deck_cursor: 0,
+            discard_start: 0,
+            draws_completed: 0,
+            drawing: false,
+            players_drawn: [false; MAX_PLAYERS],
             started_at: 0,
+            entropy_deadline: 0,
+            entropy_commits_closed: false,
             last_action_at: Clock::get()?.unix_timestamp,
             shuffle_session_id: [0; 32],
             bump,
@@ -146,6 +323,42 @@ impl Game {
         Ok(game)
     }
     
+    /// Check if the table posts a button ante instead of blinds
+    pub fn uses_button_ante(&self) -> bool {
+        self.button_ante > 0
+    }
+    
+    /// Check if the table rotates between games
+    pub fn is_mixed_game(&self) -> bool {
+        self.rotation_len > 0
+    }
+    
+    /// Betting rounds completed this hand (0 = first round)
+    pub fn betting_round(&self) -> u8 {
+        if self.variant.is_stud() {
+            self.street as u8
+        } else if self.variant.is_draw() {
+            self.draws_completed
+        } else {
+            match self.stage {
+                GameStage::Flop => 1,
+                GameStage::Turn => 2,
+                GameStage::River => 3,
+                _ => 0,
+            }
+        }
+    }
+    
+    /// Size of a bet or raise in fixed-limit play: the big blind on early
+    /// rounds and twice that on later ones
+    pub fn fixed_limit_bet(&self) -> u64 {
+        if self.variant.uses_big_bet(self.betting_round()) {
+            self.big_blind * 2
+        } else {
+            self.big_blind
+        }
+    }
+    
     /// Check if game is full
     pub fn is_full(&self) -> bool {
         self.player_count >= self.max_players
@@ -158,14 +371,15 @@ impl Game {
             .any(|p| p == player)
     }
     
-    /// Add player to game
-    pub fn add_player(&mut self, player: Pubkey) -> Result<u8> {
+    /// Add player to game with the x25519 key their cards are encrypted to
+    pub fn add_player(&mut self, player: Pubkey, encryption_key: [u8; 32]) -> Result<u8> {
         require!(!self.is_full(), crate::shared::PokerError::GameFull);
         require!(!self.has_player(&player), crate::shared::PokerError::PlayerAlreadyInGame);
         
         let seat_index = self.player_count;
         self.players[seat_index as usize] = player;
         self.active_players[seat_index as usize] = true;
+        self.encryption_keys[seat_index as usize] = encryption_key;
         self.player_count += 1;
         
         Ok(seat_index)
@@ -186,25 +400,80 @@ impl Game {
             for i in player_index..(self.player_count as usize - 1) {
                 self.players[i] = self.players[i + 1];
                 self.active_players[i] = self.active_players[i + 1];
+                self.encryption_keys[i] = self.encryption_keys[i + 1];
             }
             self.players[self.player_count as usize - 1] = Pubkey::default();
             self.active_players[self.player_count as usize - 1] = false;
+            self.encryption_keys[self.player_count as usize - 1] = [0; 32];
             self.player_count -= 1;
         }
         
         Ok(())
     }
     
-    /// Get encrypted deck (for dealing cards)
-    /// Note: This is a simplified accessor. In production, the encrypted deck
-    /// would be stored in a separate account to handle larger data structures
-    pub fn get_encrypted_deck(&self) -> Result<crate::cards::deck::EncryptedDeck> {
-        // For MVP, we store deck reference in encrypted_deck field
-        // In production, this would load from a separate PDA account
+    /// Reserve the next `count` undealt deck positions
+    ///
+    /// Never hands a position out twice in a hand: when the stock is short
+    /// this fails with `DeckExhausted`. Draw games first reshuffle their
+    /// discards back into the stock (`recycle_discards`), which moves the
+    /// cursor back to `discard_start`.
+    pub fn take_deck_positions(&mut self, count: u8) -> Result<Vec<u8>> {
         require!(self.deck_initialized, crate::shared::PokerError::DeckNotInitialized);
+        require!(
+            self.deck_cursor + count <= self.variant.deck_size() as u8,
+            crate::shared::PokerError::DeckExhausted
+        );
+        
+        let start = self.deck_cursor;
+        self.deck_cursor += count;
+        
+        Ok((start..self.deck_cursor).collect())
+    }
+    
+    /// Offset for the next MPC computation this game queues
+    ///
+    /// Unique per game and computation, so a stale or replayed callback can
+    /// never answer a later hand's computation.
+    pub fn next_computation_offset(&self) -> u64 {
+        let hash = keccak::hashv(&[
+            self.authority.as_ref(),
+            &self.game_id.to_le_bytes()[..],
+            &self.computations_queued.to_le_bytes()[..],
+        ]);
+        u64::from_le_bytes(hash.to_bytes()[..8].try_into().unwrap())
+    }
+    
+    /// Record a queued computation; only its callback is accepted until it
+    /// resolves or the hand is aborted
+    pub fn await_computation(&mut self, computation_offset: u64, comp_def_offset: u32, now: i64) {
+        self.computations_queued += 1;
+        self.pending_computation = computation_offset;
+        self.pending_comp_def_offset = comp_def_offset;
+        self.computation_queued_at = now;
+    }
+    
+    /// A computation is waiting for its MXE callback
+    pub fn has_pending_computation(&self) -> bool {
+        self.pending_comp_def_offset != 0
+    }
+    
+    /// Accept a callback for the pending computation and clear it
+    pub fn resolve_computation(&mut self, computation_offset: u64, comp_def_offset: u32) -> Result<()> {
+        require!(
+            self.has_pending_computation()
+                && self.pending_comp_def_offset == comp_def_offset
+                && self.pending_computation == computation_offset,
+            crate::shared::PokerError::UnexpectedComputation
+        );
         
-        // TODO: Load actual encrypted deck from separate account
-        // For now, create mock deck structure
-        Ok(crate::cards::deck::EncryptedDeck::default())
+        self.clear_pending_computation();
+        Ok(())
+    }
+    
+    /// Forget the pending computation; a late callback for it is rejected
+    pub fn clear_pending_computation(&mut self) {
+        self.pending_computation = 0;
+        self.pending_comp_def_offset = 0;
+        self.computation_queued_at = 0;
     }
 }
\ No newline at end of file
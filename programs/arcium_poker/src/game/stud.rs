@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+use super::state::Game;
+use crate::cards::deck::Card;
+use crate::cards::dealing::open_deal_round;
+use crate::cards::stud::{bring_in_key, evaluate_showing, evaluate_showing_low, razz_bring_in_key, ShowingHand};
+use crate::types::{DealRound, EncryptedHoleCard, GameStage, StudStreet};
+use crate::shared::{constants::*, PokerError};
+
+/// Seat that posts the bring-in: lowest upcard on third street
+/// (highest upcard in Razz, where aces are low)
+pub fn bring_in_seat(game: &Game) -> Result<u8> {
+    let is_razz = game.variant.is_lowball();
+    let mut bring_in: Option<(u8, (u8, u8))> = None;
+    
+    for seat in 0..game.player_count {
+        if !game.active_players[seat as usize] {
+            continue;
+        }
+        
+        let card = Card::from_index(game.up_cards[seat as usize][0])?;
+        let (key, brings_in) = if is_razz {
+            let key = razz_bring_in_key(&card);
+            (key, bring_in.is_none_or(|(_, current)| key > current))
+        } else {
+            let key = bring_in_key(&card);
+            (key, bring_in.is_none_or(|(_, current)| key < current))
+        };
+        if brings_in {
+            bring_in = Some((seat, key));
+        }
+    }
+    
+    bring_in
+        .map(|(seat, _)| seat)
+        .ok_or(PokerError::NotEnoughPlayers.into())
+}
+
+/// First seat to act on the current street
+///
+/// Third street: the player after the bring-in. Later streets: the best
+/// showing hand (the lowest in Razz), ties going to the player closest to
+/// the dealer's left.
+pub fn first_to_act(game: &Game) -> Result<u8> {
+    if game.street == StudStreet::Third {
+        return next_active_seat(game, bring_in_seat(game)?);
+    }
+    
+    let showing = game.street.up_cards_showing();
+    let mut best: Option<(u8, ShowingHand)> = None;
+    
+    for offset in 1..=game.player_count {
+        let seat = (game.dealer_position + offset) % game.player_count;
+        if !game.active_players[seat as usize] {
+            continue;
+        }
+        
+        let up_cards = game.up_cards[seat as usize][..showing]
+            .iter()
+            .map(|&index| Card::from_index(index))
+            .collect::<Result<Vec<Card>>>()?;
+        let (hand, better) = if game.variant.is_lowball() {
+            let hand = evaluate_showing_low(&up_cards)?;
+            (hand, best.is_none_or(|(_, current)| hand < current))
+        } else {
+            let hand = evaluate_showing(&up_cards)?;
+            (hand, best.is_none_or(|(_, current)| hand > current))
+        };
+        
+        if better {
+            best = Some((seat, hand));
+        }
+    }
+    
+    best.map(|(seat, _)| seat)
+        .ok_or(PokerError::NotEnoughPlayers.into())
+}
+
+/// Next active seat after `seat`
+pub(crate) fn next_active_seat(game: &Game, seat: u8) -> Result<u8> {
+    for offset in 1..=game.player_count {
+        let next = (seat + offset) % game.player_count;
+        if game.active_players[next as usize] {
+            return Ok(next);
+        }
+    }
+    
+    Err(PokerError::NotEnoughPlayers.into())
+}
+
+/// Move a stud hand to its next street and open its deal round
+///
+/// Fourth through sixth street deal face up; seventh street deals face down.
+/// Betting reopens once every live player has the street's card (see
+/// `cards::dealing`). After seventh street the hand goes to showdown.
+pub fn advance_street(game: &mut Game) -> Result<()> {
+    require!(
+        game.stage == GameStage::PreFlop,
+        PokerError::InvalidGameStage
+    );
+    
+    let Some(next_street) = game.street.next() else {
+        msg!("[STUD] Seventh street complete, advancing to Showdown");
+        game.stage = GameStage::Showdown;
+        return Ok(());
+    };
+    
+    msg!("[STUD] Advancing to {:?} street", next_street);
+    game.street = next_street;
+    
+    let round = if next_street == StudStreet::Seventh {
+        DealRound::DownCards
+    } else {
+        DealRound::UpCards
+    };
+    open_deal_round(game, round)
+}
+
+/// Clear stud cards and return to third street for a new hand
+pub fn reset_stud_hand(game: &mut Game) {
+    game.street = StudStreet::Third;
+    game.up_cards = [[NO_CARD; STUD_UP_CARDS]; MAX_PLAYERS];
+    game.seventh_street_cards = [NO_CARD; MAX_PLAYERS];
+    game.seventh_street_hole_cards = [EncryptedHoleCard::default(); MAX_PLAYERS];
+    game.deal_round = DealRound::None;
+    game.deal_seat = 0;
+}
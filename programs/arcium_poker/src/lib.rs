@@ -13,11 +13,17 @@ pub mod token;
 pub mod security;
 pub mod advanced;
 
+// Off-chain equity/outs calculator for clients and bots (library builds only)
+#[cfg(any(feature = "no-entrypoint", test))]
+pub mod equity;
+
 declare_id!("B5E1V3DJsjMPzQb4QyMUuVhESqnWMXVcead4AEBvJB4W");
 
 // Re-export account state structs for use in Account Context structs below
 pub use game::state::Game;
 pub use player::state::PlayerState;
+pub use cards::deck::Deck;
+pub use arcium::{ArciumConfig, CircuitConfig};
 
 #[program]
 pub mod arcium_poker {
@@ -44,9 +50,40 @@ pub mod arcium_poker {
         )
     }
     
+    /// Choose the poker variant, betting structure and optional antes
+    /// (before the game starts)
+    pub fn configure_game(
+        ctx: Context<ConfigureGame>,
+        variant: types::GameVariant,
+        betting_structure: Option<types::BettingStructure>,
+        button_ante: Option<u64>,
+        ante: Option<u64>,
+    ) -> Result<()> {
+        game::configure_handler(ctx, variant, betting_structure, button_ante, ante)
+    }
+    
+    /// Rotate the table between games (HORSE, 8-Game) every N hands or orbit
+    pub fn configure_rotation(
+        ctx: Context<ConfigureGame>,
+        schedule: Vec<types::RotationEntry>,
+        hands_per_variant: u8,
+    ) -> Result<()> {
+        game::rotation_handler(ctx, schedule, hands_per_variant)
+    }
+    
+    /// Settle showdowns inside MPC, revealing only the winners and,
+    /// optionally, the winning hand (Hold'em tables, before the game starts)
+    pub fn configure_showdown(
+        ctx: Context<ConfigureGame>,
+        private_showdown: bool,
+        show_winning_hand: bool,
+    ) -> Result<()> {
+        game::configure_showdown_handler(ctx, private_showdown, show_winning_hand)
+    }
+    
     /// Player joins a game
-    pub fn join_game(ctx: Context<JoinGame>, buy_in: u64) -> Result<()> {
-        player::join_handler(ctx, buy_in)
+    pub fn join_game(ctx: Context<JoinGame>, buy_in: u64, encryption_pubkey: [u8; 32]) -> Result<()> {
+        player::join_handler(ctx, buy_in, encryption_pubkey)
     }
     
     /// Player leaves a game
@@ -54,12 +91,40 @@ pub mod arcium_poker {
         player::leave_handler(ctx)
     }
     
-    /// Start the game - performs Arcium MPC shuffle and deals cards
-    pub fn start_game(
-        ctx: Context<StartGame>,
-        player_entropy: Vec<[u8; 32]>,
+    /// Commit to shuffle entropy for the next hand (hash of the preimage,
+    /// game and player; see `player::entropy::entropy_commitment`)
+    pub fn commit_entropy(ctx: Context<PlayerAction>, commitment: [u8; 32]) -> Result<()> {
+        player::commit_entropy_handler(ctx, commitment)
+    }
+    
+    /// Reveal the entropy committed with `commit_entropy`
+    pub fn reveal_entropy(ctx: Context<PlayerAction>, preimage: [u8; 32]) -> Result<()> {
+        player::reveal_entropy_handler(ctx, preimage)
+    }
+    
+    /// Start the game - performs Arcium MPC shuffle over the players'
+    /// revealed entropy and posts the blinds; `deal_cards` then deals
+    /// Remaining accounts: every seat's PlayerState, in seat order
+    pub fn start_game(ctx: Context<StartGame>) -> Result<()> {
+        game::start_handler(ctx)
+    }
+    
+    /// Deal the cards the table owes (hole cards, stud street cards) from
+    /// the shuffled deck; permissionless
+    /// Remaining accounts: every seat's PlayerState, in seat order
+    pub fn deal_cards(ctx: Context<DealCards>) -> Result<()> {
+        game::deal_handler(ctx)
+    }
+    
+    /// Draw games: discard 0-5 hole cards and receive replacements
+    /// `recycle_entropy` (encrypted to the MXE) reshuffles the discards
+    /// into the stock when it runs short
+    pub fn draw_cards(
+        ctx: Context<DrawCards>,
+        discard_slots: Vec<u8>,
+        recycle_entropy: [u8; 32],
     ) -> Result<()> {
-        game::start_handler(ctx, player_entropy)
+        game::draw_handler(ctx, discard_slots, recycle_entropy)
     }
     
     /// Player folds their hand
@@ -120,8 +185,9 @@ pub mod arcium_poker {
     }
     
     /// Advance game to next stage (PreFlop -> Flop -> Turn -> River -> Showdown)
+    /// and reveal the board cards the new street is owed
     pub fn advance_stage(ctx: Context<AdvanceStage>) -> Result<()> {
-        game::advance_game_stage(&mut ctx.accounts.game)
+        game::advance_handler(ctx)
     }
     
     /// Handle player timeout (auto-fold)
@@ -140,43 +206,59 @@ pub mod arcium_poker {
     }
     
     /// Execute showdown - reveal cards and distribute winnings
-    /// Note: This is a simplified version. Full implementation would handle
-    /// encrypted card reveals via Arcium MPC
+    /// MXE accounts are those for `reveal_hole_cards`; on Arcium each call
+    /// shows one seat's hand, and the last seat's callback pays out
+    /// Remaining accounts: every seat's PlayerState, in seat order
     pub fn execute_showdown(ctx: Context<ExecuteShowdown>) -> Result<()> {
-        // Create pot manager from game state
-        let mut pot_manager = betting::PotManager::new();
-        pot_manager.main_pot = ctx.accounts.game.pot;
-        // Side pots would be calculated from betting history
-        
-        // Load player states (simplified - would use remaining_accounts in production)
-        let mut player_states = vec![(*ctx.accounts.player_state).clone()];
-        
-        showdown::handle_showdown(
-            &mut ctx.accounts.game,
-            &mut player_states,
-            &pot_manager,
-        )?;
-        
-        // Update player state
-        *ctx.accounts.player_state = player_states[0].clone();
-        
-        Ok(())
+        showdown::public_showdown_handler(ctx)
+    }
+    
+    /// Execute a private showdown - winners are decided inside MPC and no
+    /// hole cards are revealed
+    /// MXE accounts are those for `evaluate_showdown`
+    /// Remaining accounts: every seat's PlayerState, in seat order
+    pub fn execute_private_showdown(ctx: Context<AdvanceStage>) -> Result<()> {
+        showdown::private_showdown_handler(ctx)
+    }
+    
+    /// Void a hand whose MPC computation never called back and refund every bet
+    /// Permissionless once the Arcium config timeout has passed
+    /// Remaining accounts: every seat's PlayerState, in seat order
+    pub fn abort_pending_computation(ctx: Context<AbortPendingComputation>) -> Result<()> {
+        game::abort_handler(ctx)
     }
     
-    /// Initialize computation definition for MPC shuffle
-    /// Must be called once after deployment
-    pub fn init_shuffle_comp_def(
+    /// Create the program-wide Arcium config
+    /// Only the program's upgrade authority may; it becomes the config admin
+    pub fn init_arcium_config(
+        ctx: Context<InitArciumConfig>,
+        params: arcium::ArciumConfigParams,
+    ) -> Result<()> {
+        arcium::config::init_handler(ctx, params)
+    }
+    
+    /// Change the Arcium config (admin only)
+    pub fn update_arcium_config(
+        ctx: Context<UpdateArciumConfig>,
+        params: arcium::ArciumConfigParams,
+    ) -> Result<()> {
+        arcium::config::update_handler(ctx, params)
+    }
+    
+    /// Create the program-wide circuit config (Arcium config admin only)
+    /// Must be called once after `init_arcium_config`, before `init_comp_def`
+    pub fn init_circuit_config(ctx: Context<InitCircuitConfig>) -> Result<()> {
+        arcium::comp_defs::init_config_handler(ctx)
+    }
+    
+    /// Initialize a circuit's computation definition and register it
+    /// Must be called once per circuit after deployment
+    pub fn init_comp_def(
         ctx: Context<InitCompDef>,
-        comp_def_offset: u32,
+        circuit: arcium::Circuit,
+        circuit_hash: [u8; 32],
     ) -> Result<()> {
-        arcium::integration::init_computation_definition(
-            &ctx.accounts.comp_def_account,
-            &ctx.accounts.mxe_account,
-            &ctx.accounts.authority,
-            &ctx.accounts.system_program,
-            comp_def_offset,
-            0, // shuffle_deck instruction index
-        )
+        arcium::comp_defs::init_comp_def_handler(ctx, circuit, circuit_hash)
     }
     
     /// Handle MXE callback with shuffle result
@@ -186,12 +268,238 @@ pub mod arcium_poker {
         computation_id: [u8; 32],
         encrypted_output: Vec<u8>,
     ) -> Result<()> {
+        let circuit = arcium::Circuit::shuffle(ctx.accounts.game.variant);
+        arcium::integration::authenticate_callback(ctx.accounts, &computation_id, circuit)?;
+        
         arcium::integration::handle_shuffle_callback(
             &mut ctx.accounts.game,
+            &mut ctx.accounts.deck,
             computation_id,
             encrypted_output,
         )
     }
+    
+    /// Handle MXE callback with a seat's hole cards
+    /// Called by Arcium network after the deal_hole_cards computation;
+    /// card `i` is encrypted to the seat under `nonce + i`
+    /// Remaining accounts: every seat's PlayerState, in seat order
+    pub fn handle_deal_callback(
+        ctx: Context<MxeCallback>,
+        computation_id: [u8; 32],
+        nonce: u128,
+        ciphertexts: [[u8; 32]; shared::constants::MAX_HOLE_CARDS],
+    ) -> Result<()> {
+        arcium::integration::authenticate_callback(
+            ctx.accounts,
+            &computation_id,
+            arcium::Circuit::DealHoleCards,
+        )?;
+        
+        // The circuit pads the hand to MAX_HOLE_CARDS
+        let count = ctx.accounts.game.variant.hole_cards_at_start();
+        let dealt = arcium::mpc_deal::hidden_cards(nonce, &ciphertexts[..count]);
+        handle_deal_output(ctx, computation_id, dealt)
+    }
+    
+    /// Handle MXE callback with a stud down card, encrypted to its seat
+    /// Called by Arcium network after the deal_card computation
+    /// Remaining accounts: every seat's PlayerState, in seat order
+    pub fn handle_down_card_callback(
+        ctx: Context<MxeCallback>,
+        computation_id: [u8; 32],
+        nonce: u128,
+        ciphertext: [u8; 32],
+    ) -> Result<()> {
+        arcium::integration::authenticate_callback(
+            ctx.accounts,
+            &computation_id,
+            arcium::Circuit::DealCard,
+        )?;
+        
+        let dealt = arcium::mpc_deal::hidden_cards(nonce, &[ciphertext]);
+        handle_deal_output(ctx, computation_id, dealt)
+    }
+    
+    /// Handle MXE callback with a stud upcard, revealed to everyone
+    /// Called by Arcium network after the deal_up_card computation
+    /// Remaining accounts: every seat's PlayerState, in seat order
+    pub fn handle_up_card_callback(
+        ctx: Context<MxeCallback>,
+        computation_id: [u8; 32],
+        card: u8,
+    ) -> Result<()> {
+        arcium::integration::authenticate_callback(
+            ctx.accounts,
+            &computation_id,
+            arcium::Circuit::DealUpCard,
+        )?;
+        
+        handle_deal_output(ctx, computation_id, arcium::mpc_deal::Dealt::FaceUp(card))
+    }
+    
+    /// Handle MXE callback with a draw: the updated deck and the drawing
+    /// player's new hand, card `i` encrypted to them under `nonce + i`
+    /// Called by Arcium network after the draw_cards computation
+    /// Remaining accounts: the drawing player's PlayerState
+    pub fn handle_draw_callback(
+        ctx: Context<MxeCallback>,
+        computation_id: [u8; 32],
+        encrypted_deck: Vec<u8>,
+        nonce: u128,
+        ciphertexts: [[u8; 32]; shared::constants::DRAW_HAND_CARDS],
+    ) -> Result<()> {
+        arcium::integration::authenticate_callback(
+            ctx.accounts,
+            &computation_id,
+            arcium::Circuit::DrawCards,
+        )?;
+        
+        let account = ctx.remaining_accounts.first().ok_or(shared::PokerError::PlayerNotInGame)?;
+        let mut player_state = player::load_player_state(
+            ctx.accounts.game.key(),
+            ctx.accounts.game.current_player_index,
+            account,
+        )?;
+        
+        game::draw::apply_draw(
+            &mut ctx.accounts.game,
+            &mut ctx.accounts.deck,
+            &mut player_state,
+            arcium::mpc_draw::drawn(encrypted_deck, nonce, &ciphertexts),
+        )?;
+        
+        player::store_player_states(&ctx.remaining_accounts[..1], &[player_state])
+    }
+    
+    /// Handle MXE callback with the deck after the discards were reshuffled
+    /// into the stock
+    /// Called by Arcium network after the recycle_discards computation; the
+    /// drawing player then draws again
+    pub fn handle_recycle_callback(
+        ctx: Context<MxeCallback>,
+        computation_id: [u8; 32],
+        encrypted_deck: Vec<u8>,
+    ) -> Result<()> {
+        arcium::integration::authenticate_callback(
+            ctx.accounts,
+            &computation_id,
+            arcium::Circuit::RecycleDiscards,
+        )?;
+        
+        game::draw::apply_recycle(
+            &mut ctx.accounts.game,
+            &mut ctx.accounts.deck,
+            &encrypted_deck,
+        )
+    }
+    
+    /// Handle MXE callback with the revealed flop, turn or river
+    /// Called by Arcium network after the reveal_community computation
+    pub fn handle_community_callback(
+        ctx: Context<MxeCallback>,
+        computation_id: [u8; 32],
+        revealed: Vec<u8>,
+    ) -> Result<()> {
+        arcium::integration::authenticate_callback(
+            ctx.accounts,
+            &computation_id,
+            arcium::Circuit::RevealCommunity,
+        )?;
+        
+        arcium::mpc_community::handle_community_callback(
+            &mut ctx.accounts.game,
+            computation_id,
+            revealed,
+        )
+    }
+    
+    /// Handle MXE callback with a seat's hole cards shown at showdown
+    /// Called by Arcium network after the reveal_hole_cards computation
+    /// Remaining accounts: every seat's PlayerState, in seat order
+    pub fn handle_reveal_callback(
+        ctx: Context<MxeCallback>,
+        computation_id: [u8; 32],
+        revealed: Vec<u8>,
+    ) -> Result<()> {
+        arcium::integration::authenticate_callback(
+            ctx.accounts,
+            &computation_id,
+            arcium::Circuit::RevealHoleCards,
+        )?;
+        
+        let game_key = ctx.accounts.game.key();
+        let mut player_states = player::load_player_states(
+            game_key,
+            ctx.accounts.game.player_count,
+            ctx.remaining_accounts,
+        )?;
+        
+        arcium::mpc_reveal::handle_reveal_callback(
+            &mut ctx.accounts.game,
+            &mut player_states,
+            computation_id,
+            revealed,
+        )?;
+        
+        player::store_player_states(ctx.remaining_accounts, &player_states)
+    }
+    
+    /// Handle MXE callback with the private showdown winners
+    /// Called by Arcium network after the evaluate_showdown computation
+    /// Remaining accounts: every seat's PlayerState, in seat order
+    pub fn handle_showdown_callback(
+        ctx: Context<MxeCallback>,
+        computation_id: [u8; 32],
+        winners: [bool; shared::constants::MAX_PLAYERS],
+        winning_score: u32,
+    ) -> Result<()> {
+        arcium::integration::authenticate_callback(
+            ctx.accounts,
+            &computation_id,
+            arcium::Circuit::EvaluateShowdown,
+        )?;
+        
+        let game_key = ctx.accounts.game.key();
+        let mut player_states = player::load_player_states(
+            game_key,
+            ctx.accounts.game.player_count,
+            ctx.remaining_accounts,
+        )?;
+        
+        arcium::mpc_showdown::handle_showdown_callback(
+            &mut ctx.accounts.game,
+            &mut player_states,
+            computation_id,
+            winners,
+            winning_score,
+        )?;
+        
+        player::store_player_states(ctx.remaining_accounts, &player_states)
+    }
+}
+
+/// Record an authenticated deal callback's cards on the game and the seats'
+/// PlayerStates (remaining accounts, in seat order)
+fn handle_deal_output(
+    ctx: Context<MxeCallback>,
+    computation_id: [u8; 32],
+    dealt: arcium::mpc_deal::Dealt,
+) -> Result<()> {
+    let game_key = ctx.accounts.game.key();
+    let mut player_states = player::load_player_states(
+        game_key,
+        ctx.accounts.game.player_count,
+        ctx.remaining_accounts,
+    )?;
+    
+    arcium::mpc_deal::handle_deal_callback(
+        &mut ctx.accounts.game,
+        &mut player_states,
+        computation_id,
+        dealt,
+    )?;
+    
+    player::store_player_states(ctx.remaining_accounts, &player_states)
 }
 
 // ============================================================================
@@ -210,12 +518,32 @@ pub struct InitializeGame<'info> {
     )]
     pub game: Account<'info, Game>,
     
+    /// MXE-encrypted deck for this game, written by the shuffle callback
+    #[account(
+        init,
+        payer = authority,
+        space = Deck::LEN,
+        seeds = [b"deck", game.key().as_ref()],
+        bump
+    )]
+    pub deck: Box<Account<'info, Deck>>,
+    
     #[account(mut)]
     pub authority: Signer<'info>,
     
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct ConfigureGame<'info> {
+    #[account(mut)]
+    pub game: Account<'info, Game>,
+    
+    /// Game authority (creator) must configure the game
+    #[account(constraint = authority.key() == game.authority @ shared::PokerError::InvalidAction)]
+    pub authority: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct StartGame<'info> {
     #[account(mut)]
@@ -225,57 +553,148 @@ pub struct StartGame<'info> {
     #[account(constraint = authority.key() == game.authority @ shared::PokerError::InvalidAction)]
     pub authority: Signer<'info>,
     
+    /// Game's MXE-encrypted deck
+    #[account(mut, seeds = [b"deck", game.key().as_ref()], bump = deck.bump)]
+    pub deck: Box<Account<'info, Deck>>,
+    
+    /// Registered comp defs, for the shuffle circuit's offset
+    #[account(seeds = [CircuitConfig::SEED], bump = circuit_config.bump)]
+    pub circuit_config: Box<Account<'info, CircuitConfig>>,
+    
+    /// MXE program and PDAs the MXE accounts below are checked against
+    #[account(seeds = [ArciumConfig::SEED], bump = arcium_config.bump)]
+    pub arcium_config: Box<Account<'info, ArciumConfig>>,
+    
     /// MXE program for encrypted computations
-    /// CHECK: Arcium MXE program ID verified in handler
+    /// CHECK: Address constrained to the configured MXE program
+    #[account(
+        address = arcium_config.mxe_program_id
+            @ arcium::integration::ErrorCode::InvalidMxeAccount
+    )]
     pub mxe_program: AccountInfo<'info>,
     
     /// MXE account for this program
-    /// CHECK: PDA derived from program ID
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
     #[account(mut)]
     pub mxe_account: AccountInfo<'info>,
     
     /// Computation definition account for shuffle
-    /// CHECK: PDA derived from comp def offset
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
     #[account(mut)]
     pub comp_def_account: AccountInfo<'info>,
     
     /// Mempool account for queueing computations
-    /// CHECK: PDA derived from program ID
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
     #[account(mut)]
     pub mempool_account: AccountInfo<'info>,
     
     /// Executing pool account
-    /// CHECK: PDA derived from program ID
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
     #[account(mut)]
     pub executing_pool_account: AccountInfo<'info>,
     
     /// Cluster account
-    /// CHECK: Verified cluster on Arcium network
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
     pub cluster_account: AccountInfo<'info>,
     
     /// Computation account (will be created)
-    /// CHECK: PDA derived from computation offset
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
     #[account(mut)]
     pub computation_account: AccountInfo<'info>,
-
+    
     /// Sign seed PDA
-    /// CHECK: PDA derived from MXE program
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
     pub sign_seed: AccountInfo<'info>,
-
+    
     /// Staking pool PDA
-    /// CHECK: PDA derived from MXE program
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
     pub staking_pool: AccountInfo<'info>,
-
+    
     pub system_program: Program<'info, System>,
-
-    /// Clock sysvar
-    /// CHECK: Clock sysvar account
+    
+    /// Arcium clock account
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
     pub clock: AccountInfo<'info>,
-
+    
     // Remaining accounts: PlayerState accounts for all players in order
     // These will be validated and updated during execution
 }
 
+#[derive(Accounts)]
+pub struct DealCards<'info> {
+    #[account(mut)]
+    pub game: Account<'info, Game>,
+    
+    /// Anyone may deal; pays for the computation
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// Game's MXE-encrypted deck, dealt from by reference
+    #[account(seeds = [b"deck", game.key().as_ref()], bump = deck.bump)]
+    pub deck: Box<Account<'info, Deck>>,
+    
+    /// Registered comp defs, for the deal circuit's offset
+    #[account(seeds = [CircuitConfig::SEED], bump = circuit_config.bump)]
+    pub circuit_config: Box<Account<'info, CircuitConfig>>,
+    
+    /// MXE program and PDAs the MXE accounts below are checked against
+    #[account(seeds = [ArciumConfig::SEED], bump = arcium_config.bump)]
+    pub arcium_config: Box<Account<'info, ArciumConfig>>,
+    
+    /// MXE program for encrypted computations
+    /// CHECK: Address constrained to the configured MXE program
+    #[account(
+        address = arcium_config.mxe_program_id
+            @ arcium::integration::ErrorCode::InvalidMxeAccount
+    )]
+    pub mxe_program: AccountInfo<'info>,
+    
+    /// MXE account for this program
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    #[account(mut)]
+    pub mxe_account: AccountInfo<'info>,
+    
+    /// Computation definition account of the next deal's circuit
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    #[account(mut)]
+    pub comp_def_account: AccountInfo<'info>,
+    
+    /// Mempool account for queueing computations
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    #[account(mut)]
+    pub mempool_account: AccountInfo<'info>,
+    
+    /// Executing pool account
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    #[account(mut)]
+    pub executing_pool_account: AccountInfo<'info>,
+    
+    /// Cluster account
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    pub cluster_account: AccountInfo<'info>,
+    
+    /// Computation account (will be created)
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    #[account(mut)]
+    pub computation_account: AccountInfo<'info>,
+    
+    /// Sign seed PDA
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    pub sign_seed: AccountInfo<'info>,
+    
+    /// Staking pool PDA
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    pub staking_pool: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// Arcium clock account
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    pub clock: AccountInfo<'info>,
+    
+    // Remaining accounts: every seat's PlayerState, in seat order
+}
+
 #[derive(Accounts)]
 pub struct JoinGame<'info> {
     #[account(mut)]
@@ -333,15 +752,162 @@ pub struct PlayerAction<'info> {
     pub player: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct DrawCards<'info> {
+    #[account(mut)]
+    pub game: Account<'info, Game>,
+    
+    #[account(
+        mut,
+        seeds = [b"player", game.key().as_ref(), player.key().as_ref()],
+        bump = player_state.bump,
+        has_one = game,
+        has_one = player
+    )]
+    pub player_state: Account<'info, PlayerState>,
+    
+    /// Drawing player; pays for the computation
+    #[account(mut)]
+    pub player: Signer<'info>,
+    
+    /// Game's MXE-encrypted deck; the mock backend updates it in place
+    #[account(mut, seeds = [b"deck", game.key().as_ref()], bump = deck.bump)]
+    pub deck: Box<Account<'info, Deck>>,
+    
+    /// Registered comp defs, for the offset of the circuit queued
+    #[account(seeds = [CircuitConfig::SEED], bump = circuit_config.bump)]
+    pub circuit_config: Box<Account<'info, CircuitConfig>>,
+    
+    /// MXE program and PDAs the MXE accounts below are checked against
+    #[account(seeds = [ArciumConfig::SEED], bump = arcium_config.bump)]
+    pub arcium_config: Box<Account<'info, ArciumConfig>>,
+    
+    /// MXE program for encrypted computations
+    /// CHECK: Address constrained to the configured MXE program
+    #[account(
+        address = arcium_config.mxe_program_id
+            @ arcium::integration::ErrorCode::InvalidMxeAccount
+    )]
+    pub mxe_program: AccountInfo<'info>,
+    
+    /// MXE account for this program
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    #[account(mut)]
+    pub mxe_account: AccountInfo<'info>,
+    
+    /// Computation definition account of the draw or recycle circuit
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    #[account(mut)]
+    pub comp_def_account: AccountInfo<'info>,
+    
+    /// Mempool account for queueing computations
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    #[account(mut)]
+    pub mempool_account: AccountInfo<'info>,
+    
+    /// Executing pool account
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    #[account(mut)]
+    pub executing_pool_account: AccountInfo<'info>,
+    
+    /// Cluster account
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    pub cluster_account: AccountInfo<'info>,
+    
+    /// Computation account (will be created)
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    #[account(mut)]
+    pub computation_account: AccountInfo<'info>,
+    
+    /// Sign seed PDA
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    pub sign_seed: AccountInfo<'info>,
+    
+    /// Staking pool PDA
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    pub staking_pool: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// Arcium clock account
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    pub clock: AccountInfo<'info>,
+}
+
 #[derive(Accounts)]
 pub struct AdvanceStage<'info> {
     #[account(mut)]
     pub game: Account<'info, Game>,
     
-    /// Any player or authority can advance the stage
+    /// Any player or authority can advance the stage; pays for the computation
+    #[account(mut)]
     pub signer: Signer<'info>,
     
-    // Remaining accounts: PlayerState accounts for all players
+    /// Game's MXE-encrypted deck, read by reference by the circuit queued
+    #[account(seeds = [b"deck", game.key().as_ref()], bump = deck.bump)]
+    pub deck: Box<Account<'info, Deck>>,
+    
+    /// Registered comp defs, for the offset of the circuit queued
+    #[account(seeds = [CircuitConfig::SEED], bump = circuit_config.bump)]
+    pub circuit_config: Box<Account<'info, CircuitConfig>>,
+    
+    /// MXE program and PDAs the MXE accounts below are checked against
+    #[account(seeds = [ArciumConfig::SEED], bump = arcium_config.bump)]
+    pub arcium_config: Box<Account<'info, ArciumConfig>>,
+    
+    /// MXE program for encrypted computations
+    /// CHECK: Address constrained to the configured MXE program
+    #[account(
+        address = arcium_config.mxe_program_id
+            @ arcium::integration::ErrorCode::InvalidMxeAccount
+    )]
+    pub mxe_program: AccountInfo<'info>,
+    
+    /// MXE account for this program
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    #[account(mut)]
+    pub mxe_account: AccountInfo<'info>,
+    
+    /// Computation definition account of `reveal_community`, or of
+    /// `evaluate_showdown` for a private showdown
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    #[account(mut)]
+    pub comp_def_account: AccountInfo<'info>,
+    
+    /// Mempool account for queueing computations
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    #[account(mut)]
+    pub mempool_account: AccountInfo<'info>,
+    
+    /// Executing pool account
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    #[account(mut)]
+    pub executing_pool_account: AccountInfo<'info>,
+    
+    /// Cluster account
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    pub cluster_account: AccountInfo<'info>,
+    
+    /// Computation account (will be created)
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    #[account(mut)]
+    pub computation_account: AccountInfo<'info>,
+    
+    /// Sign seed PDA
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    pub sign_seed: AccountInfo<'info>,
+    
+    /// Staking pool PDA
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    pub staking_pool: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// Arcium clock account
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    pub clock: AccountInfo<'info>,
+    
+    // Remaining accounts (execute_private_showdown): every seat's PlayerState, in seat order
 }
 
 #[derive(Accounts)]
@@ -369,30 +935,193 @@ pub struct ExecuteShowdown<'info> {
     #[account(mut)]
     pub game: Account<'info, Game>,
     
+    /// Any seated player can call the showdown; pays for the computation
+    #[account(mut)]
+    pub player: Signer<'info>,
+    
+    /// Game's MXE-encrypted deck the hole cards are revealed from
+    #[account(seeds = [b"deck", game.key().as_ref()], bump = deck.bump)]
+    pub deck: Box<Account<'info, Deck>>,
+    
+    /// Registered comp defs, for the offset of `reveal_hole_cards`
+    #[account(seeds = [CircuitConfig::SEED], bump = circuit_config.bump)]
+    pub circuit_config: Box<Account<'info, CircuitConfig>>,
+    
+    /// MXE program and PDAs the MXE accounts below are checked against
+    #[account(seeds = [ArciumConfig::SEED], bump = arcium_config.bump)]
+    pub arcium_config: Box<Account<'info, ArciumConfig>>,
+    
+    /// MXE program for encrypted computations
+    /// CHECK: Address constrained to the configured MXE program
+    #[account(
+        address = arcium_config.mxe_program_id
+            @ arcium::integration::ErrorCode::InvalidMxeAccount
+    )]
+    pub mxe_program: AccountInfo<'info>,
+    
+    /// MXE account for this program
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    #[account(mut)]
+    pub mxe_account: AccountInfo<'info>,
+    
+    /// Computation definition account of `reveal_hole_cards`
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    #[account(mut)]
+    pub comp_def_account: AccountInfo<'info>,
+    
+    /// Mempool account for queueing computations
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    #[account(mut)]
+    pub mempool_account: AccountInfo<'info>,
+    
+    /// Executing pool account
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    #[account(mut)]
+    pub executing_pool_account: AccountInfo<'info>,
+    
+    /// Cluster account
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    pub cluster_account: AccountInfo<'info>,
+    
+    /// Computation account (will be created)
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    #[account(mut)]
+    pub computation_account: AccountInfo<'info>,
+    
+    /// Sign seed PDA
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    pub sign_seed: AccountInfo<'info>,
+    
+    /// Staking pool PDA
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    pub staking_pool: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// Arcium clock account
+    /// CHECK: PDA verified by `ArciumConfig::validate_mxe_accounts`
+    pub clock: AccountInfo<'info>,
+    
+    // Remaining accounts: every seat's PlayerState, in seat order
+}
+
+#[derive(Accounts)]
+pub struct AbortPendingComputation<'info> {
+    #[account(mut)]
+    pub game: Account<'info, Game>,
+    
+    /// Supplies the computation timeout
+    #[account(seeds = [ArciumConfig::SEED], bump = arcium_config.bump)]
+    pub arcium_config: Box<Account<'info, ArciumConfig>>,
+    
+    // Remaining accounts: every seat's PlayerState, in seat order
+}
+
+#[derive(Accounts)]
+pub struct InitArciumConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = ArciumConfig::LEN,
+        seeds = [ArciumConfig::SEED],
+        bump
+    )]
+    pub arcium_config: Box<Account<'info, ArciumConfig>>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    /// This program's ProgramData; its upgrade authority is the only admin
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key())
+            @ shared::PokerError::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateArciumConfig<'info> {
     #[account(
         mut,
-        seeds = [b"player", game.key().as_ref(), player.key().as_ref()],
-        bump = player_state.bump,
-        has_one = game,
-        has_one = player
+        seeds = [ArciumConfig::SEED],
+        bump = arcium_config.bump,
+        has_one = admin @ shared::PokerError::Unauthorized
     )]
-    pub player_state: Account<'info, PlayerState>,
+    pub arcium_config: Box<Account<'info, ArciumConfig>>,
     
-    pub player: Signer<'info>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitCircuitConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = CircuitConfig::LEN,
+        seeds = [CircuitConfig::SEED],
+        bump
+    )]
+    pub circuit_config: Box<Account<'info, CircuitConfig>>,
     
-    // Remaining accounts: Other PlayerState accounts for all players in showdown
+    /// Only the Arcium config admin may create the circuit config
+    #[account(
+        seeds = [ArciumConfig::SEED],
+        bump = arcium_config.bump,
+        constraint = arcium_config.admin == authority.key() @ shared::PokerError::Unauthorized
+    )]
+    pub arcium_config: Box<Account<'info, ArciumConfig>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
+#[instruction(circuit: arcium::Circuit)]
 pub struct InitCompDef<'info> {
+    /// Registry the circuit's comp def is recorded in
+    #[account(
+        mut,
+        seeds = [CircuitConfig::SEED],
+        bump = circuit_config.bump,
+        has_one = authority @ shared::PokerError::Unauthorized
+    )]
+    pub circuit_config: Box<Account<'info, CircuitConfig>>,
+    
+    /// MXE program and PDAs the comp def is created with
+    #[account(seeds = [ArciumConfig::SEED], bump = arcium_config.bump)]
+    pub arcium_config: Box<Account<'info, ArciumConfig>>,
+    
+    /// Arcium program that owns the comp def
+    /// CHECK: Address constrained to the configured MXE program
+    #[account(
+        address = arcium_config.mxe_program_id
+            @ arcium::integration::ErrorCode::InvalidMxeAccount
+    )]
+    pub mxe_program: AccountInfo<'info>,
+    
     /// MXE account
-    /// CHECK: PDA derived from program ID
-    #[account(mut)]
+    /// CHECK: Address constrained to the MXE account PDA
+    #[account(
+        mut,
+        address = arcium_config.mxe_account_address()
+            @ arcium::integration::ErrorCode::InvalidMxeAccount
+    )]
     pub mxe_account: AccountInfo<'info>,
     
     /// Computation definition account to initialize
-    /// CHECK: Will be created by Arcium
-    #[account(mut)]
+    /// CHECK: Must be the circuit's comp def PDA; created by Arcium
+    #[account(
+        mut,
+        address = arcium_config.comp_def_address(circuit.comp_def_offset())
+            @ arcium::integration::ErrorCode::InvalidMxeAccount
+    )]
     pub comp_def_account: AccountInfo<'info>,
     
     #[account(mut)]
@@ -406,11 +1135,36 @@ pub struct MxeCallback<'info> {
     #[account(mut)]
     pub game: Account<'info, Game>,
     
+    /// Game's Deck account, receives the MXE-encrypted deck
+    #[account(mut, seeds = [b"deck", game.key().as_ref()], bump = deck.bump)]
+    pub deck: Box<Account<'info, Deck>>,
+    
+    /// MXE program and PDAs the callback is checked against
+    #[account(seeds = [ArciumConfig::SEED], bump = arcium_config.bump)]
+    pub arcium_config: Box<Account<'info, ArciumConfig>>,
+    
+    /// Registered comp defs the callback is checked against
+    #[account(seeds = [CircuitConfig::SEED], bump = circuit_config.bump)]
+    pub circuit_config: Box<Account<'info, CircuitConfig>>,
+    
     /// MXE program calling back
-    /// CHECK: Verified as MXE program
+    /// CHECK: Address constrained to the configured MXE program
+    #[account(
+        address = arcium_config.mxe_program_id
+            @ arcium::integration::ErrorCode::InvalidMxeCallback
+    )]
     pub mxe_program: AccountInfo<'info>,
     
+    /// Computation definition of the circuit that produced the output
+    /// CHECK: Verified in `authenticate_callback` against the expected comp def
+    pub comp_def_account: AccountInfo<'info>,
+    
     /// Computation account with results
-    /// CHECK: Verified via computation ID
+    /// CHECK: Verified in `authenticate_callback` against the pending computation
     pub computation_account: AccountInfo<'info>,
+    
+    /// Instructions sysvar, used to check the Arcium program is the caller
+    /// CHECK: Address constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
 }
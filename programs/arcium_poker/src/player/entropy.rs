@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+use solana_keccak_hasher as keccak;
+use super::state::PlayerState;
+use crate::game::state::Game;
+use crate::types::GameStage;
+use crate::shared::{constants::*, PokerError};
+
+/// Commitment a player posts before revealing their shuffle entropy
+///
+/// Binds the preimage to the game and player, so one player can't copy
+/// another's commitment and then echo their reveal.
+pub fn entropy_commitment(preimage: &[u8; 32], game: &Pubkey, player: &Pubkey) -> [u8; 32] {
+    keccak::hashv(&[b"arcium-poker/entropy", game.as_ref(), player.as_ref(), preimage]).to_bytes()
+}
+
+/// Commit to shuffle entropy for the next hand
+///
+/// The first commitment opens the reveal window; commitments close as soon
+/// as anyone reveals, so nobody can pick entropy after seeing another's.
+pub fn commit_handler(ctx: Context<crate::PlayerAction>, commitment: [u8; 32]) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+    let player_state = &mut ctx.accounts.player_state;
+    
+    require!(
+        game.stage == GameStage::Waiting,
+        PokerError::GameAlreadyStarted
+    );
+    require!(commitment != [0u8; 32], PokerError::EntropyNotCommitted);
+    require!(
+        player_state.entropy_commitment == [0u8; 32],
+        PokerError::InvalidAction
+    );
+    
+    let now = Clock::get()?.unix_timestamp;
+    open_commit_window(game, now)?;
+    
+    player_state.entropy_commitment = commitment;
+    player_state.entropy_revealed = false;
+    
+    msg!(
+        "[ENTROPY] Player {} committed; reveals close at {}",
+        player_state.player,
+        game.entropy_deadline
+    );
+    
+    Ok(())
+}
+
+/// Reveal the preimage of an earlier commitment
+pub fn reveal_handler(ctx: Context<crate::PlayerAction>, preimage: [u8; 32]) -> Result<()> {
+    let game_key = ctx.accounts.game.key();
+    let game = &mut ctx.accounts.game;
+    let player_state = &mut ctx.accounts.player_state;
+    
+    require!(
+        game.stage == GameStage::Waiting,
+        PokerError::GameAlreadyStarted
+    );
+    
+    let now = Clock::get()?.unix_timestamp;
+    reveal(game, player_state, &game_key, preimage, now)?;
+    
+    msg!("[ENTROPY] Player {} revealed", player_state.player);
+    
+    Ok(())
+}
+
+/// Check commitments are still open, starting the window on the first one
+fn open_commit_window(game: &mut Game, now: i64) -> Result<()> {
+    require!(!game.entropy_commits_closed, PokerError::EntropyWindowClosed);
+    
+    if game.entropy_deadline == 0 {
+        game.entropy_deadline = now + ENTROPY_REVEAL_TIMEOUT;
+    }
+    require!(now < game.entropy_deadline, PokerError::EntropyWindowClosed);
+    
+    Ok(())
+}
+
+/// Accept a reveal that matches the player's commitment
+fn reveal(
+    game: &mut Game,
+    player_state: &mut PlayerState,
+    game_key: &Pubkey,
+    preimage: [u8; 32],
+    now: i64,
+) -> Result<()> {
+    require!(
+        player_state.entropy_commitment != [0u8; 32],
+        PokerError::EntropyNotCommitted
+    );
+    require!(!player_state.entropy_revealed, PokerError::InvalidAction);
+    require!(now <= game.entropy_deadline, PokerError::EntropyWindowClosed);
+    require!(
+        entropy_commitment(&preimage, game_key, &player_state.player)
+            == player_state.entropy_commitment,
+        PokerError::EntropyMismatch
+    );
+    
+    player_state.entropy = preimage;
+    player_state.entropy_revealed = true;
+    game.entropy_commits_closed = true;
+    
+    Ok(())
+}
+
+/// Revealed entropy for the shuffle, in seat order, and the seats it came
+/// from
+///
+/// Starts as soon as every committed player has revealed, or once the
+/// deadline passes; seats without a reveal by then are left out of the hand.
+pub fn collect_revealed_entropy(
+    game: &Game,
+    player_states: &[PlayerState],
+    now: i64,
+) -> Result<(Vec<[u8; 32]>, Vec<u8>)> {
+    require!(game.entropy_deadline != 0, PokerError::EntropyNotCommitted);
+    
+    let all_revealed = player_states.iter().all(|state| state.entropy_revealed);
+    require!(
+        all_revealed || now > game.entropy_deadline,
+        PokerError::EntropyRevealsPending
+    );
+    
+    let mut entropy = Vec::with_capacity(player_states.len());
+    let mut seats = Vec::with_capacity(player_states.len());
+    for state in player_states.iter().filter(|state| state.entropy_revealed) {
+        entropy.push(state.entropy);
+        seats.push(state.seat_index);
+    }
+    
+    require!(seats.len() >= MIN_PLAYERS, PokerError::NotEnoughPlayers);
+    
+    Ok((entropy, seats))
+}
+
+/// Close this hand's commit-reveal round once the shuffle has used it
+pub fn reset_entropy_round(game: &mut Game) {
+    game.entropy_deadline = 0;
+    game.entropy_commits_closed = false;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commitment_binds_game_and_player() {
+        let preimage = [42u8; 32];
+        let game = Pubkey::new_from_array([1; 32]);
+        let player = Pubkey::new_from_array([2; 32]);
+        let other = Pubkey::new_from_array([3; 32]);
+
+        let commitment = entropy_commitment(&preimage, &game, &player);
+        assert_eq!(commitment, entropy_commitment(&preimage, &game, &player));
+        assert_ne!(commitment, entropy_commitment(&[43u8; 32], &game, &player));
+        assert_ne!(commitment, entropy_commitment(&preimage, &game, &other));
+        assert_ne!(commitment, entropy_commitment(&preimage, &other, &player));
+    }
+}
@@ -6,7 +6,10 @@ use crate::types::GameStage;
 use crate::shared::{PokerError, validate_buy_in};
 
 /// Player joins a poker game
-pub fn handler(ctx: Context<crate::JoinGame>, buy_in: u64) -> Result<()> {
+///
+/// `encryption_pubkey` is the player's x25519 public key; the deal circuit
+/// encrypts their hole cards to it.
+pub fn handler(ctx: Context<crate::JoinGame>, buy_in: u64, encryption_pubkey: [u8; 32]) -> Result<()> {
     let game = &mut ctx.accounts.game;
     let player_state = &mut ctx.accounts.player_state;
     
@@ -19,8 +22,14 @@ pub fn handler(ctx: Context<crate::JoinGame>, buy_in: u64) -> Result<()> {
     // Validate buy-in amount
     validate_buy_in(buy_in, game.min_buy_in, game.max_buy_in)?;
     
+    // An all-zero key would leave hole cards readable by anyone
+    require!(
+        encryption_pubkey != [0u8; 32],
+        PokerError::InvalidEncryptionKey
+    );
+    
     // Add player to game and get seat index
-    let seat_index = game.add_player(ctx.accounts.player.key())?;
+    let seat_index = game.add_player(ctx.accounts.player.key(), encryption_pubkey)?;
     
     // Store values we need for later (before transfers)
     let game_key = game.key();
@@ -45,6 +54,7 @@ pub fn handler(ctx: Context<crate::JoinGame>, buy_in: u64) -> Result<()> {
     player_state.initialize(
         player_key,
         game_key,
+        encryption_pubkey,
         seat_index,
         buy_in,
         ctx.bumps.player_state,
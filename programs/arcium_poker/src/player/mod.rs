@@ -2,11 +2,14 @@ pub mod state;
 pub mod join;
 pub mod leave;
 pub mod actions;
+pub mod entropy;
 
 pub use state::*;
 
 // Export the handler functions
 pub use join::handler as join_handler;
 pub use leave::handler as leave_handler;
+pub use entropy::commit_handler as commit_entropy_handler;
+pub use entropy::reveal_handler as reveal_entropy_handler;
 
 // Note: JoinGame and LeaveGame structs are now in lib.rs at crate root
\ No newline at end of file
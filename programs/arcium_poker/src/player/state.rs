@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
-use crate::types::PlayerStatus;
-use crate::shared::constants::HOLE_CARDS;
+use crate::types::{EncryptedHoleCard, PlayerStatus};
+use crate::shared::{constants::MAX_HOLE_CARDS, PokerError};
 
 /// Player state account (PDA per player per game)
 #[account]
@@ -11,6 +11,9 @@ pub struct PlayerState {
     /// Game this player belongs to
     pub game: Pubkey,
     
+    /// x25519 public key hole cards are encrypted to
+    pub encryption_pubkey: [u8; 32],
+    
     /// Player's seat index in the game
     pub seat_index: u8,
     
@@ -26,8 +29,11 @@ pub struct PlayerState {
     /// Total contribution to pot this hand
     pub total_bet_this_hand: u64,
     
-    /// Encrypted hole cards (indices in deck)
-    pub encrypted_hole_cards: [u8; HOLE_CARDS],
+    /// Deck positions of the hole cards, one per card the variant deals
+    pub hole_card_positions: Vec<u8>,
+    
+    /// Hole cards encrypted to `encryption_pubkey`, parallel to `hole_card_positions`
+    pub hole_cards: Vec<EncryptedHoleCard>,
     
     /// Has cards been dealt to this player
     pub has_cards: bool,
@@ -38,6 +44,15 @@ pub struct PlayerState {
     /// Player is all-in
     pub is_all_in: bool,
     
+    /// Commitment to this player's shuffle entropy (zero = not committed)
+    pub entropy_commitment: [u8; 32],
+    
+    /// Shuffle entropy, once revealed and checked against the commitment
+    pub entropy: [u8; 32],
+    
+    /// `entropy` has been revealed
+    pub entropy_revealed: bool,
+    
     /// Timestamp when player joined
     pub joined_at: i64,
     
@@ -53,15 +68,20 @@ impl PlayerState {
     pub const LEN: usize = 8 + // discriminator
         32 + // player
         32 + // game
+        32 + // encryption_pubkey
         1 + // seat_index
         1 + // status
         8 + // chip_stack
         8 + // current_bet
         8 + // total_bet_this_hand
-        (1 * HOLE_CARDS) + // encrypted_hole_cards
+        (4 + MAX_HOLE_CARDS) + // hole_card_positions
+        (4 + EncryptedHoleCard::LEN * MAX_HOLE_CARDS) + // hole_cards
         1 + // has_cards
         1 + // has_folded
         1 + // is_all_in
+        32 + // entropy_commitment
+        32 + // entropy
+        1 + // entropy_revealed
         8 + // joined_at
         8 + // last_action_at
         1; // bump
@@ -71,21 +91,27 @@ impl PlayerState {
         &mut self,
         player: Pubkey,
         game: Pubkey,
+        encryption_pubkey: [u8; 32],
         seat_index: u8,
         buy_in: u64,
         bump: u8,
     ) {
         self.player = player;
         self.game = game;
+        self.encryption_pubkey = encryption_pubkey;
         self.seat_index = seat_index;
         self.status = PlayerStatus::Waiting;
         self.chip_stack = buy_in;
         self.current_bet = 0;
         self.total_bet_this_hand = 0;
-        self.encrypted_hole_cards = [0; HOLE_CARDS];
+        self.hole_card_positions = Vec::new();
+        self.hole_cards = Vec::new();
         self.has_cards = false;
         self.has_folded = false;
         self.is_all_in = false;
+        self.entropy_commitment = [0; 32];
+        self.entropy = [0; 32];
+        self.entropy_revealed = false;
         self.joined_at = Clock::get().unwrap().unix_timestamp;
         self.last_action_at = Clock::get().unwrap().unix_timestamp;
         self.bump = bump;
@@ -128,7 +154,8 @@ impl PlayerState {
     pub fn reset_for_new_hand(&mut self) {
         self.current_bet = 0;
         self.total_bet_this_hand = 0;
-        self.encrypted_hole_cards = [0; HOLE_CARDS];
+        self.hole_card_positions.clear();
+        self.hole_cards.clear();
         self.has_cards = false;
         self.has_folded = false;
         self.is_all_in = false;
@@ -138,8 +165,62 @@ impl PlayerState {
         }
     }
     
+    /// Forget this hand's entropy once the shuffle has used it
+    pub fn clear_entropy(&mut self) {
+        self.entropy_commitment = [0; 32];
+        self.entropy = [0; 32];
+        self.entropy_revealed = false;
+    }
+    
     /// Add winnings
     pub fn add_winnings(&mut self, amount: u64) {
         self.chip_stack += amount;
     }
-}
\ No newline at end of file
+}
+
+/// Load every seat's PlayerState from remaining accounts, in seat order
+pub fn load_player_states(
+    game: Pubkey,
+    player_count: u8,
+    accounts: &[AccountInfo],
+) -> Result<Vec<PlayerState>> {
+    require!(
+        accounts.len() >= player_count as usize,
+        PokerError::PlayerNotInGame
+    );
+    
+    accounts
+        .iter()
+        .take(player_count as usize)
+        .enumerate()
+        .map(|(seat, account)| load_player_state(game, seat as u8, account))
+        .collect()
+}
+
+/// Load one seat's PlayerState from an account
+pub fn load_player_state(game: Pubkey, seat: u8, account: &AccountInfo) -> Result<PlayerState> {
+    require!(account.owner == &crate::ID, PokerError::PlayerNotInGame);
+    
+    let data = account.try_borrow_data()?;
+    let player_state = PlayerState::try_deserialize(&mut &data[..])?;
+    require!(
+        player_state.game == game && player_state.seat_index == seat,
+        PokerError::PlayerNotInGame
+    );
+    
+    Ok(player_state)
+}
+
+/// Write PlayerStates loaded by `load_player_states` back to their accounts
+pub fn store_player_states(
+    accounts: &[AccountInfo],
+    player_states: &[PlayerState],
+) -> Result<()> {
+    for (account, player_state) in accounts.iter().zip(player_states) {
+        let mut data = account.try_borrow_mut_data()?;
+        let mut writer = &mut data[..];
+        player_state.try_serialize(&mut writer)?;
+    }
+    
+    Ok(())
+}
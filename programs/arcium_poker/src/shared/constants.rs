@@ -1,21 +1,51 @@
-/// Maximum number of players per game
-pub const MAX_PLAYERS: usize = 6;
+/// Maximum number of players per game (shared with the circuits)
+pub use poker_shared::MAX_PLAYERS;
 
 /// Minimum number of players to start
 pub const MIN_PLAYERS: usize = 2;
 
-/// Number of hole cards per player
+/// Number of hole cards per player (Texas Hold'em)
 pub const HOLE_CARDS: usize = 2;
 
+/// Maximum hole cards any variant deals to a player (6-card Omaha)
+pub const MAX_HOLE_CARDS: usize = 6;
+
 /// Number of community cards
 pub const COMMUNITY_CARDS: usize = 5;
 
+/// Face-down cards per player in seven-card stud (two on third street, one on seventh)
+pub const STUD_DOWN_CARDS: usize = 3;
+
+/// Face-up cards per player in seven-card stud (third through sixth street)
+pub const STUD_UP_CARDS: usize = 4;
+
+/// Cards per hand in draw games
+pub const DRAW_HAND_CARDS: usize = 5;
+
+/// Filler for card slots that hold no card
+pub const NO_CARD: u8 = u8::MAX;
+
 /// Total cards in deck
 pub const DECK_SIZE: usize = 52;
 
+/// Cards in a short deck (sixes through aces)
+pub const SHORT_DECK_SIZE: usize = 36;
+
+/// Lowest rank value kept in a short deck
+pub const SHORT_DECK_LOWEST_RANK: u8 = 6;
+
 /// Turn timeout in seconds
 pub const TURN_TIMEOUT: i64 = 60;
 
+/// Seconds from the first entropy commitment until reveals close
+pub const ENTROPY_REVEAL_TIMEOUT: i64 = 120;
+
+/// Bets allowed per fixed-limit betting round (a bet and three raises)
+pub const FIXED_LIMIT_MAX_BETS: u8 = 4;
+
+/// Maximum games in a mixed-game rotation (8-Game)
+pub const MAX_ROTATION_GAMES: usize = 8;
+
 /// Minimum raise multiplier
 pub const MIN_RAISE_MULTIPLIER: u64 = 2;
 
@@ -70,4 +70,52 @@ pub enum PokerError {
     
     #[msg("Game has not finished")]
     GameNotFinished,
+    
+    #[msg("Not enough cards left in the deck")]
+    DeckExhausted,
+    
+    #[msg("Invalid discard selection")]
+    InvalidDiscard,
+    
+    #[msg("Invalid x25519 encryption key")]
+    InvalidEncryptionKey,
+    
+    #[msg("Community cards are still being revealed")]
+    CommunityRevealPending,
+    
+    #[msg("Private showdown is still being evaluated")]
+    ShowdownPending,
+    
+    #[msg("Entropy has not been committed")]
+    EntropyNotCommitted,
+    
+    #[msg("Revealed entropy does not match the commitment")]
+    EntropyMismatch,
+    
+    #[msg("Entropy commitments or reveals are closed")]
+    EntropyWindowClosed,
+    
+    #[msg("Waiting for players to reveal their entropy")]
+    EntropyRevealsPending,
+    
+    #[msg("MXE accounts are required by the Arcium MPC backend")]
+    MxeAccountsMissing,
+    
+    #[msg("MPC result arrives via callback and is not available yet")]
+    MpcResultPending,
+    
+    #[msg("Callback does not answer the computation this game is waiting for")]
+    UnexpectedComputation,
+    
+    #[msg("Signer is not the config authority")]
+    Unauthorized,
+    
+    #[msg("No MPC computation is pending")]
+    NoPendingComputation,
+    
+    #[msg("Pending MPC computation has not timed out yet")]
+    ComputationNotTimedOut,
+    
+    #[msg("A contender is all-in for less; the private showdown cannot settle side pots")]
+    PrivateShowdownSidePots,
 }
\ No newline at end of file
@@ -1,19 +1,68 @@
 use anchor_lang::prelude::*;
 use crate::game::state::Game;
-use crate::player::state::PlayerState;
-use crate::cards::deck::Card;
-use crate::arcium::mpc_reveal::{mpc_reveal_card, RevealParams};
+use crate::player::state::{load_player_states, store_player_states, PlayerState};
+use crate::cards::deck::{Card, Deck};
+use crate::cards::codec::{CardSet, format_cards};
+use crate::arcium::backend::{mxe_accounts, MxeQueue};
+use crate::arcium::comp_defs::Circuit;
+use crate::arcium::mpc_deal::EncryptedCard;
+use crate::arcium::mpc_reveal::{mpc_reveal_card_with_mxe, record_shown_cards, MxeRevealParams};
 use crate::betting::pot_manager::PotManager;
+use crate::betting::state::SidePot;
 use crate::types::GameStage;
-use crate::shared::PokerError;
+use crate::shared::{constants::*, PokerError};
 use super::winner::evaluate_and_determine_winners;
 use super::payout::distribute_winnings;
+use super::private::has_side_pots;
+
+/// Show the next seat's hand for `execute_showdown`
+///
+/// Remaining accounts: every seat's PlayerState, in seat order.
+pub fn handler(ctx: Context<crate::ExecuteShowdown>) -> Result<()> {
+    let game_key = ctx.accounts.game.key();
+    let player_count = ctx.accounts.game.player_count;
+    require!(
+        ctx.accounts.game.has_player(&ctx.accounts.player.key()),
+        PokerError::PlayerNotInGame
+    );
+    let mut player_states = load_player_states(game_key, player_count, ctx.remaining_accounts)?;
+    
+    // The reveal callback pays out to the seats' PlayerStates
+    let mut callback_accounts = vec![game_key, ctx.accounts.deck.key()];
+    callback_accounts.extend(
+        ctx.remaining_accounts
+            .iter()
+            .take(player_count as usize)
+            .map(|account| account.key()),
+    );
+    let mxe = MxeQueue {
+        accounts: mxe_accounts!(ctx.accounts, player),
+        arcium_config: &ctx.accounts.arcium_config,
+        circuit_config: &ctx.accounts.circuit_config,
+        callback_accounts,
+    };
+    
+    handle_showdown(
+        &mut ctx.accounts.game,
+        &ctx.accounts.deck,
+        Some(&mxe),
+        &mut player_states,
+    )?;
+    
+    store_player_states(ctx.remaining_accounts, &player_states)
+}
 
 /// Handle showdown - reveal cards and determine winners
+///
+/// Every seat still in the hand shows its cards through `reveal_hole_cards`,
+/// one seat per computation. The mock backend reveals them all at once and
+/// settles; on Arcium each call queues the next seat's reveal, and
+/// `handle_reveal_callback` settles once the last seat has shown.
 pub fn handle_showdown(
     game: &mut Game,
+    deck: &Deck,
+    mxe: Option<&MxeQueue>,
     player_states: &mut [PlayerState],
-    pot_manager: &PotManager,
 ) -> Result<()> {
     // Validate game is in showdown stage
     require!(
@@ -21,42 +70,102 @@ pub fn handle_showdown(
         PokerError::InvalidGameStage
     );
     
+    // Private tables settle through `handle_private_showdown`; revealing
+    // here would show the losing hands. Side pots need every hand ranked,
+    // so those hands are shown.
+    require!(
+        !game.private_showdown || has_side_pots(game, player_states),
+        PokerError::InvalidAction
+    );
+    require!(!game.has_pending_computation(), PokerError::MpcResultPending);
+    
     msg!("[SHOWDOWN] Starting showdown for game {}", game.game_id);
     
-    // Collect player hole cards (would be revealed via Arcium MPC)
-    // For MVP, we assume cards are already decrypted
-    let mut player_hole_cards = Vec::new();
+    while let Some(seat) = next_seat_to_show(game, player_states) {
+        let player_state = &player_states[seat];
+        let computation_offset = game.next_computation_offset();
+        let (accounts, comp_def_offset) = match mxe {
+            Some(queue) => {
+                let (accounts, offset) = queue.accounts_for(Circuit::RevealHoleCards, computation_offset)?;
+                (Some(accounts), offset)
+            }
+            None => (None, Circuit::RevealHoleCards.comp_def_offset()),
+        };
+        
+        let revealed = mpc_reveal_card_with_mxe(MxeRevealParams {
+            mxe: accounts,
+            encrypted_cards: showdown_cards(game, player_state)?,
+            requester: player_state.player,
+            deck,
+            computation_offset,
+            comp_def_offset,
+            callback_accounts: mxe.map(|queue| queue.callback_accounts.clone()).unwrap_or_default(),
+            is_showdown: true,
+        })?;
+        
+        game.reveal_seat = seat as u8;
+        match revealed {
+            Some(cards) => record_shown_cards(game, &cards)?,
+            None => {
+                game.await_computation(computation_offset, comp_def_offset, Clock::get()?.unix_timestamp);
+                return Ok(());
+            }
+        }
+    }
+    
+    settle_public_showdown(game, player_states)
+}
+
+/// Evaluate the shown hands and pay every pot, once every seat still in the
+/// hand has shown; until then the next `execute_showdown` reveals the next seat
+pub fn settle_public_showdown(game: &mut Game, player_states: &mut [PlayerState]) -> Result<()> {
+    if next_seat_to_show(game, player_states).is_some() {
+        return Ok(());
+    }
     
-    for i in 0..game.player_count as usize {
-        if !game.active_players[i] {
+    let mut player_hole_cards = Vec::new();
+    for seat in 0..game.player_count as usize {
+        if !is_contender(game, &player_states[seat]) {
             continue;
         }
         
-        let player_state = &player_states[i];
+        let mut hole_cards = game.shown_cards[seat]
+            .iter()
+            .take_while(|&&index| index != NO_CARD)
+            .map(|&index| Card::from_index_for_variant(index, game.variant))
+            .collect::<Result<Vec<Card>>>()?;
         
-        // Skip folded players
-        if player_state.has_folded {
-            continue;
+        if game.variant.is_stud() {
+            hole_cards = collect_stud_cards(game, &player_states[seat], hole_cards)?;
         }
         
-        // Use Arcium MPC to reveal encrypted hole cards
-        let hole_cards = reveal_player_cards(
-            player_state,
-            game.shuffle_session_id,
-            player_state.player,
-        )?;
-        
-        player_hole_cards.push((i as u8, hole_cards));
+        msg!("[SHOWDOWN] Seat {} shows {}", seat, format_cards(&hole_cards));
+        player_hole_cards.push((seat as u8, hole_cards));
     }
     
-    // Get community cards
-    let mut community_cards = [Card::from_index(0)?; 5];
-    for i in 0..5 {
-        community_cards[i] = Card::from_index(game.community_cards[i])?;
+    // Get community cards (none in stud)
+    let community_cards = game.community_cards[..game.variant.community_cards()]
+        .iter()
+        .map(|&index| Card::from_index_for_variant(index, game.variant))
+        .collect::<Result<Vec<Card>>>()?;
+    if !community_cards.is_empty() {
+        msg!("[SHOWDOWN] Board: {}", format_cards(&community_cards));
     }
     
-    // Evaluate hands and determine winners
+    // Every card shown must appear once; a repeat means the deal or a
+    // reveal went wrong, and the hand cannot be settled on it
+    let shown: Vec<Card> = player_hole_cards
+        .iter()
+        .flat_map(|(_, cards)| cards.iter())
+        .chain(&community_cards)
+        .copied()
+        .collect();
+    CardSet::from_unique(&shown)?;
+    
+    // Evaluate hands and determine winners, pot by pot
+    let pot_manager = showdown_pots(game, player_states)?;
     let winners = evaluate_and_determine_winners(
+        game.variant,
         &player_hole_cards,
         &community_cards,
         pot_manager.main_pot,
@@ -75,34 +184,117 @@ pub fn handle_showdown(
     Ok(())
 }
 
-/// Reveal player's hole cards using Arcium MPC
-pub fn reveal_player_cards(
-    player_state: &PlayerState,
-    session_id: [u8; 32],
-    requester: Pubkey,
-) -> Result<[Card; 2]> {
-    let mut revealed_cards = [Card::from_index(0)?; 2];
-    
-    for i in 0..2 {
-        // Create encrypted card from player state
-        let encrypted_card = crate::arcium::mpc_deal::EncryptedCard {
-            encrypted_index: player_state.encrypted_hole_cards[i],
-            key_shard: [0; 32], // Would be stored separately
+/// Split the hand's bets into the pots the showdown awards
+///
+/// Every seat's `total_bet_this_hand` goes in, folded or not. Each all-in
+/// contender caps a side pot; whatever lies above the last cap is the main
+/// pot, which only the contenders who put chips that high can win.
+pub fn showdown_pots(game: &Game, player_states: &[PlayerState]) -> Result<PotManager> {
+    let mut pot_manager = PotManager::new();
+    let mut all_in = [false; MAX_PLAYERS];
+    let mut contenders = [false; MAX_PLAYERS];
+    
+    for player_state in player_states {
+        let seat = player_state.seat_index as usize;
+        pot_manager.add_bet(seat, player_state.total_bet_this_hand);
+        contenders[seat] = game.active_players[seat] && !player_state.has_folded;
+        all_in[seat] = contenders[seat] && player_state.is_all_in;
+    }
+    require!(pot_manager.main_pot == game.pot, PokerError::InvalidBetAmount);
+    
+    pot_manager.calculate_side_pots(player_states.len(), &all_in, &contenders)?;
+    if pot_manager.side_pot_count == 0 || pot_manager.main_pot == 0 {
+        return Ok(pot_manager);
+    }
+    
+    // Winners treat the main pot as open to every contender, so once side
+    // pots exist it becomes one more pot for the seats above the last cap
+    let cap = (0..player_states.len())
+        .filter(|&seat| all_in[seat])
+        .map(|seat| pot_manager.player_contributions[seat])
+        .max()
+        .unwrap_or(0);
+    let mut top_pot = SidePot::new(pot_manager.main_pot);
+    for seat in 0..player_states.len() {
+        if contenders[seat] && pot_manager.player_contributions[seat] > cap {
+            top_pot.add_eligible_player(seat);
+        }
+    }
+    
+    let last = pot_manager.side_pot_count as usize - 1;
+    if top_pot.player_count == 0 {
+        // Only folded chips sit above the cap: they go to the last side pot
+        pot_manager.side_pots[last].amount += pot_manager.main_pot;
+    } else {
+        require!(last + 1 < pot_manager.side_pots.len(), PokerError::InvalidGameConfig);
+        pot_manager.side_pots[last + 1] = top_pot;
+        pot_manager.side_pot_count += 1;
+    }
+    pot_manager.main_pot = 0;
+    
+    Ok(pot_manager)
+}
+
+/// Seat still in the hand
+fn is_contender(game: &Game, player_state: &PlayerState) -> bool {
+    game.active_players[player_state.seat_index as usize] && !player_state.has_folded
+}
+
+/// First seat still in the hand that has not shown its cards yet
+fn next_seat_to_show(game: &Game, player_states: &[PlayerState]) -> Option<usize> {
+    (0..game.player_count as usize).find(|&seat| {
+        is_contender(game, &player_states[seat]) && game.shown_cards[seat][0] == NO_CARD
+    })
+}
+
+/// Cards a seat shows: its hole cards and, in stud, the seventh-street down
+/// card (the upcards are already public)
+fn showdown_cards(game: &Game, player_state: &PlayerState) -> Result<Vec<EncryptedCard>> {
+    let mut cards: Vec<EncryptedCard> = player_state
+        .hole_card_positions
+        .iter()
+        .zip(&player_state.hole_cards)
+        .map(|(&position, hole_card)| EncryptedCard {
+            encrypted_index: position,
+            ciphertext: hole_card.ciphertext,
+            nonce: hole_card.nonce,
             owner: player_state.player,
-        };
-        
-        // Reveal using Arcium MPC
-        let reveal_params = RevealParams {
-            encrypted_card,
-            requester,
-            session_id,
-            is_showdown: true,
-        };
-        
-        revealed_cards[i] = mpc_reveal_card(reveal_params)?;
+        })
+        .collect();
+    
+    if game.variant.is_stud() {
+        let seat = player_state.seat_index as usize;
+        require!(game.seventh_street_cards[seat] != NO_CARD, PokerError::CardsNotDealt);
+        cards.push(EncryptedCard {
+            encrypted_index: game.seventh_street_cards[seat],
+            ciphertext: game.seventh_street_hole_cards[seat].ciphertext,
+            nonce: game.seventh_street_hole_cards[seat].nonce,
+            owner: player_state.player,
+        });
+    }
+    
+    require!(!cards.is_empty(), PokerError::CardsNotDealt);
+    Ok(cards)
+}
+
+/// Complete a stud player's seven cards in dealing order:
+/// two down, four up (already public), then the seventh-street down card
+fn collect_stud_cards(
+    game: &Game,
+    player_state: &PlayerState,
+    shown: Vec<Card>,
+) -> Result<Vec<Card>> {
+    let seat = player_state.seat_index as usize;
+    let down_cards = player_state.hole_card_positions.len();
+    require!(shown.len() == down_cards + 1, PokerError::CardsNotDealt);
+    
+    let mut cards = shown[..down_cards].to_vec();
+    for &up_card in &game.up_cards[seat] {
+        cards.push(Card::from_index(up_card)?);
     }
+    cards.push(shown[down_cards]);
     
-    Ok(revealed_cards)
+    Ok(cards)
 }
 
 /// Allow player to muck (fold without showing)
@@ -118,4 +310,146 @@ pub fn handle_muck(
     );
     
     Ok(())
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arcium::mpc_reveal::handle_reveal_callback;
+    use crate::arcium::mpc_shuffle::mock_deck_account;
+    use crate::cards::codec::{self, parse_cards};
+    use crate::types::EncryptedHoleCard;
+
+    fn seat(seat_index: u8, total_bet_this_hand: u64, is_all_in: bool, has_folded: bool) -> PlayerState {
+        let mut player_state = PlayerState::deserialize(&mut &[0u8; PlayerState::LEN][..]).unwrap();
+        player_state.seat_index = seat_index;
+        player_state.total_bet_this_hand = total_bet_this_hand;
+        player_state.is_all_in = is_all_in;
+        player_state.has_folded = has_folded;
+        player_state
+    }
+
+    fn table(player_states: &[PlayerState]) -> Game {
+        let mut game = Game::deserialize(&mut &[0u8; Game::LEN][..]).unwrap();
+        game.player_count = player_states.len() as u8;
+        game.active_players[..player_states.len()].fill(true);
+        game.pot = player_states.iter().map(|player_state| player_state.total_bet_this_hand).sum();
+        game
+    }
+
+    #[test]
+    fn test_showdown_pots_cap_short_all_ins() {
+        // Seat 0 all-in for 50, seat 1 all-in for 120, seat 2 folded after 80,
+        // seat 3 covered everyone
+        let player_states = vec![
+            seat(0, 50, true, false),
+            seat(1, 120, true, false),
+            seat(2, 80, false, true),
+            seat(3, 200, false, false),
+        ];
+        let pots = showdown_pots(&table(&player_states), &player_states).unwrap();
+
+        assert_eq!(pots.main_pot, 0);
+        assert_eq!(pots.side_pot_count, 3);
+        assert_eq!(pots.side_pots[0].amount, 200);
+        assert_eq!(pots.side_pots[0].eligible_players[..4], [true, true, false, true]);
+        assert_eq!(pots.side_pots[1].amount, 70 + 30 + 70);
+        assert_eq!(pots.side_pots[1].eligible_players[..4], [false, true, false, true]);
+        assert_eq!(pots.side_pots[2].amount, 80);
+        assert_eq!(pots.side_pots[2].eligible_players[..4], [false, false, false, true]);
+        assert_eq!(pots.get_total_pot(), 450);
+    }
+
+    #[test]
+    fn test_showdown_pots_without_all_ins_is_one_pot() {
+        let player_states = vec![seat(0, 100, false, false), seat(1, 60, false, true), seat(2, 100, false, false)];
+        let pots = showdown_pots(&table(&player_states), &player_states).unwrap();
+
+        assert_eq!((pots.main_pot, pots.side_pot_count), (260, 0));
+
+        // The pot must match what the seats put in
+        let mut game = table(&player_states);
+        game.pot += 1;
+        assert!(showdown_pots(&game, &player_states).is_err());
+    }
+
+    fn indices(cards: &str) -> Vec<u8> {
+        parse_cards(cards).unwrap().iter().map(codec::encode).collect()
+    }
+
+    /// Hold'em table at showdown; hands are dealt from a deck whose
+    /// position p holds card index p
+    fn showdown_table(player_states: &mut [PlayerState], hands: &[&str], board: &str) -> Game {
+        for (player_state, hand) in player_states.iter_mut().zip(hands) {
+            player_state.hole_card_positions = indices(hand);
+            player_state.hole_cards = vec![EncryptedHoleCard::default(); player_state.hole_card_positions.len()];
+        }
+
+        let mut game = table(player_states);
+        game.stage = GameStage::Showdown;
+        game.shown_cards = [[NO_CARD; MAX_HOLE_CARDS]; MAX_PLAYERS];
+        game.community_cards.copy_from_slice(&indices(board));
+        game
+    }
+
+    #[test]
+    fn test_public_showdown_shows_every_contender_and_pays_each_pot() {
+        let deck = mock_deck_account(&core::array::from_fn(|position| position as u8));
+        let mut player_states = vec![
+            seat(0, 50, true, false),
+            seat(1, 150, false, false),
+            seat(2, 150, false, false),
+            seat(3, 40, false, true),
+        ];
+        let mut game = showdown_table(
+            &mut player_states,
+            &["As Ah", "4d 5d", "Qh Qd", "Kc Kd"],
+            "2c 7d 9h Js 3c",
+        );
+        game.active_players[3] = false;
+
+        handle_showdown(&mut game, &deck, None, &mut player_states).unwrap();
+
+        assert_eq!(game.stage, GameStage::Finished);
+        assert_eq!(game.shown_cards[2][..2], indices("Qh Qd")[..]);
+        assert_eq!(game.shown_cards[3][0], NO_CARD);
+
+        // Aces win the pot the all-in capped (folded chips included),
+        // queens the rest
+        let stacks: Vec<u64> = player_states.iter().map(|player_state| player_state.chip_stack).collect();
+        assert_eq!(stacks, vec![190, 0, 200, 0]);
+        assert_eq!(game.pot, 0);
+    }
+
+    #[test]
+    fn test_last_reveal_callback_settles_the_showdown() {
+        let mut player_states = vec![seat(0, 100, false, false), seat(1, 100, false, false)];
+        let mut game = showdown_table(&mut player_states, &["As Ah", "4d 5d"], "2c 7d 9h Js 3c");
+        let shown = |hand: &str| {
+            let mut cards = vec![NO_CARD; MAX_HOLE_CARDS];
+            cards[..2].copy_from_slice(&indices(hand));
+            cards
+        };
+
+        // Seat 1 still has to show
+        game.reveal_seat = 0;
+        handle_reveal_callback(&mut game, &mut player_states, [0; 32], shown("As Ah")).unwrap();
+        assert_eq!((game.stage, game.pot), (GameStage::Showdown, 200));
+
+        game.reveal_seat = 1;
+        handle_reveal_callback(&mut game, &mut player_states, [0; 32], shown("4d 5d")).unwrap();
+        assert_eq!(game.stage, GameStage::Finished);
+        assert_eq!((player_states[0].chip_stack, player_states[1].chip_stack), (200, 0));
+    }
+
+    #[test]
+    fn test_showdown_fails_on_a_card_shown_twice() {
+        let deck = mock_deck_account(&core::array::from_fn(|position| position as u8));
+        let mut player_states = vec![seat(0, 100, false, false), seat(1, 100, false, false)];
+
+        // Seat 1's ace of spades is also on the board
+        let mut game = showdown_table(&mut player_states, &["Kh Kd", "As 5d"], "As 7d 9h Js 3c");
+
+        assert!(handle_showdown(&mut game, &deck, None, &mut player_states).is_err());
+        assert_eq!(player_states[0].chip_stack + player_states[1].chip_stack, 0);
+    }
+}
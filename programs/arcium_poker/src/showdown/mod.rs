@@ -2,15 +2,24 @@
 pub mod instruction;
 pub mod winner;
 pub mod payout;
+pub mod private;
 
 // Export specific items
-pub use instruction::{handle_showdown, reveal_player_cards, handle_muck};
+pub use instruction::{handler as public_showdown_handler, handle_showdown, settle_public_showdown, handle_muck};
+pub use private::{handler as private_showdown_handler, handle_private_showdown, has_side_pots, apply_showdown_outcome};
 pub use winner::{
     PotWinner,
+    PotHalf,
+    HiLoHand,
     determine_main_pot_winners,
     determine_side_pot_winners,
     determine_all_winners,
     evaluate_and_determine_winners,
+    determine_hi_lo_pot_winners,
+    determine_hi_lo_side_pot_winners,
+    determine_all_hi_lo_winners,
+    determine_lowball_pot_winners,
+    determine_all_lowball_winners,
 };
 pub use payout::{
     distribute_winnings,
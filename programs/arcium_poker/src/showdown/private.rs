@@ -0,0 +1,246 @@
+use anchor_lang::prelude::*;
+use crate::game::state::Game;
+use crate::game::events::PrivateShowdownResolved;
+use crate::player::state::{load_player_states, store_player_states, PlayerState};
+use crate::arcium::backend::{mxe_accounts, MxeQueue};
+use crate::arcium::comp_defs::Circuit;
+use crate::cards::deck::Deck;
+use crate::arcium::mpc_showdown::{
+    mpc_evaluate_showdown_with_mxe, score_hand_rank, MxeShowdownParams, ShowdownOutcome,
+    SHOWDOWN_HOLE_CARDS,
+};
+use crate::types::GameStage;
+use crate::shared::{constants::*, PokerError};
+use super::payout::distribute_winnings;
+
+/// Queue the private showdown for `execute_private_showdown`
+///
+/// Remaining accounts: every seat's PlayerState, in seat order.
+pub fn handler(ctx: Context<crate::AdvanceStage>) -> Result<()> {
+    let game_key = ctx.accounts.game.key();
+    let player_count = ctx.accounts.game.player_count;
+    let mut player_states = load_player_states(game_key, player_count, ctx.remaining_accounts)?;
+    
+    // The showdown callback pays out to the seats' PlayerStates
+    let mut callback_accounts = vec![game_key, ctx.accounts.deck.key()];
+    callback_accounts.extend(
+        ctx.remaining_accounts
+            .iter()
+            .take(player_count as usize)
+            .map(|account| account.key()),
+    );
+    let mxe = MxeQueue {
+        accounts: mxe_accounts!(ctx.accounts, signer),
+        arcium_config: &ctx.accounts.arcium_config,
+        circuit_config: &ctx.accounts.circuit_config,
+        callback_accounts,
+    };
+    
+    handle_private_showdown(
+        &mut ctx.accounts.game,
+        &ctx.accounts.deck,
+        Some(&mxe),
+        &mut player_states,
+    )?;
+    
+    store_player_states(ctx.remaining_accounts, &player_states)
+}
+
+/// Settle the showdown inside MPC without revealing any hole cards
+///
+/// Queues `evaluate_showdown` for every seat still in the hand. The mock
+/// backend applies the outcome at once; on Arcium the pot stays put until
+/// `handle_showdown_callback` delivers the winners.
+///
+/// The circuit only names the best hands, so it cannot settle side pots: a
+/// hand with a contender all-in for less goes to the public showdown.
+pub fn handle_private_showdown(
+    game: &mut Game,
+    deck: &Deck,
+    mxe: Option<&MxeQueue>,
+    player_states: &mut [PlayerState],
+) -> Result<()> {
+    require!(
+        game.stage == GameStage::Showdown,
+        PokerError::InvalidGameStage
+    );
+    require!(
+        game.private_showdown && game.variant.supports_private_showdown(),
+        PokerError::InvalidGameConfig
+    );
+    require!(!game.showdown_pending, PokerError::ShowdownPending);
+    require!(!game.has_pending_computation(), PokerError::MpcResultPending);
+    require!(
+        !has_side_pots(game, player_states),
+        PokerError::PrivateShowdownSidePots
+    );
+    require!(
+        game.community_cards_revealed as usize == COMMUNITY_CARDS,
+        PokerError::CardsNotDealt
+    );
+    
+    msg!("[SHOWDOWN] Starting private showdown for game {}", game.game_id);
+    
+    let mut hole_positions = [[NO_CARD; SHOWDOWN_HOLE_CARDS]; MAX_PLAYERS];
+    let mut contenders = [false; MAX_PLAYERS];
+    for seat in 0..game.player_count as usize {
+        let player_state = &player_states[seat];
+        if !game.active_players[seat] || player_state.has_folded {
+            continue;
+        }
+        
+        require!(
+            player_state.hole_card_positions.len() == SHOWDOWN_HOLE_CARDS,
+            PokerError::CardsNotDealt
+        );
+        hole_positions[seat].copy_from_slice(&player_state.hole_card_positions);
+        contenders[seat] = true;
+    }
+    
+    let computation_offset = game.next_computation_offset();
+    let (accounts, comp_def_offset) = match mxe {
+        Some(queue) => {
+            let (accounts, offset) = queue.accounts_for(Circuit::EvaluateShowdown, computation_offset)?;
+            (Some(accounts), offset)
+        }
+        None => (None, Circuit::EvaluateShowdown.comp_def_offset()),
+    };
+    
+    let outcome = mpc_evaluate_showdown_with_mxe(MxeShowdownParams {
+        mxe: accounts,
+        deck,
+        hole_positions,
+        contenders,
+        board: game.community_cards,
+        show_winning_hand: game.show_winning_hand,
+        computation_offset,
+        comp_def_offset,
+        callback_accounts: mxe.map(|queue| queue.callback_accounts.clone()).unwrap_or_default(),
+    })?;
+    
+    game.showdown_pending = true;
+    
+    match outcome {
+        Some(outcome) => apply_showdown_outcome(game, player_states, outcome),
+        None => {
+            game.await_computation(computation_offset, comp_def_offset, Clock::get()?.unix_timestamp);
+            Ok(())
+        }
+    }
+}
+
+/// A contender is all-in for less than someone else put in this hand, so the
+/// pot has side pots the private showdown cannot split
+pub fn has_side_pots(game: &Game, player_states: &[PlayerState]) -> bool {
+    let most = player_states
+        .iter()
+        .map(|player_state| player_state.total_bet_this_hand)
+        .max()
+        .unwrap_or(0);
+    
+    player_states.iter().any(|player_state| {
+        game.active_players[player_state.seat_index as usize]
+            && !player_state.has_folded
+            && player_state.is_all_in
+            && player_state.total_bet_this_hand < most
+    })
+}
+
+/// Pay the pot to the revealed winners and finish the hand
+///
+/// Winners split the pot evenly, the odd chips going to the lowest seat.
+pub fn apply_showdown_outcome(
+    game: &mut Game,
+    player_states: &mut [PlayerState],
+    outcome: ShowdownOutcome,
+) -> Result<()> {
+    require!(game.showdown_pending, PokerError::InvalidGameStage);
+    
+    // Only seats still in the hand can win
+    let winning_seats: Vec<u8> = (0..game.player_count)
+        .filter(|&seat| outcome.winners[seat as usize])
+        .collect();
+    require!(!winning_seats.is_empty(), PokerError::InvalidAction);
+    for &seat in &winning_seats {
+        require!(
+            game.active_players[seat as usize] && !player_states[seat as usize].has_folded,
+            PokerError::InvalidAction
+        );
+    }
+    
+    let pot = game.pot;
+    let winners = split_pot(&winning_seats, pot);
+    distribute_winnings(game, player_states, &winners)?;
+    
+    let winning_hand = score_hand_rank(outcome.winning_score);
+    
+    game.showdown_pending = false;
+    game.stage = GameStage::Finished;
+    
+    msg!(
+        "[SHOWDOWN] Private showdown complete: seats {:?} win {}",
+        winning_seats,
+        pot
+    );
+    
+    emit!(PrivateShowdownResolved {
+        game_id: game.game_id,
+        winners: winning_seats,
+        pot,
+        winning_hand,
+        winning_score: outcome.winning_score,
+    });
+    
+    Ok(())
+}
+
+/// Split a pot evenly between seats; the first seat takes the odd chips
+fn split_pot(seats: &[u8], amount: u64) -> Vec<(u8, u64)> {
+    let share = amount / seats.len() as u64;
+    let remainder = amount % seats.len() as u64;
+    
+    seats
+        .iter()
+        .enumerate()
+        .map(|(i, &seat)| (seat, if i == 0 { share + remainder } else { share }))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seat(seat_index: u8, total_bet_this_hand: u64, is_all_in: bool) -> PlayerState {
+        let mut player_state = PlayerState::deserialize(&mut &[0u8; PlayerState::LEN][..]).unwrap();
+        player_state.seat_index = seat_index;
+        player_state.total_bet_this_hand = total_bet_this_hand;
+        player_state.is_all_in = is_all_in;
+        player_state
+    }
+
+    #[test]
+    fn test_short_all_in_contender_needs_side_pots() {
+        let mut game = Game::deserialize(&mut &[0u8; Game::LEN][..]).unwrap();
+        game.active_players[..3].copy_from_slice(&[true; 3]);
+
+        // Everyone matched the all-in: a single pot
+        let mut player_states = vec![seat(0, 100, true), seat(1, 100, false), seat(2, 100, false)];
+        assert!(!has_side_pots(&game, &player_states));
+
+        // Seat 0 is all-in for less
+        player_states[1].total_bet_this_hand = 250;
+        assert!(has_side_pots(&game, &player_states));
+
+        // Folded all-in players contest nothing
+        game.active_players[0] = false;
+        player_states[0].has_folded = true;
+        assert!(!has_side_pots(&game, &player_states));
+    }
+
+    #[test]
+    fn test_split_pot_gives_odd_chips_to_first_seat() {
+        assert_eq!(split_pot(&[2], 101), vec![(2, 101)]);
+        assert_eq!(split_pot(&[1, 4], 101), vec![(1, 51), (4, 50)]);
+        assert_eq!(split_pot(&[0, 3, 5], 100), vec![(0, 34), (3, 33), (5, 33)]);
+    }
+}
@@ -1,52 +1,192 @@
 use anchor_lang::prelude::*;
-use crate::cards::evaluator::{EvaluatedHand, evaluate_best_hand};
+use crate::cards::evaluator::{EvaluatedHand, evaluate_player_hand};
 use crate::cards::deck::Card;
+use crate::cards::codec::format_cards;
+use crate::cards::lowball::{LowHand, evaluate_lowball_hand, evaluate_omaha_low, evaluate_stud_low};
 use crate::betting::state::SidePot;
 use crate::shared::constants::MAX_PLAYERS;
 use crate::shared::PokerError;
+use crate::types::GameVariant;
+
+/// Which part of a pot a winner took
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PotHalf {
+    Whole,  // Whole pot (high-only or lowball game, or no qualifying low)
+    High,   // High half of a hi/lo pot
+    Low,    // Low half of a hi/lo pot
+}
 
 /// Winner information for a pot
 #[derive(Clone, Debug)]
 pub struct PotWinner {
     pub seat_index: u8,
     pub hand: EvaluatedHand,
+    pub low_hand: Option<LowHand>,
+    pub half: PotHalf,
     pub share: u64,  // Amount won from this pot
 }
 
+/// Hand shown down in a hi/lo or lowball game: (seat_index, high hand, low hand)
+pub type HiLoHand = (u8, EvaluatedHand, Option<LowHand>);
+
+/// Split `amount` evenly among `winners`; the first winner gets any odd chips
+fn split_among(winners: &[HiLoHand], amount: u64, half: PotHalf) -> Vec<PotWinner> {
+    if winners.is_empty() {
+        return Vec::new();
+    }
+    
+    let share = amount / winners.len() as u64;
+    let remainder = amount % winners.len() as u64;
+    
+    winners
+        .iter()
+        .enumerate()
+        .map(|(i, (seat, hand, low))| PotWinner {
+            seat_index: *seat,
+            hand: *hand,
+            low_hand: *low,
+            half,
+            share: if i == 0 { share + remainder } else { share },
+        })
+        .collect()
+}
+
+/// Players holding the best high hand
+fn best_high_hands(player_hands: &[HiLoHand]) -> Vec<HiLoHand> {
+    let best_hand = player_hands.iter().map(|(_, hand, _)| *hand).max();
+    
+    player_hands
+        .iter()
+        .filter(|(_, hand, _)| Some(*hand) == best_hand)
+        .copied()
+        .collect()
+}
+
 /// Determine winners for main pot
 pub fn determine_main_pot_winners(
     player_hands: &[(u8, EvaluatedHand)], // (seat_index, hand)
     pot_amount: u64,
 ) -> Vec<PotWinner> {
-    if player_hands.is_empty() {
-        return Vec::new();
-    }
-    
-    // Find best hand
-    let best_hand = player_hands
+    let hands: Vec<HiLoHand> = player_hands
         .iter()
-        .map(|(_, hand)| hand)
-        .max()
-        .unwrap();
+        .map(|(seat, hand)| (*seat, *hand, None))
+        .collect();
     
     // Find all players with best hand (for splits)
-    let winners: Vec<&(u8, EvaluatedHand)> = player_hands
+    split_among(&best_high_hands(&hands), pot_amount, PotHalf::Whole)
+}
+
+/// Determine winners of a hi/lo pot
+///
+/// The high hand takes half (plus the odd chip) and the best qualifying low
+/// takes the other half. Ties split their half, so a tied low is quartered.
+/// With no qualifying low the high hand scoops the whole pot.
+pub fn determine_hi_lo_pot_winners(
+    player_hands: &[HiLoHand],
+    pot_amount: u64,
+) -> Vec<PotWinner> {
+    let best_low = player_hands.iter().filter_map(|(_, _, low)| *low).min();
+    
+    let Some(best_low) = best_low else {
+        return split_among(&best_high_hands(player_hands), pot_amount, PotHalf::Whole);
+    };
+    
+    let low_winners: Vec<HiLoHand> = player_hands
         .iter()
-        .filter(|(_, hand)| hand == best_hand)
+        .filter(|(_, _, low)| *low == Some(best_low))
+        .copied()
         .collect();
     
-    // Split pot among winners
-    let share = pot_amount / winners.len() as u64;
-    let remainder = pot_amount % winners.len() as u64;
+    let low_amount = pot_amount / 2;
+    let high_amount = pot_amount - low_amount;
     
+    let mut winners = split_among(&best_high_hands(player_hands), high_amount, PotHalf::High);
+    winners.extend(split_among(&low_winners, low_amount, PotHalf::Low));
     winners
-        .into_iter()
+}
+
+/// Determine winners of a hi/lo side pot among its eligible players
+pub fn determine_hi_lo_side_pot_winners(
+    player_hands: &[HiLoHand],
+    side_pot: &SidePot,
+) -> Vec<PotWinner> {
+    let eligible_hands: Vec<HiLoHand> = player_hands
+        .iter()
+        .filter(|(seat, _, _)| side_pot.is_eligible(*seat as usize))
+        .copied()
+        .collect();
+    
+    determine_hi_lo_pot_winners(&eligible_hands, side_pot.amount)
+}
+
+/// Determine winners of a lowball pot: the lowest hand takes it all
+pub fn determine_lowball_pot_winners(
+    player_hands: &[HiLoHand],
+    pot_amount: u64,
+) -> Vec<PotWinner> {
+    let best_low = player_hands.iter().filter_map(|(_, _, low)| *low).min();
+    
+    let low_winners: Vec<HiLoHand> = player_hands
+        .iter()
+        .filter(|(_, _, low)| low.is_some() && *low == best_low)
+        .copied()
+        .collect();
+    
+    split_among(&low_winners, pot_amount, PotHalf::Whole)
+}
+
+/// Determine all winners of a hi/lo hand; each pot is split independently
+pub fn determine_all_hi_lo_winners(
+    player_hands: &[HiLoHand],
+    main_pot: u64,
+    side_pots: &[SidePot],
+    side_pot_count: u8,
+) -> Vec<(u8, u64)> {
+    total_winnings_by_seat(player_hands, main_pot, side_pots, side_pot_count, determine_hi_lo_pot_winners)
+}
+
+/// Determine all winners of a lowball hand (Razz, 2-7 Triple Draw)
+pub fn determine_all_lowball_winners(
+    player_hands: &[HiLoHand],
+    main_pot: u64,
+    side_pots: &[SidePot],
+    side_pot_count: u8,
+) -> Vec<(u8, u64)> {
+    total_winnings_by_seat(player_hands, main_pot, side_pots, side_pot_count, determine_lowball_pot_winners)
+}
+
+/// Award every pot with `pot_winners`, restricted to each side pot's eligible
+/// players, and total the winnings per seat
+fn total_winnings_by_seat(
+    player_hands: &[HiLoHand],
+    main_pot: u64,
+    side_pots: &[SidePot],
+    side_pot_count: u8,
+    pot_winners: fn(&[HiLoHand], u64) -> Vec<PotWinner>,
+) -> Vec<(u8, u64)> {
+    let mut total_winnings = [0u64; MAX_PLAYERS];
+    
+    for side_pot in &side_pots[..side_pot_count as usize] {
+        let eligible_hands: Vec<HiLoHand> = player_hands
+            .iter()
+            .filter(|(seat, _, _)| side_pot.is_eligible(*seat as usize))
+            .copied()
+            .collect();
+        
+        for winner in pot_winners(&eligible_hands, side_pot.amount) {
+            total_winnings[winner.seat_index as usize] += winner.share;
+        }
+    }
+    
+    for winner in pot_winners(player_hands, main_pot) {
+        total_winnings[winner.seat_index as usize] += winner.share;
+    }
+    
+    total_winnings
+        .iter()
         .enumerate()
-        .map(|(i, (seat, hand))| PotWinner {
-            seat_index: *seat,
-            hand: *hand,
-            share: if i == 0 { share + remainder } else { share },
-        })
+        .filter(|(_, &amount)| amount > 0)
+        .map(|(seat, &amount)| (seat as u8, amount))
         .collect()
 }
 
@@ -99,8 +239,9 @@ pub fn determine_all_winners(
 
 /// Evaluate all player hands and determine winners
 pub fn evaluate_and_determine_winners(
-    player_hole_cards: &[(u8, [Card; 2])], // (seat_index, hole_cards)
-    community_cards: &[Card; 5],
+    variant: GameVariant,
+    player_hole_cards: &[(u8, Vec<Card>)], // (seat_index, hole_cards)
+    community_cards: &[Card],
     main_pot: u64,
     side_pots: &[SidePot],
     side_pot_count: u8,
@@ -109,25 +250,44 @@ pub fn evaluate_and_determine_winners(
     let mut evaluated_hands = Vec::new();
     
     for (seat, hole_cards) in player_hole_cards {
-        let hand = evaluate_best_hand(hole_cards, community_cards)?;
-        evaluated_hands.push((*seat, hand));
+        let best = evaluate_player_hand(variant, hole_cards, community_cards)?;
+        let low = if variant.is_hi_lo() && variant.is_stud() {
+            evaluate_stud_low(hole_cards)?
+        } else if variant.is_hi_lo() {
+            let board: &[Card; 5] = community_cards
+                .try_into()
+                .map_err(|_| PokerError::InvalidCardIndex)?;
+            evaluate_omaha_low(hole_cards, board)?
+        } else if variant.is_lowball() {
+            Some(evaluate_lowball_hand(variant, hole_cards)?)
+        } else {
+            None
+        };
+        evaluated_hands.push((*seat, best.hand, low));
         
         msg!(
-            "[SHOWDOWN] Seat {} hand: {:?} (primary: {}, secondary: {})",
+            "[SHOWDOWN] Seat {} hand: {} ({})",
             seat,
-            hand.rank,
-            hand.primary_value,
-            hand.secondary_value
+            best.description(),
+            format_cards(&best.cards)
         );
+        if let Some(low) = low {
+            msg!("[SHOWDOWN] Seat {} low: {}", seat, low);
+        }
     }
     
     // Determine winners
-    let winners = determine_all_winners(
-        &evaluated_hands,
-        main_pot,
-        side_pots,
-        side_pot_count,
-    );
+    let winners = if variant.is_hi_lo() {
+        determine_all_hi_lo_winners(&evaluated_hands, main_pot, side_pots, side_pot_count)
+    } else if variant.is_lowball() {
+        determine_all_lowball_winners(&evaluated_hands, main_pot, side_pots, side_pot_count)
+    } else {
+        let high_hands: Vec<(u8, EvaluatedHand)> = evaluated_hands
+            .iter()
+            .map(|(seat, hand, _)| (*seat, *hand))
+            .collect();
+        determine_all_winners(&high_hands, main_pot, side_pots, side_pot_count)
+    };
     
     // Log winners
     for (seat, amount) in &winners {
@@ -135,4 +295,122 @@ pub fn evaluate_and_determine_winners(
     }
     
     Ok(winners)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::HandRank;
+    
+    fn high(rank: HandRank, primary: u8) -> EvaluatedHand {
+        EvaluatedHand::new(rank, primary, 0, [0; 5])
+    }
+    
+    fn low(ranks: [u8; 5]) -> Option<LowHand> {
+        Some(LowHand { rank: HandRank::HighCard, ranks })
+    }
+    
+    fn share_of(winners: &[PotWinner], seat: u8, half: PotHalf) -> u64 {
+        winners
+            .iter()
+            .filter(|w| w.seat_index == seat && w.half == half)
+            .map(|w| w.share)
+            .sum()
+    }
+    
+    #[test]
+    fn test_high_scoops_without_qualifying_low() {
+        let hands = [
+            (0, high(HandRank::Flush, 14), None),
+            (1, high(HandRank::OnePair, 9), None),
+        ];
+        
+        let winners = determine_hi_lo_pot_winners(&hands, 101);
+        assert_eq!(winners.len(), 1);
+        assert_eq!(share_of(&winners, 0, PotHalf::Whole), 101);
+    }
+    
+    #[test]
+    fn test_split_with_odd_chip_to_high() {
+        let hands = [
+            (0, high(HandRank::Flush, 14), None),
+            (1, high(HandRank::OnePair, 9), low([8, 5, 4, 2, 1])),
+        ];
+        
+        let winners = determine_hi_lo_pot_winners(&hands, 101);
+        assert_eq!(share_of(&winners, 0, PotHalf::High), 51);
+        assert_eq!(share_of(&winners, 1, PotHalf::Low), 50);
+    }
+    
+    #[test]
+    fn test_scoop_high_and_low() {
+        let hands = [
+            (0, high(HandRank::Straight, 5), low([5, 4, 3, 2, 1])),
+            (1, high(HandRank::OnePair, 9), low([8, 5, 4, 2, 1])),
+        ];
+        
+        let totals = determine_all_hi_lo_winners(&hands, 100, &[], 0);
+        assert_eq!(totals, vec![(0, 100)]);
+    }
+    
+    #[test]
+    fn test_quartered_low() {
+        let hands = [
+            (0, high(HandRank::Flush, 14), None),
+            (1, high(HandRank::OnePair, 9), low([7, 5, 4, 2, 1])),
+            (2, high(HandRank::HighCard, 13), low([7, 5, 4, 2, 1])),
+        ];
+        
+        let winners = determine_hi_lo_pot_winners(&hands, 120);
+        assert_eq!(share_of(&winners, 0, PotHalf::High), 60);
+        assert_eq!(share_of(&winners, 1, PotHalf::Low), 30);
+        assert_eq!(share_of(&winners, 2, PotHalf::Low), 30);
+    }
+    
+    #[test]
+    fn test_side_pots_split_independently() {
+        // Seat 0 is all-in and only eligible for the side pot
+        let hands = [
+            (0, high(HandRank::HighCard, 13), low([6, 4, 3, 2, 1])),
+            (1, high(HandRank::Flush, 14), None),
+            (2, high(HandRank::OnePair, 9), low([8, 7, 4, 2, 1])),
+        ];
+        
+        let mut side_pot = SidePot::new(90);
+        side_pot.add_eligible_player(0);
+        side_pot.add_eligible_player(1);
+        side_pot.add_eligible_player(2);
+        
+        // Main pot contested by seats 1 and 2 only
+        let main_hands = [hands[1], hands[2]];
+        let side = determine_hi_lo_side_pot_winners(&hands, &side_pot);
+        let main = determine_hi_lo_pot_winners(&main_hands, 40);
+        
+        assert_eq!(share_of(&side, 1, PotHalf::High), 45);
+        assert_eq!(share_of(&side, 0, PotHalf::Low), 45);
+        assert_eq!(share_of(&main, 1, PotHalf::High), 20);
+        assert_eq!(share_of(&main, 2, PotHalf::Low), 20);
+    }
+    
+    #[test]
+    fn test_lowball_pot_goes_to_lowest_hand() {
+        let paired = Some(LowHand { rank: HandRank::OnePair, ranks: [2, 5, 4, 3, 0] });
+        let hands = [
+            (0, high(HandRank::OnePair, 2), paired),
+            (1, high(HandRank::HighCard, 13), low([13, 12, 11, 10, 8])),
+            (2, high(HandRank::HighCard, 7), low([7, 5, 4, 3, 2])),
+            (3, high(HandRank::HighCard, 7), low([7, 5, 4, 3, 2])),
+        ];
+        
+        let winners = determine_lowball_pot_winners(&hands, 101);
+        assert_eq!(share_of(&winners, 2, PotHalf::Whole), 51);
+        assert_eq!(share_of(&winners, 3, PotHalf::Whole), 50);
+        
+        // Seat 1's king-low still beats a pair when the best lows aren't eligible
+        let mut side_pot = SidePot::new(60);
+        side_pot.add_eligible_player(0);
+        side_pot.add_eligible_player(1);
+        let totals = determine_all_lowball_winners(&hands, 0, &[side_pot], 1);
+        assert_eq!(totals, vec![(1, 60)]);
+    }
+}
@@ -1,4 +1,7 @@
 use anchor_lang::prelude::*;
+use crate::shared::constants::{
+    COMMUNITY_CARDS, DECK_SIZE, DRAW_HAND_CARDS, SHORT_DECK_SIZE, STUD_DOWN_CARDS, STUD_UP_CARDS,
+};
 
 /// Game stage/phase
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
@@ -18,6 +21,253 @@ impl Default for GameStage {
     }
 }
 
+/// Street in a seven-card stud hand
+///
+/// Stud tables keep `GameStage::PreFlop` while the hand is being bet and
+/// track the street here; River-style stages are not used.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum StudStreet {
+    #[default]
+    Third,          // 2 down + 1 up, bring-in
+    Fourth,         // 1 up
+    Fifth,          // 1 up
+    Sixth,          // 1 up
+    Seventh,        // 1 down
+}
+
+impl StudStreet {
+    /// Street that follows this one (None after seventh street)
+    pub fn next(&self) -> Option<StudStreet> {
+        match self {
+            StudStreet::Third => Some(StudStreet::Fourth),
+            StudStreet::Fourth => Some(StudStreet::Fifth),
+            StudStreet::Fifth => Some(StudStreet::Sixth),
+            StudStreet::Sixth => Some(StudStreet::Seventh),
+            StudStreet::Seventh => None,
+        }
+    }
+    
+    /// Number of upcards each player shows once this street is dealt
+    pub fn up_cards_showing(&self) -> usize {
+        match self {
+            StudStreet::Third => 1,
+            StudStreet::Fourth => 2,
+            StudStreet::Fifth => 3,
+            StudStreet::Sixth | StudStreet::Seventh => 4,
+        }
+    }
+}
+
+/// Cards the table owes every live seat before play can continue
+///
+/// Each round deals to the seats in order, one deal computation per seat;
+/// betting stays closed until the round is complete.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum DealRound {
+    #[default]
+    None,
+    HoleCards,      // Starting hole cards, encrypted to each seat
+    UpCards,        // One face-up card each (stud)
+    DownCards,      // One face-down card each (stud, seventh street)
+}
+
+/// Poker variant played at a table
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum GameVariant {
+    #[default]
+    TexasHoldem,    // 2 hole cards, any five of seven
+    Omaha,          // 4 hole cards, exactly two hole + three board
+    Omaha5,         // 5-card PLO
+    Omaha6,         // 6-card PLO
+    OmahaHiLo,      // 4-card Omaha, pot split with an eight-or-better low
+    ShortDeck,      // 6+ Hold'em: 36-card deck, flush beats full house
+    SevenCardStud,  // 3 down + 4 up, no community cards
+    FiveCardDraw,   // 5 hole cards, one draw, no community cards
+    Razz,           // Seven-card stud played for ace-to-five low
+    StudHiLo,       // Seven-card stud split with an eight-or-better low
+    TripleDraw27,   // Deuce-to-seven lowball with three draws
+}
+
+impl GameVariant {
+    /// Number of hole (face-down) cards dealt to each player
+    pub fn hole_cards(&self) -> usize {
+        match self {
+            GameVariant::SevenCardStud | GameVariant::Razz | GameVariant::StudHiLo => STUD_DOWN_CARDS,
+            GameVariant::FiveCardDraw | GameVariant::TripleDraw27 => DRAW_HAND_CARDS,
+            GameVariant::TexasHoldem | GameVariant::ShortDeck => 2,
+            GameVariant::Omaha => 4,
+            GameVariant::Omaha5 => 5,
+            GameVariant::Omaha6 => 6,
+            GameVariant::OmahaHiLo => 4,
+        }
+    }
+    
+    /// Hands must use exactly two hole cards and three board cards
+    pub fn is_omaha(&self) -> bool {
+        matches!(
+            self,
+            GameVariant::Omaha | GameVariant::Omaha5 | GameVariant::Omaha6 | GameVariant::OmahaHiLo
+        )
+    }
+    
+    /// Pots are split between the best high and the best qualifying low
+    pub fn is_hi_lo(&self) -> bool {
+        matches!(self, GameVariant::OmahaHiLo | GameVariant::StudHiLo)
+    }
+    
+    /// Lowest hand wins the whole pot (no high half)
+    pub fn is_lowball(&self) -> bool {
+        self.lowball_ranking().is_some()
+    }
+    
+    /// How low hands are ranked in a lowball variant
+    pub fn lowball_ranking(&self) -> Option<LowballRanking> {
+        match self {
+            GameVariant::Razz => Some(LowballRanking::AceToFive),
+            GameVariant::TripleDraw27 => Some(LowballRanking::DeuceToSeven),
+            _ => None,
+        }
+    }
+    
+    /// Hole cards dealt when the hand starts (stud deals its last down card on seventh street)
+    pub fn hole_cards_at_start(&self) -> usize {
+        if self.is_stud() {
+            STUD_DOWN_CARDS - 1
+        } else {
+            self.hole_cards()
+        }
+    }
+    
+    /// Total private and face-up cards a player ends the hand with
+    pub fn cards_per_player(&self) -> usize {
+        if self.is_stud() {
+            STUD_DOWN_CARDS + STUD_UP_CARDS
+        } else {
+            self.hole_cards()
+        }
+    }
+    
+    /// Number of community cards on the board
+    pub fn community_cards(&self) -> usize {
+        if self.is_stud() || self.is_draw() {
+            0
+        } else {
+            COMMUNITY_CARDS
+        }
+    }
+    
+    /// Cards a full hand uses from the deck, including burns
+    /// (draw replacements are not counted; they recycle discards when short)
+    pub fn cards_needed(&self, players: usize) -> usize {
+        // Board games burn before the flop, turn and river
+        let burns = if self.community_cards() > 0 { 3 } else { 0 };
+        self.cards_per_player() * players + self.community_cards() + burns
+    }
+    
+    /// The `evaluate_showdown` circuit can settle this game privately
+    /// (two hole cards, a five-card board, standard high rankings)
+    pub fn supports_private_showdown(&self) -> bool {
+        matches!(self, GameVariant::TexasHoldem)
+    }
+    
+    /// Seven-card stud: individual up and down cards, no board
+    pub fn is_stud(&self) -> bool {
+        matches!(
+            self,
+            GameVariant::SevenCardStud | GameVariant::Razz | GameVariant::StudHiLo
+        )
+    }
+    
+    /// Players replace cards from their hand during draw phases
+    pub fn is_draw(&self) -> bool {
+        self.draw_rounds() > 0
+    }
+    
+    /// Number of draw phases in a hand (0 for non-draw games)
+    pub fn draw_rounds(&self) -> u8 {
+        match self {
+            GameVariant::FiveCardDraw => 1,
+            GameVariant::TripleDraw27 => 3,
+            _ => 0,
+        }
+    }
+    
+    /// Played with the 36-card (sixes through aces) deck
+    pub fn is_short_deck(&self) -> bool {
+        matches!(self, GameVariant::ShortDeck)
+    }
+    
+    /// Number of cards in the deck this variant is dealt from
+    pub fn deck_size(&self) -> usize {
+        if self.is_short_deck() {
+            SHORT_DECK_SIZE
+        } else {
+            DECK_SIZE
+        }
+    }
+    
+    /// Betting structure normally played with this variant
+    pub fn default_betting_structure(&self) -> BettingStructure {
+        if self.is_stud() || *self == GameVariant::TripleDraw27 {
+            BettingStructure::FixedLimit
+        } else if self.is_omaha() {
+            BettingStructure::PotLimit
+        } else {
+            BettingStructure::NoLimit
+        }
+    }
+    
+    /// Whether fixed-limit betting uses the big bet (twice the small bet)
+    ///
+    /// `round` counts betting rounds from 0: board games switch on the turn,
+    /// stud on fifth street, draw games after the first half of their draws.
+    pub fn uses_big_bet(&self, round: u8) -> bool {
+        if self.is_draw() {
+            round > self.draw_rounds() / 2
+        } else {
+            round >= 2
+        }
+    }
+}
+
+/// One game in a mixed-game rotation (e.g. the "H" of HORSE is
+/// Texas Hold'em played fixed-limit)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct RotationEntry {
+    pub variant: GameVariant,
+    pub betting_structure: BettingStructure,
+}
+
+/// A card encrypted by the deal circuit to its owner's x25519 key
+///
+/// Only the holder of the matching x25519 secret key can decrypt it; the
+/// program just stores the ciphertext and nonce for the owner to read.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct EncryptedHoleCard {
+    pub ciphertext: [u8; 32],
+    pub nonce: u128,
+}
+
+impl EncryptedHoleCard {
+    pub const LEN: usize = 32 + 16;
+}
+
+/// Low hand ranking used by lowball variants
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LowballRanking {
+    AceToFive,     // Aces low, straights and flushes ignored (Razz)
+    DeuceToSeven,  // Aces high, straights and flushes count against (Kansas City)
+}
+
+/// Betting structure (caps on bet and raise sizes)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum BettingStructure {
+    #[default]
+    NoLimit,        // Any amount up to the player's stack
+    PotLimit,       // Bets and raises capped at the size of the pot
+    FixedLimit,     // Fixed small/big bet sizes with a cap on raises per round
+}
+
 /// Player action types
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
 pub enum PlayerAction {
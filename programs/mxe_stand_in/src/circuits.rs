@@ -0,0 +1,423 @@
+use anchor_lang::prelude::*;
+use arcium_poker::arcium::Circuit;
+use arcium_poker::shared::constants::{DECK_SIZE, MAX_PLAYERS};
+use poker_shared::circuits::{
+    self, DRAW_HAND_CARDS, MAX_HOLE_CARDS, SHOWDOWN_HOLE_CARDS, SHUFFLE_DRAW_ATTEMPTS,
+};
+use solana_keccak_hasher as keccak;
+use crate::errors::StandInError;
+use crate::types::Argument;
+
+/// Stand-in for the cluster's joint randomness (`ArcisRNG`)
+///
+/// A Keccak stream of the test seed and the computation offset: every
+/// computation draws differently, and the same seed replays the same run.
+pub struct NodeRng {
+    seed: [u8; 32],
+    counter: u64,
+    block: [u8; 32],
+    used: usize,
+}
+
+impl NodeRng {
+    pub fn new(seed: &[u8; 32], comp_offset: u64) -> Self {
+        Self {
+            seed: keccak::hashv(&[b"mxe-stand-in/rng", seed, &comp_offset.to_le_bytes()]).to_bytes(),
+            counter: 0,
+            block: [0; 32],
+            used: 32,
+        }
+    }
+    
+    fn next_byte(&mut self) -> u8 {
+        if self.used == self.block.len() {
+            self.block = keccak::hashv(&[&self.seed[..], &self.counter.to_le_bytes()]).to_bytes();
+            self.counter += 1;
+            self.used = 0;
+        }
+        
+        let byte = self.block[self.used];
+        self.used += 1;
+        byte
+    }
+    
+    /// The shuffle circuits' node draws, as `ArcisRNG` would fill them
+    pub fn shuffle_draws(&mut self) -> [[u8; SHUFFLE_DRAW_ATTEMPTS]; DECK_SIZE] {
+        let mut draws = [[0u8; SHUFFLE_DRAW_ATTEMPTS]; DECK_SIZE];
+        for draw in draws.iter_mut().skip(1).flatten() {
+            *draw = self.next_byte();
+        }
+        draws
+    }
+}
+
+// ============================================================================
+// MXE-OWNED DECK
+// ============================================================================
+
+/// Length of an `Enc<Mxe, Deck>`: nonce, then one 32-byte block per card
+pub const ENCRYPTED_DECK_LEN: usize = 16 + 32 * DECK_SIZE;
+
+/// Block hiding the card at a deck position
+fn keystream(seed: &[u8; 32], nonce: u128, position: usize) -> [u8; 32] {
+    keccak::hashv(&[
+        b"mxe-stand-in/deck",
+        seed,
+        &nonce.to_le_bytes(),
+        &(position as u32).to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// Encrypt a deck to the stand-in MXE, laid out like the Deck account
+pub fn encrypt_deck(seed: &[u8; 32], nonce: u128, deck: &[u8; DECK_SIZE]) -> Vec<u8> {
+    let mut output = nonce.to_le_bytes().to_vec();
+    for (position, &card) in deck.iter().enumerate() {
+        let mut block = keystream(seed, nonce, position);
+        block[0] ^= card;
+        output.extend_from_slice(&block);
+    }
+    output
+}
+
+/// Block hiding an `Enc<Shared, _>` output from everyone but the receiver
+///
+/// The stand-in has no x25519 exchange: the receiver's public key stands in
+/// for the shared secret, so tests decrypt with the key they registered.
+pub fn shared_keystream(receiver: &[u8; 32], nonce: u128) -> [u8; 32] {
+    keccak::hashv(&[b"mxe-stand-in/shared", receiver, &nonce.to_le_bytes()]).to_bytes()
+}
+
+/// Encrypt cards to a receiver, card `i` under `nonce + i`
+pub fn encrypt_shared(receiver: &[u8; 32], nonce: u128, cards: &[u8]) -> Vec<[u8; 32]> {
+    cards
+        .iter()
+        .enumerate()
+        .map(|(i, &card)| {
+            let mut block = shared_keystream(receiver, nonce + i as u128);
+            block[0] ^= card;
+            block
+        })
+        .collect()
+}
+
+/// Decrypt a deck encrypted by `encrypt_deck` under the same seed
+pub fn decrypt_deck(seed: &[u8; 32], data: &[u8]) -> Result<[u8; DECK_SIZE]> {
+    require!(data.len() == ENCRYPTED_DECK_LEN, StandInError::InvalidCiphertext);
+    
+    let (nonce, blocks) = data.split_at(16);
+    let nonce = u128::from_le_bytes(nonce.try_into().unwrap());
+    let mut deck = [0u8; DECK_SIZE];
+    for (position, (card, block)) in deck.iter_mut().zip(blocks.chunks_exact(32)).enumerate() {
+        let stream = keystream(seed, nonce, position);
+        require!(block[1..] == stream[1..], StandInError::InvalidCiphertext);
+        *card = block[0] ^ stream[0];
+    }
+    Ok(deck)
+}
+
+// ============================================================================
+// EXECUTION
+// ============================================================================
+
+/// Reads a computation's arguments in circuit order
+///
+/// `Encrypted*` arguments are read as plaintext: clients of the stand-in
+/// submit their inputs unencrypted.
+pub struct Inputs<'a> {
+    args: &'a [Argument],
+    next: usize,
+}
+
+impl<'a> Inputs<'a> {
+    pub fn new(args: &'a [Argument]) -> Self {
+        Self { args, next: 0 }
+    }
+    
+    fn next(&mut self) -> Result<&'a Argument> {
+        let arg = self.args.get(self.next).ok_or(StandInError::InvalidArguments)?;
+        self.next += 1;
+        Ok(arg)
+    }
+    
+    pub fn encrypted(&mut self) -> Result<[u8; 32]> {
+        match self.next()? {
+            Argument::EncryptedU8(data) => Ok(*data),
+            _ => err!(StandInError::InvalidArguments),
+        }
+    }
+    
+    pub fn u8(&mut self) -> Result<u8> {
+        match self.next()? {
+            Argument::PlaintextU8(value) => Ok(*value),
+            _ => err!(StandInError::InvalidArguments),
+        }
+    }
+    
+    pub fn u128(&mut self) -> Result<u128> {
+        match self.next()? {
+            Argument::PlaintextU128(value) => Ok(*value),
+            _ => err!(StandInError::InvalidArguments),
+        }
+    }
+    
+    /// Receiver of an `Enc<Shared, _>` output and its nonce
+    pub fn receiver(&mut self) -> Result<([u8; 32], u128)> {
+        let key = match self.next()? {
+            Argument::ArcisPubkey(key) => *key,
+            _ => return err!(StandInError::InvalidArguments),
+        };
+        Ok((key, self.u128()?))
+    }
+    
+    pub fn bool(&mut self) -> Result<bool> {
+        match self.next()? {
+            Argument::PlaintextBool(value) => Ok(*value),
+            _ => err!(StandInError::InvalidArguments),
+        }
+    }
+    
+    /// MXE deck stored in an account (`Enc<Mxe, &Deck>`)
+    pub fn deck(&mut self, seed: &[u8; 32], accounts: &[AccountInfo]) -> Result<[u8; DECK_SIZE]> {
+        let (key, offset, len) = match self.next()? {
+            Argument::Account(key, offset, len) => (*key, *offset as usize, *len as usize),
+            _ => return err!(StandInError::InvalidArguments),
+        };
+        let account = accounts
+            .iter()
+            .find(|account| account.key() == key)
+            .ok_or(StandInError::MissingAccount)?;
+        let data = account.try_borrow_data()?;
+        let stored = data.get(offset..offset + len).ok_or(StandInError::InvalidArguments)?;
+        decrypt_deck(seed, stored)
+    }
+    
+    /// Every argument was read
+    pub fn finish(&self) -> Result<()> {
+        require!(self.next == self.args.len(), StandInError::InvalidArguments);
+        Ok(())
+    }
+}
+
+/// Circuit registered at a comp def offset
+pub fn circuit_at(comp_def_offset: u32) -> Result<Circuit> {
+    Circuit::ALL
+        .iter()
+        .copied()
+        .find(|circuit| circuit.comp_def_offset() == comp_def_offset)
+        .ok_or(error!(StandInError::UnknownCircuit))
+}
+
+/// Run a computation in plaintext and return its Borsh-encoded output, the
+/// callback arguments that follow the computation ID
+///
+/// `accounts` must hold every account an `Argument::Account` references.
+pub fn execute(
+    circuit: Circuit,
+    args: &[Argument],
+    seed: &[u8; 32],
+    comp_offset: u64,
+    accounts: &[AccountInfo],
+) -> Result<Vec<u8>> {
+    let mut inputs = Inputs::new(args);
+    let output = match circuit {
+        Circuit::ShuffleDeck | Circuit::ShuffleShortDeck => {
+            let mut entropy = [[0u8; 32]; MAX_PLAYERS];
+            for seat_entropy in entropy.iter_mut() {
+                *seat_entropy = inputs.encrypted()?;
+            }
+            let active_count = inputs.u8()?;
+            let nonce = inputs.u128()?;
+            
+            let draws = NodeRng::new(seed, comp_offset).shuffle_draws();
+            let deck = if circuit == Circuit::ShuffleDeck {
+                circuits::shuffled_deck(&entropy, active_count, &draws)
+            } else {
+                circuits::shuffled_short_deck(&entropy, active_count, &draws)
+            };
+            borsh::to_vec(&encrypt_deck(seed, nonce, &deck))?
+        }
+        Circuit::DealHoleCards => {
+            let deck = inputs.deck(seed, accounts)?;
+            let start = inputs.u8()?;
+            let count = inputs.u8()?;
+            let (receiver, nonce) = inputs.receiver()?;
+            let cards = circuits::hole_cards(&deck, start, count);
+            let ciphertexts: [[u8; 32]; MAX_HOLE_CARDS] = encrypt_shared(&receiver, nonce, &cards)
+                .try_into()
+                .unwrap();
+            borsh::to_vec(&(nonce, ciphertexts))?
+        }
+        Circuit::DealCard => {
+            let deck = inputs.deck(seed, accounts)?;
+            let position = inputs.u8()?;
+            let (receiver, nonce) = inputs.receiver()?;
+            let card = circuits::card_at(&deck, position);
+            borsh::to_vec(&(nonce, encrypt_shared(&receiver, nonce, &[card])[0]))?
+        }
+        Circuit::DealUpCard => {
+            let deck = inputs.deck(seed, accounts)?;
+            let position = inputs.u8()?;
+            borsh::to_vec(&circuits::card_at(&deck, position))?
+        }
+        Circuit::DrawCards => {
+            let deck = inputs.deck(seed, accounts)?;
+            let mut hand_positions = [0u8; DRAW_HAND_CARDS];
+            for position in hand_positions.iter_mut() {
+                *position = inputs.u8()?;
+            }
+            let mut discard = [false; DRAW_HAND_CARDS];
+            for slot in discard.iter_mut() {
+                *slot = inputs.bool()?;
+            }
+            let cursor = inputs.u8()?;
+            let (receiver, nonce) = inputs.receiver()?;
+            
+            let (deck, hand) = circuits::drawn_hand(&deck, &hand_positions, &discard, cursor);
+            // The MXE re-encrypts the deck under a fresh nonce; the
+            // computation offset is unique per game
+            let ciphertexts: [[u8; 32]; DRAW_HAND_CARDS] = encrypt_shared(&receiver, nonce, &hand)
+                .try_into()
+                .unwrap();
+            borsh::to_vec(&(encrypt_deck(seed, comp_offset as u128, &deck), nonce, ciphertexts))?
+        }
+        Circuit::RecycleDiscards => {
+            let deck = inputs.deck(seed, accounts)?;
+            let start = inputs.u8()?;
+            let entropy = inputs.encrypted()?;
+            
+            let draws = NodeRng::new(seed, comp_offset).shuffle_draws();
+            let deck = circuits::recycled_deck(&deck, start, &entropy, &draws);
+            // Fresh nonce, as for `draw_cards`
+            borsh::to_vec(&encrypt_deck(seed, comp_offset as u128, &deck))?
+        }
+        Circuit::RevealCommunity => {
+            let deck = inputs.deck(seed, accounts)?;
+            let burn_index = inputs.u8()?;
+            let count = inputs.u8()?;
+            borsh::to_vec(&circuits::board_cards(&deck, burn_index, count).to_vec())?
+        }
+        Circuit::RevealHoleCards => {
+            let deck = inputs.deck(seed, accounts)?;
+            let mut positions = [0u8; MAX_HOLE_CARDS];
+            for position in positions.iter_mut() {
+                *position = inputs.u8()?;
+            }
+            let count = inputs.u8()?;
+            borsh::to_vec(&circuits::shown_hole_cards(&deck, &positions, count).to_vec())?
+        }
+        Circuit::EvaluateShowdown => {
+            let deck = inputs.deck(seed, accounts)?;
+            let mut hole_positions = [[0u8; SHOWDOWN_HOLE_CARDS]; MAX_PLAYERS];
+            for position in hole_positions.iter_mut().flatten() {
+                *position = inputs.u8()?;
+            }
+            let mut contenders = [false; MAX_PLAYERS];
+            for contender in contenders.iter_mut() {
+                *contender = inputs.bool()?;
+            }
+            let mut board = [0u8; 5];
+            for card in board.iter_mut() {
+                *card = inputs.u8()?;
+            }
+            let show_winning_hand = inputs.bool()?;
+            borsh::to_vec(&circuits::showdown_winners(
+                &deck,
+                &hole_positions,
+                &contenders,
+                &board,
+                show_winning_hand,
+            ))?
+        }
+        // Queued by a direct invoke, not `queue_computation`, and has no
+        // callback to deliver to
+        Circuit::GenerateRandom => {
+            return err!(StandInError::UnsupportedCircuit);
+        }
+    };
+    inputs.finish()?;
+    
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arcium_poker::cards::deck::Deck;
+
+    fn entropy() -> [[u8; 32]; MAX_PLAYERS] {
+        let mut entropy = [[0u8; 32]; MAX_PLAYERS];
+        for (seat, seat_entropy) in entropy.iter_mut().enumerate() {
+            *seat_entropy = [seat as u8 + 1; 32];
+        }
+        entropy
+    }
+
+    #[test]
+    fn test_shuffle_replays_for_a_seed() {
+        let shuffle = |seed: [u8; 32], comp_offset| {
+            circuits::shuffled_deck(&entropy(), 3, &NodeRng::new(&seed, comp_offset).shuffle_draws())
+        };
+        let deck = shuffle([1; 32], 5);
+        let mut sorted = deck;
+        sorted.sort_unstable();
+        assert_eq!(sorted.to_vec(), (0..DECK_SIZE as u8).collect::<Vec<_>>());
+
+        assert_eq!(shuffle([1; 32], 5), deck);
+        assert_ne!(shuffle([2; 32], 5), deck);
+        assert_ne!(shuffle([1; 32], 6), deck);
+    }
+
+    #[test]
+    fn test_deck_round_trips_in_account_layout() {
+        let deck = circuits::shuffled_deck(&entropy(), 2, &NodeRng::new(&[7; 32], 3).shuffle_draws());
+        let encrypted = encrypt_deck(&[7; 32], 11, &deck);
+        assert_eq!(encrypted.len(), Deck::ENCRYPTED_LEN as usize);
+        assert_eq!(decrypt_deck(&[7; 32], &encrypted).unwrap(), deck);
+        assert!(decrypt_deck(&[8; 32], &encrypted).is_err());
+    }
+
+    #[test]
+    fn test_circuits_resolve_by_comp_def_offset() {
+        for circuit in Circuit::ALL {
+            assert_eq!(circuit_at(circuit.comp_def_offset()).unwrap(), circuit);
+        }
+        assert!(execute(Circuit::GenerateRandom, &[], &[0; 32], 0, &[]).is_err());
+    }
+
+    #[test]
+    fn test_deals_and_draws_read_the_stored_deck() {
+        let seed = [7; 32];
+        let cards = circuits::shuffled_deck(&entropy(), 2, &NodeRng::new(&seed, 1).shuffle_draws());
+        let key = Pubkey::new_unique();
+        let mut data = vec![0u8; Deck::LEN];
+        let start = Deck::ENCRYPTED_OFFSET as usize;
+        data[start..start + ENCRYPTED_DECK_LEN].copy_from_slice(&encrypt_deck(&seed, 11, &cards));
+        let mut lamports = 0;
+        let owner = arcium_poker::ID;
+        let account = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &owner, false, 0);
+        let accounts = [account];
+        let deck = Argument::Account(key, Deck::ENCRYPTED_OFFSET, Deck::ENCRYPTED_LEN);
+        let receiver = [Argument::ArcisPubkey([5; 32]), Argument::PlaintextU128(100)];
+        let decrypt = |ciphertext: &[u8; 32], nonce: u128| ciphertext[0] ^ shared_keystream(&[5; 32], nonce)[0];
+
+        // Two hole cards from position 3, encrypted to the receiver
+        let args = [vec![deck.clone(), Argument::PlaintextU8(3), Argument::PlaintextU8(2)], receiver.to_vec()].concat();
+        let output = execute(Circuit::DealHoleCards, &args, &seed, 2, &accounts).unwrap();
+        let (nonce, hand) = <(u128, [[u8; 32]; MAX_HOLE_CARDS])>::try_from_slice(&output).unwrap();
+        assert_eq!([decrypt(&hand[0], nonce), decrypt(&hand[1], nonce + 1)], [cards[3], cards[4]]);
+
+        // Discarding slots 1 and 3 of the hand at 0..5 swaps in positions 20, 21
+        let mut args = vec![deck];
+        args.extend((0..5).map(Argument::PlaintextU8));
+        args.extend([false, true, false, true, false].map(Argument::PlaintextBool));
+        args.push(Argument::PlaintextU8(20));
+        args.extend(receiver);
+        let output = execute(Circuit::DrawCards, &args, &seed, 3, &accounts).unwrap();
+        let (encrypted, nonce, hand) = <(Vec<u8>, u128, [[u8; 32]; DRAW_HAND_CARDS])>::try_from_slice(&output).unwrap();
+        let drawn: Vec<u8> = (0..DRAW_HAND_CARDS).map(|i| decrypt(&hand[i], nonce + i as u128)).collect();
+        assert_eq!(drawn, vec![cards[0], cards[20], cards[2], cards[21], cards[4]]);
+        let updated = decrypt_deck(&seed, &encrypted).unwrap();
+        assert_eq!(updated[20..22], [cards[1], cards[3]]);
+    }
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum StandInError {
+    #[msg("No circuit has this computation definition offset")]
+    UnknownCircuit,
+    
+    #[msg("Circuit output is encrypted to a player's key, which the stand-in cannot produce")]
+    UnsupportedCircuit,
+    
+    #[msg("Computation arguments do not match the circuit")]
+    InvalidArguments,
+    
+    #[msg("Account referenced by an argument or callback was not passed")]
+    MissingAccount,
+    
+    #[msg("Computation already executed")]
+    AlreadyExecuted,
+    
+    #[msg("MXE data does not decrypt under this seed")]
+    InvalidCiphertext,
+}
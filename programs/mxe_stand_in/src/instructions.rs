@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+use crate::circuits;
+use crate::errors::StandInError;
+use crate::types::QueueComputationArgs;
+
+/// Create the MXE account
+pub fn initialize_handler(ctx: Context<crate::Initialize>, seed: [u8; 32]) -> Result<()> {
+    let mxe = &mut ctx.accounts.mxe_account;
+    mxe.authority = ctx.accounts.authority.key();
+    mxe.seed = seed;
+    mxe.bump = ctx.bumps.mxe_account;
+    
+    msg!("[MXE STAND-IN] Initialized, seed {:?}", &seed[..8]);
+    Ok(())
+}
+
+/// Create a circuit's computation definition
+pub fn init_comp_def_handler(
+    ctx: Context<crate::InitComputationDefinition>,
+    mxe_program: Pubkey,
+    comp_offset: u32,
+) -> Result<()> {
+    require_keys_eq!(mxe_program, crate::ID, StandInError::InvalidArguments);
+    let circuit = circuits::circuit_at(comp_offset)?;
+    
+    let comp_def = &mut ctx.accounts.comp_def_acc;
+    comp_def.comp_def_offset = comp_offset;
+    comp_def.bump = ctx.bumps.comp_def_acc;
+    
+    msg!("[MXE STAND-IN] Comp def {} for {}", comp_offset, circuit.name());
+    Ok(())
+}
+
+/// Record a computation; nothing runs until `execute_computation`
+pub fn queue_handler(ctx: Context<crate::QueueComputation>, args: QueueComputationArgs) -> Result<()> {
+    require_keys_eq!(args.mxe_program, crate::ID, StandInError::InvalidArguments);
+    let circuit = circuits::circuit_at(args.computation_definition_offset)?;
+    
+    msg!(
+        "[MXE STAND-IN] Queued computation {} ({}), {} callbacks",
+        args.comp_offset,
+        circuit.name(),
+        args.custom_callback_instructions.len()
+    );
+    
+    let comp = &mut ctx.accounts.comp;
+    comp.request = args;
+    comp.executed = false;
+    comp.bump = ctx.bumps.comp;
+    Ok(())
+}
+
+/// Run a computation and hand its output to every callback
+///
+/// Each callback gets its discriminator, the computation ID (the offset,
+/// little endian, zero padded to 32 bytes) and the circuit's output.
+pub fn execute_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, crate::ExecuteComputation<'info>>,
+) -> Result<()> {
+    require!(!ctx.accounts.comp.executed, StandInError::AlreadyExecuted);
+    
+    let request = &ctx.accounts.comp.request;
+    let circuit = circuits::circuit_at(request.computation_definition_offset)?;
+    let output = circuits::execute(
+        circuit,
+        &request.args,
+        &ctx.accounts.mxe.seed,
+        request.comp_offset,
+        ctx.remaining_accounts,
+    )?;
+    
+    let mut computation_id = [0u8; 32];
+    computation_id[..8].copy_from_slice(&request.comp_offset.to_le_bytes());
+    
+    let available: Vec<AccountInfo> = [
+        ctx.accounts.comp.to_account_info(),
+        ctx.accounts.comp_def_acc.to_account_info(),
+    ]
+    .into_iter()
+    .chain(ctx.remaining_accounts.iter().cloned())
+    .collect();
+    let find = |pubkey: &Pubkey| {
+        available
+            .iter()
+            .find(|account| account.key == pubkey)
+            .cloned()
+            .ok_or(error!(StandInError::MissingAccount))
+    };
+    
+    for callback in &request.custom_callback_instructions {
+        let mut data = callback.discriminator.clone();
+        data.extend_from_slice(&computation_id);
+        data.extend_from_slice(&output);
+        
+        let mut infos = vec![find(&callback.program_id)?];
+        let mut metas = Vec::with_capacity(callback.accounts.len());
+        for account in &callback.accounts {
+            infos.push(find(&account.pubkey)?);
+            metas.push(AccountMeta {
+                pubkey: account.pubkey,
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            });
+        }
+        
+        invoke(
+            &Instruction {
+                program_id: callback.program_id,
+                accounts: metas,
+                data,
+            },
+            &infos,
+        )?;
+    }
+    
+    msg!(
+        "[MXE STAND-IN] Executed computation {} ({}), {} output bytes",
+        request.comp_offset,
+        circuit.name(),
+        output.len()
+    );
+    
+    ctx.accounts.comp.executed = true;
+    Ok(())
+}
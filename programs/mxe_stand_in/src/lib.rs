@@ -0,0 +1,171 @@
+use anchor_lang::prelude::*;
+
+// Module declarations MUST come before declare_id
+pub mod types;
+pub mod state;
+pub mod errors;
+pub mod circuits;
+pub mod instructions;
+
+declare_id!("Gaiuqov1J3HhL1BHP2W3ReBPfsM4bpcPMaFCiraDx2QH");
+
+pub use state::{Computation, ComputationDefinition, MxeState};
+pub use types::QueueComputationArgs;
+
+/// Local stand-in for the Arcium MXE
+///
+/// Takes the same `init_computation_definition` and `queue_computation`
+/// instructions the poker program sends to Arcium, then runs the queued
+/// circuit in plaintext when a test cranks `execute_computation` and invokes
+/// the computation's callback instructions with the output. Point the poker
+/// program's `ArciumConfig` at this program to run the real (non-mock) code
+/// path on a local validator. Never deploy it anywhere else: inputs are
+/// read unencrypted and every output follows from the test seed.
+#[program]
+pub mod mxe_stand_in {
+    use super::*;
+    
+    /// Create the MXE account with the seed all randomness derives from
+    pub fn initialize(ctx: Context<Initialize>, seed: [u8; 32]) -> Result<()> {
+        instructions::initialize_handler(ctx, seed)
+    }
+    
+    /// Create a circuit's computation definition (Arcium interface)
+    pub fn init_computation_definition(
+        ctx: Context<InitComputationDefinition>,
+        mxe_program: Pubkey,
+        comp_offset: u32,
+    ) -> Result<()> {
+        instructions::init_comp_def_handler(ctx, mxe_program, comp_offset)
+    }
+    
+    /// Record a computation for execution (Arcium interface)
+    pub fn queue_computation(ctx: Context<QueueComputation>, args: QueueComputationArgs) -> Result<()> {
+        instructions::queue_handler(ctx, args)
+    }
+    
+    /// Run a queued computation and invoke its callbacks, as the cluster
+    /// would
+    /// Remaining accounts: every account the arguments reference and the
+    /// callbacks take, including the callback programs
+    pub fn execute_computation<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteComputation<'info>>,
+    ) -> Result<()> {
+        instructions::execute_handler(ctx)
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = MxeState::LEN,
+        seeds = [MxeState::SEED, crate::ID.as_ref()],
+        bump
+    )]
+    pub mxe_account: Account<'info, MxeState>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts in Arcium's order
+#[derive(Accounts)]
+#[instruction(mxe_program: Pubkey, comp_offset: u32)]
+pub struct InitComputationDefinition<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    
+    #[account(mut, seeds = [MxeState::SEED, crate::ID.as_ref()], bump = mxe.bump)]
+    pub mxe: Account<'info, MxeState>,
+    
+    #[account(
+        init,
+        payer = signer,
+        space = ComputationDefinition::LEN,
+        seeds = [ComputationDefinition::SEED, crate::ID.as_ref(), &comp_offset.to_le_bytes()],
+        bump
+    )]
+    pub comp_def_acc: Account<'info, ComputationDefinition>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts in Arcium's order (see `queue_mxe_computation` in the poker
+/// program); the cluster accounts are accepted as passed
+#[derive(Accounts)]
+#[instruction(args: QueueComputationArgs)]
+pub struct QueueComputation<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    
+    /// CHECK: Queuing program's signer PDA; the stand-in trusts any caller
+    pub sign_seed: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = signer,
+        space = Computation::space(&args),
+        seeds = [Computation::SEED, crate::ID.as_ref(), &args.comp_offset.to_le_bytes()],
+        bump
+    )]
+    pub comp: Account<'info, Computation>,
+    
+    #[account(mut, seeds = [MxeState::SEED, crate::ID.as_ref()], bump = mxe.bump)]
+    pub mxe: Account<'info, MxeState>,
+    
+    /// CHECK: No cluster behind the stand-in
+    #[account(mut)]
+    pub executing_pool: UncheckedAccount<'info>,
+    
+    /// CHECK: No cluster behind the stand-in
+    #[account(mut)]
+    pub mempool: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [
+            ComputationDefinition::SEED,
+            crate::ID.as_ref(),
+            &args.computation_definition_offset.to_le_bytes(),
+        ],
+        bump = comp_def_acc.bump
+    )]
+    pub comp_def_acc: Account<'info, ComputationDefinition>,
+    
+    /// CHECK: No cluster behind the stand-in
+    pub cluster: UncheckedAccount<'info>,
+    
+    /// CHECK: No fees are charged
+    pub pool_account: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// CHECK: No Arcium clock behind the stand-in
+    pub clock: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteComputation<'info> {
+    #[account(
+        mut,
+        seeds = [Computation::SEED, crate::ID.as_ref(), &comp.request.comp_offset.to_le_bytes()],
+        bump = comp.bump
+    )]
+    pub comp: Account<'info, Computation>,
+    
+    #[account(seeds = [MxeState::SEED, crate::ID.as_ref()], bump = mxe.bump)]
+    pub mxe: Account<'info, MxeState>,
+    
+    #[account(
+        seeds = [
+            ComputationDefinition::SEED,
+            crate::ID.as_ref(),
+            &comp.request.computation_definition_offset.to_le_bytes(),
+        ],
+        bump = comp_def_acc.bump
+    )]
+    pub comp_def_acc: Account<'info, ComputationDefinition>,
+}
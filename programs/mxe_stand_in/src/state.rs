@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use crate::types::QueueComputationArgs;
+
+/// The stand-in's MXE account, at the PDA the real MXE account lives at
+///
+/// Holds the test seed every computation's randomness and the MXE's deck
+/// "encryption" are derived from, so a run replays exactly for one seed.
+#[account]
+pub struct MxeState {
+    /// Created the stand-in
+    pub authority: Pubkey,
+    
+    /// Test seed
+    pub seed: [u8; 32],
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl MxeState {
+    pub const SEED: &'static [u8] = b"MXEAccount";
+    
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        32 + // seed
+        1; // bump
+}
+
+/// A circuit's computation definition
+#[account]
+pub struct ComputationDefinition {
+    /// Comp def offset, derived from the circuit name
+    pub comp_def_offset: u32,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl ComputationDefinition {
+    pub const SEED: &'static [u8] = b"ComputationDefinitionAccount";
+    
+    pub const LEN: usize = 8 + // discriminator
+        4 + // comp_def_offset
+        1; // bump
+}
+
+/// A queued computation, waiting for `execute_computation`
+#[account]
+pub struct Computation {
+    /// The `queue_computation` request, as received
+    pub request: QueueComputationArgs,
+    
+    /// Output delivered to the callbacks
+    pub executed: bool,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl Computation {
+    pub const SEED: &'static [u8] = b"ComputationAccount";
+    
+    /// Account size for a request
+    pub fn space(request: &QueueComputationArgs) -> usize {
+        8 + // discriminator
+            borsh::to_vec(request).map(|data| data.len()).unwrap_or(0) + // request
+            1 + // executed
+            1 // bump
+    }
+}
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+
+/// Computation input, laid out like Arcium's `Argument`
+///
+/// Mirrors `arcium_poker::arcium::types::Argument` variant for variant, so
+/// the program's `queue_computation` CPI decodes unchanged.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum Argument {
+    PlaintextBool(bool),
+    PlaintextU8(u8),
+    PlaintextU16(u16),
+    PlaintextU32(u32),
+    PlaintextU64(u64),
+    PlaintextU128(u128),
+    PlaintextFloat(f64),
+    EncryptedBool([u8; 32]),
+    EncryptedU8([u8; 32]),
+    EncryptedU16([u8; 32]),
+    EncryptedU32([u8; 32]),
+    EncryptedU64([u8; 32]),
+    EncryptedU128([u8; 32]),
+    EncryptedFloat([u8; 32]),
+    ArcisPubkey([u8; 32]),
+    ArcisSignature([u8; 64]),
+    Account(Pubkey, u32, u32),
+    ManticoreAlgo(String),
+    InputDataset(String),
+}
+
+/// Account of a callback instruction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct CallbackAccount {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+/// Instruction invoked with the computation's output
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct CallbackInstruction {
+    pub program_id: Pubkey,
+    pub discriminator: Vec<u8>,
+    pub accounts: Vec<CallbackAccount>,
+}
+
+/// Arguments of `queue_computation`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct QueueComputationArgs {
+    pub comp_offset: u64,
+    pub computation_definition_offset: u32,
+    pub cluster_index: Option<u16>,
+    pub args: Vec<Argument>,
+    pub mxe_program: Pubkey,
+    pub callback_url: Option<String>,
+    pub custom_callback_instructions: Vec<CallbackInstruction>,
+    pub input_delivery_fee: u64,
+    pub output_delivery_fee: u64,
+    pub cu_price_micro: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arcium_poker::arcium::types as poker;
+
+    #[test]
+    fn test_decodes_the_programs_queue_args() {
+        let deck = Pubkey::new_unique();
+        let queued = poker::QueueComputationArgs {
+            comp_offset: 42,
+            computation_definition_offset: 7,
+            cluster_index: None,
+            args: vec![
+                poker::Argument::EncryptedU8([3; 32]),
+                poker::Argument::PlaintextU8(2),
+                poker::Argument::PlaintextU128(9),
+                poker::Argument::Account(deck, 40, 1680),
+            ],
+            mxe_program: Pubkey::new_unique(),
+            callback_url: None,
+            custom_callback_instructions: vec![poker::CallbackInstruction {
+                program_id: arcium_poker::ID,
+                discriminator: vec![1, 2, 3, 4, 5, 6, 7, 8],
+                accounts: vec![poker::CallbackAccount {
+                    pubkey: Pubkey::new_unique(),
+                    is_signer: false,
+                    is_writable: true,
+                }],
+            }],
+            input_delivery_fee: 0,
+            output_delivery_fee: 0,
+            cu_price_micro: 0,
+        };
+        let mut data = Vec::new();
+        borsh::BorshSerialize::serialize(&queued, &mut data).unwrap();
+
+        let decoded = QueueComputationArgs::try_from_slice(&data).unwrap();
+        assert_eq!(decoded.comp_offset, 42);
+        assert_eq!(decoded.args[1], Argument::PlaintextU8(2));
+        assert_eq!(decoded.args[3], Argument::Account(deck, 40, 1680));
+        assert_eq!(decoded.custom_callback_instructions[0].program_id, arcium_poker::ID);
+
+        let mut encoded = Vec::new();
+        decoded.serialize(&mut encoded).unwrap();
+        assert_eq!(encoded, data);
+    }
+}
@@ -0,0 +1,50 @@
+#!/usr/bin/env ts-node
+
+/**
+ * Initialize Every Computation Definition
+ *
+ * Creates the program's Arcium config (the signer must be the program's
+ * upgrade authority) and circuit config, then calls `init_comp_def` for every
+ * circuit in `encrypted-ixs`. Each circuit is
+ * registered with the SHA-256 of its compiled `build/<circuit>.arcis`.
+ *
+ * Usage (provider from ANCHOR_PROVIDER_URL / ANCHOR_WALLET):
+ *   npx ts-node scripts/init-comp-defs.ts
+ */
+
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { sha256 } from '@noble/hashes/sha256';
+import * as fs from 'fs';
+import * as path from 'path';
+import { ArciumPoker } from '../target/types/arcium_poker';
+import { CIRCUITS, compDefOffset, getCompDefPda, initCircuitConfig } from '../tests/helpers';
+
+function circuitHash(circuitName: string): number[] {
+  const compiled = path.join(__dirname, '..', 'build', `${circuitName}.arcis`);
+  if (!fs.existsSync(compiled)) {
+    throw new Error(`${compiled} not found - run \`arcium build\` first`);
+  }
+  return Array.from(sha256(fs.readFileSync(compiled)));
+}
+
+async function main() {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.ArciumPoker as Program<ArciumPoker>;
+
+  console.log('🔧 Initializing computation definitions...\n');
+  await initCircuitConfig(program, circuitHash);
+
+  for (const [, name] of CIRCUITS) {
+    console.log(`✅ ${name}`);
+    console.log(`   Offset:   ${compDefOffset(name)}`);
+    console.log(`   Comp Def: ${getCompDefPda(name).toBase58()}`);
+  }
+}
+
+main()
+  .then(() => process.exit(0))
+  .catch((err) => {
+    console.error('\n❌ Error:', err);
+    process.exit(1);
+  });
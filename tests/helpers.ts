@@ -1,4 +1,6 @@
 import * as anchor from "@coral-xyz/anchor";
+import { sha256 } from "@noble/hashes/sha256";
+import { keccak_256 } from "@noble/hashes/sha3";
 
 // Re-export MXE crypto helpers
 export * from "./helpers/mxe_crypto";
@@ -55,3 +57,295 @@ export async function airdropSol(
   );
   await connection.confirmTransaction(signature);
 }
+
+/**
+ * Arcium program that owns the MXE and computation definition accounts
+ */
+export const ARCIUM_PROGRAM_ID = new anchor.web3.PublicKey(
+  "BKck65TgoKRokMjQM3datB9oRwJ8rAj2jxPXvHXUvcL6"
+);
+
+/**
+ * Cluster the devnet MXE account was initialized with
+ */
+export const CLUSTER_OFFSET = 1078779259;
+
+/**
+ * Create the Arcium config, or point it at another MXE program
+ *
+ * The provider wallet must be the program's upgrade authority (true for a
+ * program deployed by `anchor test`); it becomes the config admin.
+ *
+ * @returns Whether the config was created or changed
+ */
+export async function initArciumConfig(
+  program: anchor.Program<any>,
+  timeoutSeconds: number = 300,
+  mxeProgramId: anchor.web3.PublicKey = ARCIUM_PROGRAM_ID
+): Promise<boolean> {
+  const [arciumConfig] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("arcium_config")],
+    program.programId
+  );
+  const params = {
+    mxeProgramId,
+    clusterOffset: CLUSTER_OFFSET,
+    callbackAuthority: mxeProgramId,
+    minNodes: 2,
+    timeout: new anchor.BN(timeoutSeconds),
+  };
+
+  const existing = await program.account.arciumConfig.fetchNullable(arciumConfig);
+  if (existing) {
+    if (existing.mxeProgramId.equals(mxeProgramId)) {
+      return false;
+    }
+    await program.methods
+      .updateArciumConfig(params)
+      .accounts({ admin: program.provider.publicKey })
+      .rpc();
+    return true;
+  }
+
+  const [programData] = anchor.web3.PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+  );
+  await program.methods
+    .initArciumConfig(params)
+    .accounts({ programData })
+    .rpc();
+  return true;
+}
+
+/**
+ * Circuits in `encrypted-ixs`, as `Circuit` variant name and circuit name
+ */
+export const CIRCUITS: [string, string][] = [
+  ["shuffleDeck", "shuffle_deck"],
+  ["shuffleShortDeck", "shuffle_short_deck"],
+  ["dealCard", "deal_card"],
+  ["revealHoleCards", "reveal_hole_cards"],
+  ["revealCommunity", "reveal_community"],
+  ["evaluateShowdown", "evaluate_showdown"],
+  ["generateRandom", "generate_random"],
+  ["dealUpCard", "deal_up_card"],
+  ["dealHoleCards", "deal_hole_cards"],
+  ["drawCards", "draw_cards"],
+  ["recycleDiscards", "recycle_discards"],
+];
+
+/**
+ * Computation definition offset of a circuit: first four bytes of the
+ * SHA-256 of its name, little endian (matches `Circuit::comp_def_offset`)
+ */
+export function compDefOffset(circuitName: string): number {
+  return Buffer.from(sha256(circuitName)).readUInt32LE(0);
+}
+
+/**
+ * Helper function to derive a circuit's computation definition PDA
+ */
+export function getCompDefPda(
+  circuitName: string,
+  mxeProgramId: anchor.web3.PublicKey = ARCIUM_PROGRAM_ID
+): anchor.web3.PublicKey {
+  const offset = Buffer.alloc(4);
+  offset.writeUInt32LE(compDefOffset(circuitName));
+
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("ComputationDefinitionAccount"),
+      mxeProgramId.toBuffer(),
+      offset
+    ],
+    mxeProgramId
+  )[0];
+}
+
+/**
+ * Create the circuit config and register every circuit, once per validator
+ * and MXE program
+ *
+ * Creates the Arcium config first if needed, and registers every circuit
+ * again when the config is pointed at a different MXE program. `startGame`
+ * and the MXE callbacks look the comp def offsets up in it.
+ */
+export async function initCircuitConfig(
+  program: anchor.Program<any>,
+  circuitHash: (circuitName: string) => number[] = () => new Array(32).fill(0),
+  mxeProgramId: anchor.web3.PublicKey = ARCIUM_PROGRAM_ID
+): Promise<void> {
+  const [circuitConfig] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("circuit_config")],
+    program.programId
+  );
+  const mxeChanged = await initArciumConfig(program, 300, mxeProgramId);
+  if (await program.provider.connection.getAccountInfo(circuitConfig)) {
+    if (!mxeChanged) {
+      return;
+    }
+  } else {
+    await program.methods.initCircuitConfig().rpc();
+  }
+
+  const [mxeAccount] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("MXEAccount"), mxeProgramId.toBuffer()],
+    mxeProgramId
+  );
+  for (const [variant, name] of CIRCUITS) {
+    await program.methods
+      .initCompDef({ [variant]: {} }, circuitHash(name))
+      .accounts({
+        mxeProgram: mxeProgramId,
+        mxeAccount,
+        compDefAccount: getCompDefPda(name, mxeProgramId),
+      })
+      .rpc();
+  }
+}
+
+/**
+ * Create the MXE stand-in's MXE account, once per validator
+ *
+ * Every shuffle and deck encryption of the stand-in follows from `seed`.
+ */
+export async function initMxeStandIn(
+  standIn: anchor.Program<any>,
+  seed: number[]
+): Promise<void> {
+  const [mxeAccount] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("MXEAccount"), standIn.programId.toBuffer()],
+    standIn.programId
+  );
+  if (await standIn.provider.connection.getAccountInfo(mxeAccount)) {
+    return;
+  }
+
+  await standIn.methods.initialize(seed).rpc();
+}
+
+/**
+ * Offset of the next computation a game queues
+ *
+ * Mirrors `Game::next_computation_offset`: the first eight bytes (LE) of
+ * keccak256(authority || game_id || computations_queued).
+ */
+export function nextComputationOffset(game: {
+  authority: anchor.web3.PublicKey;
+  gameId: anchor.BN;
+  computationsQueued: anchor.BN;
+}): anchor.BN {
+  const hash = keccak_256(
+    Buffer.concat([
+      game.authority.toBuffer(),
+      game.gameId.toArrayLike(Buffer, "le", 8),
+      game.computationsQueued.toArrayLike(Buffer, "le", 8),
+    ])
+  );
+  return new anchor.BN(hash.slice(0, 8), "le");
+}
+
+/**
+ * Arcium accounts to queue a circuit's computation with, as `startGame`
+ * names them (the PDAs `ArciumConfig::validate_mxe_accounts` expects)
+ */
+export function mxeQueueAccounts(
+  program: anchor.Program<any>,
+  mxeProgramId: anchor.web3.PublicKey,
+  circuitName: string,
+  computationOffset: anchor.BN
+) {
+  const pda = (...seeds: Buffer[]) =>
+    anchor.web3.PublicKey.findProgramAddressSync(seeds, mxeProgramId)[0];
+  const cluster = Buffer.alloc(4);
+  cluster.writeUInt32LE(CLUSTER_OFFSET);
+
+  return {
+    mxeProgram: mxeProgramId,
+    mxeAccount: pda(Buffer.from("MXEAccount"), mxeProgramId.toBuffer()),
+    compDefAccount: getCompDefPda(circuitName, mxeProgramId),
+    mempoolAccount: pda(Buffer.from("Mempool"), cluster),
+    executingPoolAccount: pda(Buffer.from("Execpool"), cluster),
+    clusterAccount: pda(Buffer.from("Cluster"), cluster),
+    computationAccount: pda(
+      Buffer.from("ComputationAccount"),
+      mxeProgramId.toBuffer(),
+      computationOffset.toArrayLike(Buffer, "le", 8)
+    ),
+    signSeed: anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("SignerAccount")],
+      program.programId
+    )[0],
+    stakingPool: pda(Buffer.from("FeePool")),
+    clock: pda(Buffer.from("ClockAccount")),
+  };
+}
+
+/**
+ * Run a computation queued on the MXE stand-in, as the cluster would
+ *
+ * Passes every account the computation's arguments reference and its
+ * callbacks take, so the stand-in can invoke the callbacks.
+ */
+export async function executeComputation(
+  standIn: anchor.Program<any>,
+  computation: anchor.web3.PublicKey
+): Promise<string> {
+  const { request } = await standIn.account.computation.fetch(computation);
+
+  const accounts = new Map<string, anchor.web3.AccountMeta>();
+  const add = (pubkey: anchor.web3.PublicKey, isWritable: boolean) => {
+    const existing = accounts.get(pubkey.toBase58());
+    accounts.set(pubkey.toBase58(), {
+      pubkey,
+      isSigner: false,
+      isWritable: isWritable || (existing?.isWritable ?? false),
+    });
+  };
+  for (const arg of request.args) {
+    if (arg.account) {
+      add(arg.account[0], false);
+    }
+  }
+  for (const callback of request.customCallbackInstructions) {
+    add(callback.programId, false);
+    for (const account of callback.accounts) {
+      add(account.pubkey, account.isWritable);
+    }
+  }
+
+  const offset = Buffer.alloc(4);
+  offset.writeUInt32LE(request.computationDefinitionOffset);
+  const [compDefAcc] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("ComputationDefinitionAccount"), standIn.programId.toBuffer(), offset],
+    standIn.programId
+  );
+
+  return standIn.methods
+    .executeComputation()
+    .accountsPartial({ comp: computation, compDefAcc })
+    .remainingAccounts([...accounts.values()])
+    .rpc();
+}
+
+/**
+ * Decrypt a card the MXE stand-in encrypted to a player
+ *
+ * Mirrors `shared_keystream` in `programs/mxe_stand_in`: the stand-in has no
+ * x25519 exchange, so the registered public key stands in for the shared
+ * secret.
+ */
+export function decryptStandInCard(
+  encryptionPubkey: number[],
+  card: { ciphertext: number[]; nonce: anchor.BN }
+): number {
+  const keystream = keccak_256(
+    Buffer.concat([
+      Buffer.from("mxe-stand-in/shared"),
+      Buffer.from(encryptionPubkey),
+      card.nonce.toArrayLike(Buffer, "le", 16),
+    ])
+  );
+  return card.ciphertext[0] ^ keystream[0];
+}
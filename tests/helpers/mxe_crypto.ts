@@ -7,7 +7,9 @@
  * IMPORTANT: Encryption happens CLIENT-SIDE, not in the Solana program!
  */
 
-import { PublicKey } from "@solana/web3.js";
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { keccak_256 } from "@noble/hashes/sha3";
 
 // Note: These are placeholder implementations
 // In production, install and use: npm install @arcium-hq/client
@@ -40,6 +42,97 @@ export function generatePlayerEntropy(): Uint8Array {
   return entropy;
 }
 
+/**
+ * Commitment a player posts with `commit_entropy`
+ *
+ * Mirrors `player::entropy::entropy_commitment` on-chain:
+ * keccak256("arcium-poker/entropy" || game || player || preimage).
+ *
+ * @returns 32-byte commitment as a number array (Anchor `[u8; 32]`)
+ */
+export function entropyCommitment(
+  preimage: Uint8Array,
+  game: PublicKey,
+  player: PublicKey
+): number[] {
+  const domain = new TextEncoder().encode("arcium-poker/entropy");
+  const message = new Uint8Array(domain.length + 32 + 32 + preimage.length);
+  message.set(domain, 0);
+  message.set(game.toBytes(), domain.length);
+  message.set(player.toBytes(), domain.length + 32);
+  message.set(preimage, domain.length + 64);
+  return Array.from(keccak_256(message));
+}
+
+/**
+ * Run the commit-reveal round so `startGame` can shuffle
+ *
+ * Every player commits first and then reveals; reveals close the commit
+ * window, so the order matters.
+ */
+export async function commitAndRevealEntropy(
+  program: anchor.Program<any>,
+  game: PublicKey,
+  players: Keypair[],
+  playerStates: PublicKey[]
+): Promise<void> {
+  const preimages = players.map(() => generatePlayerEntropy());
+
+  for (let i = 0; i < players.length; i++) {
+    await program.methods
+      .commitEntropy(entropyCommitment(preimages[i], game, players[i].publicKey))
+      .accounts({
+        game,
+        playerState: playerStates[i],
+        player: players[i].publicKey,
+      })
+      .signers([players[i]])
+      .rpc();
+  }
+
+  for (let i = 0; i < players.length; i++) {
+    await program.methods
+      .revealEntropy(Array.from(preimages[i]))
+      .accounts({
+        game,
+        playerState: playerStates[i],
+        player: players[i].publicKey,
+      })
+      .signers([players[i]])
+      .rpc();
+  }
+}
+
+/**
+ * Player states passed to `startGame` as remaining accounts, in seat order
+ */
+export function playerStateAccounts(playerStates: PublicKey[]) {
+  return playerStates.map((pubkey) => ({
+    pubkey,
+    isWritable: true,
+    isSigner: false,
+  }));
+}
+
+/**
+ * Generate an x25519 public key to register when joining a game
+ *
+ * Hole cards are encrypted to this key by the deal circuit. Production
+ * implementation would keep the secret key to decrypt them:
+ * ```typescript
+ * import { x25519 } from "@arcium-hq/client";
+ *
+ * const secretKey = x25519.utils.randomPrivateKey();
+ * const publicKey = x25519.getPublicKey(secretKey);
+ * ```
+ *
+ * @returns 32-byte public key as a number array (Anchor `[u8; 32]`)
+ */
+export function generateEncryptionPubkey(): number[] {
+  // Placeholder: random bytes stand in for a real x25519 public key
+  return Array.from(generatePlayerEntropy());
+}
+
 /**
  * Encrypt data for Arcium MXE using Rescue cipher
  *
@@ -218,19 +311,16 @@ export function deriveMxeAccounts(
  * Example usage:
  *
  * ```typescript
- * import { generatePlayerEntropy, encryptForMxe } from "./helpers/mxe_crypto";
- *
- * // Generate entropy for player
- * const entropy = generatePlayerEntropy();
- * console.log("Generated 32 bytes of entropy");
+ * import { commitAndRevealEntropy } from "./helpers/mxe_crypto";
  *
- * // Encrypt for MXE
- * const encrypted = encryptForMxe(entropy, playerPublicKey);
+ * // Every seated player commits to and then reveals 32 bytes of entropy
+ * await commitAndRevealEntropy(program, gamePda, players, playerStates);
  *
- * // Submit to Solana program
+ * // Start the hand; the player states let the program read the reveals
  * await program.methods
- *   .startGame([Array.from(encrypted.ciphertext)])
+ *   .startGame()
  *   .accounts({ ... })
+ *   .remainingAccounts(playerStateAccounts(playerStates))
  *   .rpc();
  * ```
  */
@@ -2,13 +2,17 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { ArciumPoker } from "../target/types/arcium_poker";
 import { expect } from "chai";
-import { getGamePda, getPlayerStatePda, airdropSol } from "./helpers";
+import { getGamePda, getPlayerStatePda, airdropSol, generateEncryptionPubkey, commitAndRevealEntropy, playerStateAccounts, initCircuitConfig } from "./helpers";
 
 describe("Betting Tests", () => {
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
 
   const program = anchor.workspace.ArciumPoker as Program<ArciumPoker>;
+
+  before(async () => {
+    await initCircuitConfig(program);
+  });
   
   let gamePda: anchor.web3.PublicKey;
   let gameId: number;
@@ -52,7 +56,7 @@ describe("Betting Tests", () => {
       playerStates.push(playerStatePda);
 
       await program.methods
-        .joinGame(new anchor.BN(10000))
+        .joinGame(new anchor.BN(10000), generateEncryptionPubkey())
         .accounts({
           game: gamePda,
           player: player.publicKey,
@@ -65,15 +69,15 @@ describe("Betting Tests", () => {
   describe("Fold Action", () => {
     it("Player folds successfully", async () => {
       // Start game first
-      // Generate dummy entropy for each player (3 players)
-      const playerEntropy = Array(3).fill(0).map(() => Array(32).fill(0));
+      await commitAndRevealEntropy(program, gamePda, players, playerStates);
       
       await program.methods
-        .startGame(playerEntropy)
+        .startGame()
         .accounts({
           game: gamePda,
           authority: provider.wallet.publicKey,
         })
+        .remainingAccounts(playerStateAccounts(playerStates))
         .rpc();
 
       // Current player folds
@@ -97,15 +101,15 @@ describe("Betting Tests", () => {
     });
 
     it("Fails when not player's turn", async () => {
-      // Generate dummy entropy for each player (3 players)
-      const playerEntropy = Array(3).fill(0).map(() => Array(32).fill(0));
+      await commitAndRevealEntropy(program, gamePda, players, playerStates);
       
       await program.methods
-        .startGame(playerEntropy)
+        .startGame()
         .accounts({
           game: gamePda,
           authority: provider.wallet.publicKey,
         })
+        .remainingAccounts(playerStateAccounts(playerStates))
         .rpc();
 
       const game = await program.account.game.fetch(gamePda);
@@ -132,15 +136,15 @@ describe("Betting Tests", () => {
 
   describe("Check Action", () => {
     it("Player checks when no bet", async () => {
-      // Generate dummy entropy for each player (3 players)
-      const playerEntropy = Array(3).fill(0).map(() => Array(32).fill(0));
+      await commitAndRevealEntropy(program, gamePda, players, playerStates);
       
       await program.methods
-        .startGame(playerEntropy)
+        .startGame()
         .accounts({
           game: gamePda,
           authority: provider.wallet.publicKey,
         })
+        .remainingAccounts(playerStateAccounts(playerStates))
         .rpc();
 
       // Complete pre-flop by having all players call the big blind
@@ -171,15 +175,15 @@ describe("Betting Tests", () => {
     });
 
     it("Fails when there's a bet to call", async () => {
-      // Generate dummy entropy for each player (3 players)
-      const playerEntropy = Array(3).fill(0).map(() => Array(32).fill(0));
+      await commitAndRevealEntropy(program, gamePda, players, playerStates);
       
       await program.methods
-        .startGame(playerEntropy)
+        .startGame()
         .accounts({
           game: gamePda,
           authority: provider.wallet.publicKey,
         })
+        .remainingAccounts(playerStateAccounts(playerStates))
         .rpc();
 
       let game = await program.account.game.fetch(gamePda);
@@ -217,15 +221,15 @@ describe("Betting Tests", () => {
 
   describe("Bet/Raise Action", () => {
     it("Player bets valid amount", async () => {
-      // Generate dummy entropy for each player (3 players)
-      const playerEntropy = Array(3).fill(0).map(() => Array(32).fill(0));
+      await commitAndRevealEntropy(program, gamePda, players, playerStates);
       
       await program.methods
-        .startGame(playerEntropy)
+        .startGame()
         .accounts({
           game: gamePda,
           authority: provider.wallet.publicKey,
         })
+        .remainingAccounts(playerStateAccounts(playerStates))
         .rpc();
 
       const game = await program.account.game.fetch(gamePda);
@@ -248,15 +252,15 @@ describe("Betting Tests", () => {
     });
 
     it("Fails when bet amount > chip stack", async () => {
-      // Generate dummy entropy for each player (3 players)
-      const playerEntropy = Array(3).fill(0).map(() => Array(32).fill(0));
+      await commitAndRevealEntropy(program, gamePda, players, playerStates);
       
       await program.methods
-        .startGame(playerEntropy)
+        .startGame()
         .accounts({
           game: gamePda,
           authority: provider.wallet.publicKey,
         })
+        .remainingAccounts(playerStateAccounts(playerStates))
         .rpc();
 
       const game = await program.account.game.fetch(gamePda);
@@ -279,15 +283,15 @@ describe("Betting Tests", () => {
     });
 
     it("Fails when raise is less than minimum", async () => {
-      // Generate dummy entropy for each player (3 players)
-      const playerEntropy = Array(3).fill(0).map(() => Array(32).fill(0));
+      await commitAndRevealEntropy(program, gamePda, players, playerStates);
       
       await program.methods
-        .startGame(playerEntropy)
+        .startGame()
         .accounts({
           game: gamePda,
           authority: provider.wallet.publicKey,
         })
+        .remainingAccounts(playerStateAccounts(playerStates))
         .rpc();
 
       let game = await program.account.game.fetch(gamePda);
@@ -325,15 +329,15 @@ describe("Betting Tests", () => {
 
   describe("All-In Action", () => {
     it("Player goes all-in", async () => {
-      // Generate dummy entropy for each player (3 players)
-      const playerEntropy = Array(3).fill(0).map(() => Array(32).fill(0));
+      await commitAndRevealEntropy(program, gamePda, players, playerStates);
       
       await program.methods
-        .startGame(playerEntropy)
+        .startGame()
         .accounts({
           game: gamePda,
           authority: provider.wallet.publicKey,
         })
+        .remainingAccounts(playerStateAccounts(playerStates))
         .rpc();
 
       const game = await program.account.game.fetch(gamePda);
@@ -357,15 +361,15 @@ describe("Betting Tests", () => {
     });
 
     it("All-in player cannot act again", async () => {
-      // Generate dummy entropy for each player (3 players)
-      const playerEntropy = Array(3).fill(0).map(() => Array(32).fill(0));
+      await commitAndRevealEntropy(program, gamePda, players, playerStates);
       
       await program.methods
-        .startGame(playerEntropy)
+        .startGame()
         .accounts({
           game: gamePda,
           authority: provider.wallet.publicKey,
         })
+        .remainingAccounts(playerStateAccounts(playerStates))
         .rpc();
 
       let game = await program.account.game.fetch(gamePda);
@@ -401,6 +405,45 @@ describe("Betting Tests", () => {
       expect(allInPlayerState.isAllIn).to.be.true;
       expect(allInPlayerState.chipStack.toNumber()).to.equal(0);
     });
+
+    it("All-in on a pot-limit table bets the pot and keeps the rest", async () => {
+      await program.methods
+        .configureGame({ texasHoldem: {} }, { potLimit: {} }, null, null)
+        .accounts({ game: gamePda, authority: provider.wallet.publicKey })
+        .rpc();
+      await commitAndRevealEntropy(program, gamePda, players, playerStates);
+
+      await program.methods
+        .startGame()
+        .accounts({
+          game: gamePda,
+          authority: provider.wallet.publicKey,
+        })
+        .remainingAccounts(playerStateAccounts(playerStates))
+        .rpc();
+
+      const game = await program.account.game.fetch(gamePda);
+      const idx = game.currentPlayerIndex;
+      const before = await program.account.playerState.fetch(playerStates[idx]);
+
+      await program.methods
+        .playerAction({ allIn: {} })
+        .accounts({
+          game: gamePda,
+          playerState: playerStates[idx],
+          player: players[idx].publicKey,
+        })
+        .signers([players[idx]])
+        .rpc();
+
+      // Call 100, then raise by the 250 in the pot after the call
+      const after = await program.account.playerState.fetch(playerStates[idx]);
+      const callAmount = game.currentBet.toNumber() - before.currentBet.toNumber();
+      const potAfterCall = game.pot.toNumber() + callAmount;
+      expect(after.currentBet.toNumber()).to.equal(before.currentBet.toNumber() + callAmount + potAfterCall);
+      expect(after.chipStack.toNumber()).to.be.greaterThan(0);
+      expect(after.isAllIn).to.be.false;
+    });
   });
 
   describe("Side Pot Creation", () => {
@@ -424,7 +467,7 @@ describe("Betting Tests", () => {
       );
 
       await program.methods
-        .joinGame(new anchor.BN(5000)) // Minimum buy-in
+        .joinGame(new anchor.BN(5000), generateEncryptionPubkey()) // Minimum buy-in
         .accounts({
           game: gamePda,
           player: shortStack.publicKey,
@@ -436,15 +479,15 @@ describe("Betting Tests", () => {
       players.push(shortStack);
       playerStates.push(shortStackPda);
 
-      // Generate dummy entropy for each player (4 players: 3 original + shortStack)
-      const playerEntropy = Array(4).fill(0).map(() => Array(32).fill(0));
+      await commitAndRevealEntropy(program, gamePda, players, playerStates);
       
       await program.methods
-        .startGame(playerEntropy)
+        .startGame()
         .accounts({
           game: gamePda,
           authority: provider.wallet.publicKey,
         })
+        .remainingAccounts(playerStateAccounts(playerStates))
         .rpc();
 
       // All players call to complete pre-flop
@@ -2,7 +2,7 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { ArciumPoker } from "../target/types/arcium_poker";
 import { expect } from "chai";
-import { getGamePda, getPlayerStatePda, airdropSol } from "./helpers";
+import { getGamePda, getPlayerStatePda, airdropSol, generateEncryptionPubkey, commitAndRevealEntropy, playerStateAccounts, initCircuitConfig } from "./helpers";
 
 describe("Edge Cases and Security Tests", () => {
   const provider = anchor.AnchorProvider.env();
@@ -10,6 +10,10 @@ describe("Edge Cases and Security Tests", () => {
 
   const program = anchor.workspace.ArciumPoker as Program<ArciumPoker>;
 
+  before(async () => {
+    await initCircuitConfig(program);
+  });
+
   describe("Chip Conservation", () => {
     it("Total chips remain constant throughout game", async () => {
       const gameId = Date.now();
@@ -61,7 +65,7 @@ describe("Edge Cases and Security Tests", () => {
         playerStates.push(playerStatePda);
 
         await program.methods
-          .joinGame(new anchor.BN(buyIns[i]))
+          .joinGame(new anchor.BN(buyIns[i]), generateEncryptionPubkey())
           .accounts({
             game: gamePda,
             player: player.publicKey,
@@ -73,15 +77,15 @@ describe("Edge Cases and Security Tests", () => {
         totalChips += buyIns[i];
       }
 
-      // Generate dummy entropy for each player
-      const playerEntropy = Array(numPlayers).fill(0).map(() => Array(32).fill(0));
+      await commitAndRevealEntropy(program, gamePda, players, playerStates);
       
       await program.methods
-        .startGame(playerEntropy)
+        .startGame()
         .accounts({
           game: gamePda,
           authority: provider.wallet.publicKey,
         })
+        .remainingAccounts(playerStateAccounts(playerStates))
         .rpc();
 
       // Play some actions
@@ -136,6 +140,7 @@ describe("Edge Cases and Security Tests", () => {
 
       // Add players
       const players: anchor.web3.Keypair[] = [];
+      const playerStates: anchor.web3.PublicKey[] = [];
       for (let i = 0; i < 2; i++) {
         const player = anchor.web3.Keypair.generate();
         players.push(player);
@@ -154,9 +159,10 @@ describe("Edge Cases and Security Tests", () => {
           ],
           program.programId
         );
+        playerStates.push(playerStatePda);
 
         await program.methods
-          .joinGame(new anchor.BN(10000))
+          .joinGame(new anchor.BN(10000), generateEncryptionPubkey())
           .accounts({
             game: gamePda,
             player: player.publicKey,
@@ -165,16 +171,15 @@ describe("Edge Cases and Security Tests", () => {
           .rpc();
       }
 
-      // Generate dummy entropy for each player
-      const playerCount = 2; // or 3, depending on test
-      const playerEntropy = Array(playerCount).fill(0).map(() => Array(32).fill(0));
+      await commitAndRevealEntropy(program, gamePda, players, playerStates);
       
       await program.methods
-        .startGame(playerEntropy)
+        .startGame()
         .accounts({
           game: gamePda,
           authority: provider.wallet.publicKey,
         })
+        .remainingAccounts(playerStateAccounts(playerStates))
         .rpc();
 
       const game = await program.account.game.fetch(gamePda);
@@ -248,7 +253,7 @@ describe("Edge Cases and Security Tests", () => {
       );
 
       await program.methods
-        .joinGame(new anchor.BN(5000))
+        .joinGame(new anchor.BN(5000), generateEncryptionPubkey())
         .accounts({
           game: gamePda,
           player: player.publicKey,
@@ -307,7 +312,7 @@ describe("Edge Cases and Security Tests", () => {
         playerStates.push(playerStatePda);
 
         await program.methods
-          .joinGame(new anchor.BN(10000))
+          .joinGame(new anchor.BN(10000), generateEncryptionPubkey())
           .accounts({
             game: gamePda,
             player: player.publicKey,
@@ -316,16 +321,15 @@ describe("Edge Cases and Security Tests", () => {
           .rpc();
       }
 
-      // Generate dummy entropy for each player
-      const playerCount = 2; // or 3, depending on test
-      const playerEntropy = Array(playerCount).fill(0).map(() => Array(32).fill(0));
+      await commitAndRevealEntropy(program, gamePda, players, playerStates);
       
       await program.methods
-        .startGame(playerEntropy)
+        .startGame()
         .accounts({
           game: gamePda,
           authority: provider.wallet.publicKey,
         })
+        .remainingAccounts(playerStateAccounts(playerStates))
         .rpc();
 
       // Try to have wrong player act
@@ -411,7 +415,7 @@ describe("Edge Cases and Security Tests", () => {
         );
 
         await program.methods
-          .joinGame(new anchor.BN(10000))
+          .joinGame(new anchor.BN(10000), generateEncryptionPubkey())
           .accounts({
             game: gamePda,
             player: player.publicKey,
@@ -462,7 +466,7 @@ describe("Edge Cases and Security Tests", () => {
 
       try {
         await program.methods
-          .joinGame(new anchor.BN(0)) // Zero buy-in
+          .joinGame(new anchor.BN(0), generateEncryptionPubkey()) // Zero buy-in
           .accounts({
             game: gamePda,
             player: player.publicKey,
@@ -2,12 +2,17 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { ArciumPoker } from "../target/types/arcium_poker";
 import { expect } from "chai";
+import { generateEncryptionPubkey, commitAndRevealEntropy, playerStateAccounts, initCircuitConfig } from "./helpers";
 
 describe("Game Flow Tests", () => {
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
 
   const program = anchor.workspace.ArciumPoker as Program<ArciumPoker>;
+
+  before(async () => {
+    await initCircuitConfig(program);
+  });
   
   let gamePda: anchor.web3.PublicKey;
   let gameId: number;
@@ -61,7 +66,7 @@ describe("Game Flow Tests", () => {
       playerStates.push(playerStatePda);
 
       await program.methods
-        .joinGame(new anchor.BN(10000))
+        .joinGame(new anchor.BN(10000), generateEncryptionPubkey())
         .accounts({
           game: gamePda,
           playerState: playerStatePda,
@@ -75,13 +80,14 @@ describe("Game Flow Tests", () => {
 
   describe("Game Start", () => {
     it("Starts game with minimum players", async () => {
-      const playerEntropy = Array(3).fill(0).map(() => Array(32).fill(0));
+      await commitAndRevealEntropy(program, gamePda, players, playerStates);
       await program.methods
-        .startGame(playerEntropy)
+        .startGame()
         .accounts({
           game: gamePda,
           authority: provider.wallet.publicKey,
         })
+        .remainingAccounts(playerStateAccounts(playerStates))
         .rpc();
 
       const game = await program.account.game.fetch(gamePda);
@@ -130,7 +136,7 @@ describe("Game Flow Tests", () => {
       );
 
       await program.methods
-        .joinGame(new anchor.BN(10000))
+        .joinGame(new anchor.BN(10000), generateEncryptionPubkey())
         .accounts({
           game: newGamePda,
           playerState: playerStatePda,
@@ -141,9 +147,8 @@ describe("Game Flow Tests", () => {
         .rpc();
 
       try {
-        const playerEntropy2 = Array(1).fill(0).map(() => Array(32).fill(0));
         await program.methods
-          .startGame(playerEntropy2)
+          .startGame()
           .accounts({
             game: newGamePda,
             authority: provider.wallet.publicKey,
@@ -156,23 +161,24 @@ describe("Game Flow Tests", () => {
     });
 
     it("Fails when game already started", async () => {
-      const playerEntropy = Array(3).fill(0).map(() => Array(32).fill(0));
+      await commitAndRevealEntropy(program, gamePda, players, playerStates);
       await program.methods
-        .startGame(playerEntropy)
+        .startGame()
         .accounts({
           game: gamePda,
           authority: provider.wallet.publicKey,
         })
+        .remainingAccounts(playerStateAccounts(playerStates))
         .rpc();
 
       try {
-        const playerEntropy2 = Array(3).fill(0).map(() => Array(32).fill(0));
         await program.methods
-          .startGame(playerEntropy2)
+          .startGame()
           .accounts({
             game: gamePda,
             authority: provider.wallet.publicKey,
           })
+          .remainingAccounts(playerStateAccounts(playerStates))
           .rpc();
         expect.fail("Should have thrown error");
       } catch (error) {
@@ -189,13 +195,13 @@ describe("Game Flow Tests", () => {
       await provider.connection.confirmTransaction(signature);
 
       try {
-        const playerEntropy = Array(3).fill(0).map(() => Array(32).fill(0));
         await program.methods
-          .startGame(playerEntropy)
+          .startGame()
           .accounts({
             game: gamePda,
             authority: nonAuthority.publicKey,
           })
+          .remainingAccounts(playerStateAccounts(playerStates))
           .signers([nonAuthority])
           .rpc();
         expect.fail("Should have thrown error");
@@ -207,13 +213,14 @@ describe("Game Flow Tests", () => {
 
   describe("Stage Transitions", () => {
     beforeEach(async () => {
-      const playerEntropy = Array(3).fill(0).map(() => Array(32).fill(0));
+      await commitAndRevealEntropy(program, gamePda, players, playerStates);
       await program.methods
-        .startGame(playerEntropy)
+        .startGame()
         .accounts({
           game: gamePda,
           authority: provider.wallet.publicKey,
         })
+        .remainingAccounts(playerStateAccounts(playerStates))
         .rpc();
     });
 
@@ -319,13 +326,14 @@ describe("Game Flow Tests", () => {
 
   describe("Early Game End", () => {
     beforeEach(async () => {
-      const playerEntropy = Array(3).fill(0).map(() => Array(32).fill(0));
+      await commitAndRevealEntropy(program, gamePda, players, playerStates);
       await program.methods
-        .startGame(playerEntropy)
+        .startGame()
         .accounts({
           game: gamePda,
           authority: provider.wallet.publicKey,
         })
+        .remainingAccounts(playerStateAccounts(playerStates))
         .rpc();
     });
 
@@ -351,13 +359,14 @@ describe("Game Flow Tests", () => {
 
   describe("New Hand", () => {
     it("Starts new hand after previous completes", async () => {
-      const playerEntropy = Array(3).fill(0).map(() => Array(32).fill(0));
+      await commitAndRevealEntropy(program, gamePda, players, playerStates);
       await program.methods
-        .startGame(playerEntropy)
+        .startGame()
         .accounts({
           game: gamePda,
           authority: provider.wallet.publicKey,
         })
+        .remainingAccounts(playerStateAccounts(playerStates))
         .rpc();
 
       // Complete a hand (all fold except one)
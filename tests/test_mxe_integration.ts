@@ -2,6 +2,7 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { ArciumPoker } from "../target/types/arcium_poker";
 import { expect } from "chai";
+import { generateEncryptionPubkey, commitAndRevealEntropy, playerStateAccounts, initCircuitConfig } from "./helpers";
 
 /**
  * MXE Integration Tests
@@ -15,8 +16,9 @@ import { expect } from "chai";
  * 3. Computation definition PDAs
  * 4. Mempool and cluster accounts
  * 
- * For development/testing without MXE deployment, the program
- * automatically falls back to mock mode.
+ * For development/testing without MXE deployment, build the program
+ * with `--features mock-mpc`; the default build never falls back to
+ * the mock.
  */
 
 describe("MXE Integration Tests", () => {
@@ -25,6 +27,10 @@ describe("MXE Integration Tests", () => {
 
   const program = anchor.workspace.ArciumPoker as Program<ArciumPoker>;
 
+  before(async () => {
+    await initCircuitConfig(program);
+  });
+
   // MXE Program Configuration
   // Replace these with actual values after deploying encrypted-ixs
   const MXE_PROGRAM_ID = new anchor.web3.PublicKey(
@@ -81,7 +87,7 @@ describe("MXE Integration Tests", () => {
 
         // Join game
         await program.methods
-          .joinGame(new anchor.BN(10000))
+          .joinGame(new anchor.BN(10000), generateEncryptionPubkey())
           .accounts({
             game: gamePda,
             player: player.publicKey,
@@ -101,15 +107,16 @@ describe("MXE Integration Tests", () => {
         playerStates.push(playerStatePda);
       }
 
-      // Start game (without MXE accounts = mock mode)
-      // Note: We don't pass remaining accounts - the Rust code handles this
-      const playerEntropy = Array(3).fill(0).map(() => Array(32).fill(0));
+      // Start game (mock-mpc build computes the shuffle locally)
+      // Remaining accounts carry the player states with their revealed entropy
+      await commitAndRevealEntropy(program, gamePda, players, playerStates);
       await program.methods
-        .startGame(playerEntropy)
+        .startGame()
         .accounts({
           game: gamePda,
           authority: provider.wallet.publicKey,
         })
+        .remainingAccounts(playerStateAccounts(playerStates))
         .rpc();
 
       // Verify game started
@@ -154,10 +161,11 @@ describe("MXE Integration Tests", () => {
         MXE_PROGRAM_ID
       );
 
+      // ... (commit and reveal entropy - same as above)
+
       // Start game WITH MXE accounts
-      const playerEntropy = Array(2).fill(0).map(() => Array(32).fill(0));
       await program.methods
-        .startGame(playerEntropy)
+        .startGame()
         .accounts({
           game: gamePda,
           authority: provider.wallet.publicKey,
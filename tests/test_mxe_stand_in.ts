@@ -0,0 +1,420 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { ArciumPoker } from "../target/types/arcium_poker";
+import { MxeStandIn } from "../target/types/mxe_stand_in";
+import { expect } from "chai";
+import {
+  generateEncryptionPubkey,
+  commitAndRevealEntropy,
+  playerStateAccounts,
+  initCircuitConfig,
+  initMxeStandIn,
+  nextComputationOffset,
+  mxeQueueAccounts,
+  executeComputation,
+  decryptStandInCard,
+} from "./helpers";
+
+/**
+ * End-to-end tests against the local MXE stand-in
+ *
+ * The program is built WITHOUT `mock-mpc`, so every computation is queued
+ * through `queue_mxe_computation` exactly as on Arcium. The stand-in
+ * (`programs/mxe_stand_in`) records it, runs the circuit in plaintext from
+ * a fixed seed when `executeComputation` is cranked, and invokes the
+ * program's callback instruction with the output.
+ *
+ * Run with: yarn test:stand-in (`yarn test` runs it after the mock-mpc suite)
+ */
+describe("MXE Stand-in (real MPC code path)", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.ArciumPoker as Program<ArciumPoker>;
+  const standIn = anchor.workspace.MxeStandIn as Program<MxeStandIn>;
+
+  const SEED = new Array(32).fill(7);
+
+  let gamePda: anchor.web3.PublicKey;
+  let deckPda: anchor.web3.PublicKey;
+  let players: anchor.web3.Keypair[];
+  let encryptionKeys: number[][];
+  let playerStates: anchor.web3.PublicKey[];
+
+  before(async () => {
+    await initMxeStandIn(standIn, SEED);
+    await initCircuitConfig(program, undefined, standIn.programId);
+  });
+
+  beforeEach(async () => {
+    const gameId = Date.now();
+    [gamePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("game"),
+        provider.wallet.publicKey.toBuffer(),
+        new anchor.BN(gameId).toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+    [deckPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("deck"), gamePda.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initializeGame(
+        new anchor.BN(gameId),
+        new anchor.BN(10),
+        new anchor.BN(20),
+        new anchor.BN(1000),
+        new anchor.BN(50000),
+        6
+      )
+      .accounts({ authority: provider.wallet.publicKey })
+      .rpc();
+
+    players = [];
+    encryptionKeys = [];
+    playerStates = [];
+    for (let i = 0; i < 2; i++) {
+      const player = anchor.web3.Keypair.generate();
+      players.push(player);
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(
+          player.publicKey,
+          2 * anchor.web3.LAMPORTS_PER_SOL
+        )
+      );
+
+      encryptionKeys.push(generateEncryptionPubkey());
+      await program.methods
+        .joinGame(new anchor.BN(10000), encryptionKeys[i])
+        .accounts({ game: gamePda, player: player.publicKey })
+        .signers([player])
+        .rpc();
+
+      playerStates.push(
+        anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("player"), gamePda.toBuffer(), player.publicKey.toBuffer()],
+          program.programId
+        )[0]
+      );
+    }
+
+    await commitAndRevealEntropy(program, gamePda, players, playerStates);
+  });
+
+  /**
+   * Start the hand; returns the queued shuffle's computation account, or
+   * null when a `mock-mpc` build shuffled locally instead
+   */
+  async function startHand(): Promise<anchor.web3.PublicKey | null> {
+    const offset = nextComputationOffset(await program.account.game.fetch(gamePda));
+    const mxe = mxeQueueAccounts(program, standIn.programId, "shuffle_deck", offset);
+
+    await program.methods
+      .startGame()
+      .accounts({
+        game: gamePda,
+        authority: provider.wallet.publicKey,
+        ...mxe,
+      })
+      .remainingAccounts(playerStateAccounts(playerStates))
+      .rpc();
+
+    const game = await program.account.game.fetch(gamePda);
+    if (game.deckInitialized) {
+      return null;
+    }
+    expect(game.pendingComputation.eq(offset)).to.be.true;
+    return mxe.computationAccount;
+  }
+
+  /**
+   * Queue `method` with the MXE accounts for `circuit` at the game's next
+   * computation offset, then run it on the stand-in
+   */
+  async function queueAndExecute(
+    circuit: string,
+    method: (mxe: ReturnType<typeof mxeQueueAccounts>) => Promise<string>
+  ): Promise<void> {
+    const offset = nextComputationOffset(await program.account.game.fetch(gamePda));
+    const mxe = mxeQueueAccounts(program, standIn.programId, circuit, offset);
+    await method(mxe);
+
+    const game = await program.account.game.fetch(gamePda);
+    expect(game.pendingComputation.eq(offset)).to.be.true;
+    await executeComputation(standIn, mxe.computationAccount);
+  }
+
+  /**
+   * Shuffle and deal hole cards to every seat through the stand-in; false
+   * when a `mock-mpc` build did it locally
+   */
+  async function shuffleAndDeal(): Promise<boolean> {
+    const shuffle = await startHand();
+    if (!shuffle) {
+      return false;
+    }
+    await executeComputation(standIn, shuffle);
+
+    // One deal_hole_cards computation per seat
+    for (let seat = 0; seat < players.length; seat++) {
+      await queueAndExecute("deal_hole_cards", (mxe) =>
+        program.methods
+          .dealCards()
+          .accounts({ game: gamePda, payer: provider.wallet.publicKey, ...mxe })
+          .remainingAccounts(playerStateAccounts(playerStates))
+          .rpc()
+      );
+    }
+    const game = await program.account.game.fetch(gamePda);
+    expect(game.dealRound).to.have.property("none");
+    return true;
+  }
+
+  /** A seat's hole cards, decrypted with its registered key */
+  async function holeCards(seat: number): Promise<number[]> {
+    const state = await program.account.playerState.fetch(playerStates[seat]);
+    expect(state.hasCards).to.be.true;
+    return state.holeCards.map((card) => decryptStandInCard(encryptionKeys[seat], card));
+  }
+
+  /**
+   * Public showdown: one reveal_hole_cards computation per seat still to
+   * show, until the last callback settles the hand
+   */
+  async function showHands(): Promise<void> {
+    while ("showdown" in (await program.account.game.fetch(gamePda)).stage) {
+      await queueAndExecute("reveal_hole_cards", (mxe) =>
+        program.methods
+          .executeShowdown()
+          .accounts({ game: gamePda, player: players[0].publicKey, ...mxe })
+          .remainingAccounts(playerStateAccounts(playerStates))
+          .signers([players[0]])
+          .rpc()
+      );
+    }
+  }
+
+  /** The hand is over and its pot paid out; no chips appear or vanish */
+  async function expectPotPaidOut(): Promise<void> {
+    const game = await program.account.game.fetch(gamePda);
+    expect(game.stage).to.have.property("finished");
+    expect(game.pot.isZero()).to.be.true;
+
+    let chips = new anchor.BN(0);
+    for (const pubkey of playerStates) {
+      chips = chips.add((await program.account.playerState.fetch(pubkey)).chipStack);
+    }
+    expect(chips.eq(new anchor.BN(10000 * players.length))).to.be.true;
+  }
+
+  /** Call or check for whoever is to act until the betting round closes */
+  async function playBettingRound(): Promise<void> {
+    for (;;) {
+      const game = await program.account.game.fetch(gamePda);
+      if (game.communityRevealPending > 0 || game.drawing || "showdown" in game.stage) {
+        return;
+      }
+
+      const seat = game.currentPlayerIndex;
+      const state = await program.account.playerState.fetch(playerStates[seat]);
+      const action = state.currentBet.lt(game.currentBet)
+        ? program.methods.playerCall()
+        : program.methods.playerCheck();
+      await action
+        .accounts({ game: gamePda, player: players[seat].publicKey })
+        .signers([players[seat]])
+        .rpc();
+    }
+  }
+
+  it("Shuffles through the MXE and stores the deck from the callback", async function () {
+    const computation = await startHand();
+    if (!computation) {
+      this.skip(); // mock-mpc build: no MXE computation to run
+    }
+
+    // Queued, but nothing runs until the cluster (the stand-in) executes it
+    let game = await program.account.game.fetch(gamePda);
+    expect(game.deckInitialized).to.be.false;
+
+    await executeComputation(standIn, computation);
+
+    game = await program.account.game.fetch(gamePda);
+    expect(game.deckInitialized).to.be.true;
+    expect(game.pendingComputation.isZero()).to.be.true;
+
+    const deck = await program.account.deck.fetch(deckPda);
+    expect(deck.initialized).to.be.true;
+    expect(deck.ciphertexts).to.have.length(52);
+  });
+
+  it("Delivers a computation's output only once", async function () {
+    const computation = await startHand();
+    if (!computation) {
+      this.skip();
+    }
+
+    await executeComputation(standIn, computation);
+
+    try {
+      await executeComputation(standIn, computation);
+      expect.fail("Second execution should be rejected");
+    } catch (error) {
+      expect(error.toString()).to.include("AlreadyExecuted");
+    }
+  });
+
+  it("Plays a private hand from shuffle to showdown through the MXE", async function () {
+    await program.methods
+      .configureShowdown(true, false)
+      .accounts({ game: gamePda, authority: provider.wallet.publicKey })
+      .rpc();
+
+    if (!(await shuffleAndDeal())) {
+      this.skip(); // mock-mpc build: no MXE computation to run
+    }
+
+    // Each seat decrypts its own cards with its registered key
+    const shown: number[] = [];
+    for (let seat = 0; seat < players.length; seat++) {
+      const cards = await holeCards(seat);
+      expect(cards).to.have.length(2);
+      shown.push(...cards);
+    }
+
+    // Pre-flop, flop, turn, river: betting closes, advanceStage reveals
+    let game;
+    for (const revealed of [3, 4, 5]) {
+      await playBettingRound();
+      await queueAndExecute("reveal_community", (mxe) =>
+        program.methods
+          .advanceStage()
+          .accounts({ game: gamePda, signer: provider.wallet.publicKey, ...mxe })
+          .rpc()
+      );
+      game = await program.account.game.fetch(gamePda);
+      expect(game.communityCardsRevealed).to.equal(revealed);
+      expect(game.communityRevealPending).to.equal(0);
+    }
+    await playBettingRound();
+
+    game = await program.account.game.fetch(gamePda);
+    expect(game.stage).to.have.property("showdown");
+    shown.push(...game.communityCards);
+    expect(shown.every((card) => card < 52)).to.be.true;
+    expect(new Set(shown).size).to.equal(shown.length);
+
+    const pot = game.pot;
+    await queueAndExecute("evaluate_showdown", (mxe) =>
+      program.methods
+        .executePrivateShowdown()
+        .accounts({ game: gamePda, signer: provider.wallet.publicKey, ...mxe })
+        .remainingAccounts(playerStateAccounts(playerStates))
+        .rpc()
+    );
+
+    // The callback pays the pot out
+    expect(pot.gtn(0)).to.be.true;
+    await expectPotPaidOut();
+  });
+
+  it("Plays a public showdown, revealing each seat's hand through the MXE", async function () {
+    if (!(await shuffleAndDeal())) {
+      this.skip(); // mock-mpc build: no MXE computation to run
+    }
+    const hands = [];
+    for (let seat = 0; seat < players.length; seat++) {
+      hands.push(await holeCards(seat));
+    }
+
+    for (let street = 0; street < 3; street++) {
+      await playBettingRound();
+      await queueAndExecute("reveal_community", (mxe) =>
+        program.methods
+          .advanceStage()
+          .accounts({ game: gamePda, signer: provider.wallet.publicKey, ...mxe })
+          .rpc()
+      );
+    }
+    await playBettingRound();
+
+    // Seat 0's reveal alone settles nothing
+    await queueAndExecute("reveal_hole_cards", (mxe) =>
+      program.methods
+        .executeShowdown()
+        .accounts({ game: gamePda, player: players[0].publicKey, ...mxe })
+        .remainingAccounts(playerStateAccounts(playerStates))
+        .signers([players[0]])
+        .rpc()
+    );
+    let game = await program.account.game.fetch(gamePda);
+    expect(game.stage).to.have.property("showdown");
+    expect(game.shownCards[0].slice(0, 2)).to.deep.equal(hands[0]);
+
+    // The remaining seat's callback evaluates and pays out
+    await showHands();
+    game = await program.account.game.fetch(gamePda);
+    for (let seat = 0; seat < players.length; seat++) {
+      expect(game.shownCards[seat].slice(0, 2)).to.deep.equal(hands[seat]);
+    }
+    await expectPotPaidOut();
+  });
+
+  it("Plays five-card draw: draws through the MXE, then shows the new hands", async function () {
+    await program.methods
+      .configureGame({ fiveCardDraw: {} }, null, null, null)
+      .accounts({ game: gamePda, authority: provider.wallet.publicKey })
+      .rpc();
+
+    if (!(await shuffleAndDeal())) {
+      this.skip(); // mock-mpc build: no MXE computation to run
+    }
+    const dealt = await holeCards(0);
+    expect(dealt).to.have.length(5);
+
+    // Pre-draw betting closes and opens the draw
+    await playBettingRound();
+    let game = await program.account.game.fetch(gamePda);
+    expect(game.drawing).to.be.true;
+
+    // Everyone throws their first two cards, in turn
+    for (let drawn = 0; drawn < players.length; drawn++) {
+      game = await program.account.game.fetch(gamePda);
+      const seat = game.currentPlayerIndex;
+      await queueAndExecute("draw_cards", (mxe) =>
+        program.methods
+          .drawCards(Buffer.from([0, 1]), new Array(32).fill(0))
+          .accounts({ game: gamePda, player: players[seat].publicKey, ...mxe })
+          .signers([players[seat]])
+          .rpc()
+      );
+    }
+    game = await program.account.game.fetch(gamePda);
+    expect(game.drawing).to.be.false;
+    expect(game.drawsCompleted).to.equal(1);
+
+    // The kept cards stay put; the replacements are new cards
+    const hands = [];
+    for (let seat = 0; seat < players.length; seat++) {
+      hands.push(await holeCards(seat));
+    }
+    expect(hands[0].slice(2)).to.deep.equal(dealt.slice(2));
+    expect(hands[0].slice(0, 2)).to.not.include.members(dealt.slice(0, 2));
+    expect(new Set(hands.flat()).size).to.equal(5 * players.length);
+
+    // Post-draw betting goes straight to the showdown
+    await playBettingRound();
+    game = await program.account.game.fetch(gamePda);
+    expect(game.stage).to.have.property("showdown");
+
+    await showHands();
+    game = await program.account.game.fetch(gamePda);
+    for (let seat = 0; seat < players.length; seat++) {
+      expect(game.shownCards[seat].slice(0, 5)).to.deep.equal(hands[seat]);
+    }
+    await expectPotPaidOut();
+  });
+});
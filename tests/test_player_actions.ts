@@ -2,7 +2,7 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { ArciumPoker } from "../target/types/arcium_poker";
 import { expect } from "chai";
-import { getGamePda, getPlayerStatePda, airdropSol } from "./helpers";
+import { getGamePda, getPlayerStatePda, airdropSol, generateEncryptionPubkey } from "./helpers";
 
 describe("Player Actions Tests", () => {
   const provider = anchor.AnchorProvider.env();
@@ -51,7 +51,7 @@ describe("Player Actions Tests", () => {
       const buyIn = new anchor.BN(7500);
 
       await program.methods
-        .joinGame(buyIn)
+        .joinGame(buyIn, generateEncryptionPubkey())
         .accounts({
           game: gamePda,
           player: player.publicKey,
@@ -87,7 +87,7 @@ describe("Player Actions Tests", () => {
 
       try {
         await program.methods
-          .joinGame(new anchor.BN(1000)) // Less than min_buy_in (5000)
+          .joinGame(new anchor.BN(1000), generateEncryptionPubkey()) // Less than min_buy_in (5000)
           .accounts({
             game: gamePda,
             playerState: playerStatePda,
@@ -122,7 +122,7 @@ describe("Player Actions Tests", () => {
 
       try {
         await program.methods
-          .joinGame(new anchor.BN(15000)) // More than max_buy_in (10000)
+          .joinGame(new anchor.BN(15000), generateEncryptionPubkey()) // More than max_buy_in (10000)
           .accounts({
             game: gamePda,
             playerState: playerStatePda,
@@ -158,7 +158,7 @@ describe("Player Actions Tests", () => {
         );
 
         await program.methods
-          .joinGame(new anchor.BN(7500))
+          .joinGame(new anchor.BN(7500), generateEncryptionPubkey())
           .accounts({
             game: gamePda,
             playerState: playerStatePda,
@@ -188,7 +188,7 @@ describe("Player Actions Tests", () => {
 
       try {
         await program.methods
-          .joinGame(new anchor.BN(7500))
+          .joinGame(new anchor.BN(7500), generateEncryptionPubkey())
           .accounts({
             game: gamePda,
             playerState: playerStatePda,
@@ -223,7 +223,7 @@ describe("Player Actions Tests", () => {
 
       // First join
       await program.methods
-        .joinGame(new anchor.BN(7500))
+        .joinGame(new anchor.BN(7500), generateEncryptionPubkey())
         .accounts({
           game: gamePda,
           playerState: playerStatePda,
@@ -236,7 +236,7 @@ describe("Player Actions Tests", () => {
       // Try to join again
       try {
         await program.methods
-          .joinGame(new anchor.BN(7500))
+          .joinGame(new anchor.BN(7500), generateEncryptionPubkey())
           .accounts({
             game: gamePda,
             playerState: playerStatePda,
@@ -276,7 +276,7 @@ describe("Player Actions Tests", () => {
       );
 
       await program.methods
-        .joinGame(new anchor.BN(7500))
+        .joinGame(new anchor.BN(7500), generateEncryptionPubkey())
         .accounts({
           game: gamePda,
           playerState: playerStatePda,
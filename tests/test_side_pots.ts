@@ -2,12 +2,17 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { ArciumPoker } from "../target/types/arcium_poker";
 import { expect } from "chai";
+import { generateEncryptionPubkey, commitAndRevealEntropy, playerStateAccounts, initCircuitConfig } from "./helpers";
 
 describe("Side Pot Tests", () => {
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
 
   const program = anchor.workspace.ArciumPoker as Program<ArciumPoker>;
+
+  before(async () => {
+    await initCircuitConfig(program);
+  });
   
   let gamePda: anchor.web3.PublicKey;
   let gameId: number;
@@ -65,7 +70,7 @@ describe("Side Pot Tests", () => {
         playerStates.push(playerStatePda);
 
         await program.methods
-          .joinGame(new anchor.BN(buyIns[i]))
+          .joinGame(new anchor.BN(buyIns[i]), generateEncryptionPubkey())
           .accounts({
             game: gamePda,
             player: player.publicKey,
@@ -75,13 +80,14 @@ describe("Side Pot Tests", () => {
       }
 
       // Start game
-      const playerEntropy = Array(3).fill(0).map(() => Array(32).fill(0));
+      await commitAndRevealEntropy(program, gamePda, players, playerStates);
       await program.methods
-        .startGame(playerEntropy)
+        .startGame()
         .accounts({
           game: gamePda,
           authority: provider.wallet.publicKey,
         })
+        .remainingAccounts(playerStateAccounts(playerStates))
         .rpc();
 
       // Player 1 (2000 chips) goes all-in
@@ -166,7 +172,7 @@ describe("Side Pot Tests", () => {
         playerStates.push(playerStatePda);
 
         await program.methods
-          .joinGame(new anchor.BN(buyIns[i]))
+          .joinGame(new anchor.BN(buyIns[i]), generateEncryptionPubkey())
           .accounts({
             game: gamePda,
             player: player.publicKey,
@@ -176,13 +182,14 @@ describe("Side Pot Tests", () => {
       }
 
       // Start game
-      const playerEntropy = Array(4).fill(0).map(() => Array(32).fill(0));
+      await commitAndRevealEntropy(program, gamePda, players, playerStates);
       await program.methods
-        .startGame(playerEntropy)
+        .startGame()
         .accounts({
           game: gamePda,
           authority: provider.wallet.publicKey,
         })
+        .remainingAccounts(playerStateAccounts(playerStates))
         .rpc();
 
       // All 4 players go all-in
@@ -237,7 +244,7 @@ describe("Side Pot Tests", () => {
         playerStates.push(playerStatePda);
 
         await program.methods
-          .joinGame(new anchor.BN(buyIns[i]))
+          .joinGame(new anchor.BN(buyIns[i]), generateEncryptionPubkey())
           .accounts({
             game: gamePda,
             player: player.publicKey,
@@ -246,13 +253,14 @@ describe("Side Pot Tests", () => {
           .rpc();
       }
 
-      const playerEntropy = Array(3).fill(0).map(() => Array(32).fill(0));
+      await commitAndRevealEntropy(program, gamePda, players, playerStates);
       await program.methods
-        .startGame(playerEntropy)
+        .startGame()
         .accounts({
           game: gamePda,
           authority: provider.wallet.publicKey,
         })
+        .remainingAccounts(playerStateAccounts(playerStates))
         .rpc();
 
       // Two players with equal stacks go all-in